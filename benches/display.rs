@@ -0,0 +1,39 @@
+//! The cost of a frame displaying an interface, measured on a headless app.
+//!
+//! Like the examples, this needs a GPU adapter to create the render device.
+
+use bevy::prelude::*;
+use bevy::winit::WinitPlugin;
+use bevy_iced::iced::widget::{button, column, text};
+use bevy_iced::{IcedContext, IcedPlugin};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+#[derive(Event, Clone)]
+struct UiMessage;
+
+fn ui_system(mut ctx: IcedContext<UiMessage>) {
+    ctx.display(column((0..100).map(|i| {
+        button(text(format!("Button {i}")))
+            .on_press(UiMessage)
+            .into()
+    })));
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins.build().disable::<WinitPlugin>())
+        .add_plugins(IcedPlugin::default())
+        .add_event::<UiMessage>()
+        .add_systems(Update, ui_system);
+    app.finish();
+    app.cleanup();
+    app
+}
+
+fn display(c: &mut Criterion) {
+    let mut app = app();
+    c.bench_function("display 100 buttons", |b| b.iter(|| app.update()));
+}
+
+criterion_group!(benches, display);
+criterion_main!(benches);
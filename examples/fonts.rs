@@ -22,6 +22,7 @@ pub fn main() {
                 default_font: ALPHAPROTA_FONT,
                 ..Default::default()
             },
+            ..Default::default()
         })
         .add_event::<UiMessage>()
         .add_systems(Update, ui_system)
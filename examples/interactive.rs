@@ -48,11 +48,12 @@ pub fn main() {
             text: "Welcome to Iced!".to_owned(),
         })
         .insert_resource(IcedSettings {
-            scale_factor: None,
-            theme: iced::Theme::Light,
+            theme: iced::Theme::Light.into(),
             style: Style {
                 text_color: iced::Color::from_rgb(0.0, 1.0, 1.0),
-            },
+            }
+            .into(),
+            ..Default::default()
         })
         .add_systems(Startup, build_program)
         .add_systems(
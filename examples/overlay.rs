@@ -0,0 +1,54 @@
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy::window::CompositeAlphaMode;
+use bevy_iced::iced::widget::{button, column, container, text};
+use bevy_iced::iced::Length;
+use bevy_iced::{Compositing, IcedContext, IcedPlugin, IcedSettings};
+
+#[derive(Event, Clone)]
+pub enum UiMessage {
+    Quit,
+}
+
+pub fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                transparent: true,
+                decorations: false,
+                composite_alpha_mode: CompositeAlphaMode::PostMultiplied,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins(IcedPlugin::default())
+        .add_event::<UiMessage>()
+        .insert_resource(IcedSettings {
+            compositing: Compositing::Transparent,
+            ..Default::default()
+        })
+        .add_systems(Update, (ui_system, quit_system))
+        .run();
+}
+
+fn quit_system(mut messages: EventReader<UiMessage>, mut exit: EventWriter<AppExit>) {
+    if messages.read().any(|msg| matches!(msg, UiMessage::Quit)) {
+        exit.send(AppExit);
+    }
+}
+
+fn ui_system(time: Res<Time>, mut ctx: IcedContext<UiMessage>) {
+    ctx.display(
+        container(column!(
+            text(format!(
+                "Overlay running for {:.0} seconds",
+                time.elapsed_seconds()
+            )),
+            button(text("Quit")).on_press(UiMessage::Quit),
+        ))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x()
+        .center_y(),
+    );
+}
@@ -0,0 +1,54 @@
+//! Build for the web with
+//! `cargo build --example web --target wasm32-unknown-unknown`
+//! and serve the output with `wasm-bindgen` or `wasm-server-runner`.
+
+use bevy::prelude::*;
+use bevy_iced::iced::widget::{button, column, text};
+use bevy_iced::iced::Alignment;
+use bevy_iced::{IcedContext, IcedPlugin};
+
+#[derive(Clone, Event)]
+pub enum UiMessage {
+    Tapped,
+}
+
+#[derive(Resource, Default)]
+pub struct Taps(u32);
+
+pub fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                canvas: Some("#bevy".to_owned()),
+                prevent_default_event_handling: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins(IcedPlugin::default())
+        .add_event::<UiMessage>()
+        .init_resource::<Taps>()
+        .add_systems(Update, (tap_system, ui_system))
+        .run();
+}
+
+fn tap_system(mut messages: EventReader<UiMessage>, mut taps: ResMut<Taps>) {
+    for msg in messages.read() {
+        match msg {
+            UiMessage::Tapped => taps.0 += 1,
+        }
+    }
+}
+
+fn ui_system(mut ctx: IcedContext<UiMessage>, taps: Res<Taps>, touches: Res<Touches>) {
+    let fingers = touches.iter().count();
+    ctx.display(
+        column!(
+            text(format!("Tapped {} times", taps.0)),
+            text(format!("{fingers} finger(s) on the screen")),
+            button(text("Tap me")).on_press(UiMessage::Tapped),
+        )
+        .align_items(Alignment::Center)
+        .spacing(10),
+    );
+}
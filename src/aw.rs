@@ -0,0 +1,4 @@
+// `iced_aw` widgets default to the `iced` crate's Theme and Renderer, which are the same
+// types `bevy_iced` renders with, so they can be used directly in `IcedContext::display`.
+
+pub use iced_aw::*;
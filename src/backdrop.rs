@@ -0,0 +1,446 @@
+use std::any::Any;
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use bevy_utils::tracing::warn;
+
+use iced_core::widget::tree::{self, Tree};
+use iced_core::widget::{Id, Operation, Widget};
+use iced_core::{
+    event, layout, mouse, overlay, renderer, Clipboard, Element, Event, Layout, Length, Rectangle,
+    Shell, Size, Vector,
+};
+use iced_wgpu::wgpu;
+use iced_wgpu::wgpu::util::DeviceExt;
+use iced_wgpu::wgpu::TextureFormat;
+
+// The most regions blurred in a frame, see `backdrop.wgsl`.
+const MAX_REGIONS: usize = 16;
+
+// Whether going over `MAX_REGIONS` was logged.
+static WARNED_MAX_REGIONS: AtomicBool = AtomicBool::new(false);
+
+/// A widget blurring the scene behind its content, for frosted glass panels.
+///
+/// The content is laid out and drawn as usual: give it a translucent background (e.g. with
+/// a [`PanelStyle`](crate::widgets::PanelStyle)) for the blurred scene to show through.
+///
+/// The blur is applied to what the cameras rendered, not to the interfaces displayed below
+/// this one, and covers the rectangular bounds of the content. It requires presenting to
+/// [`PresentTarget::Camera`](crate::PresentTarget::Camera), since the window surface can't
+/// be sampled, and isn't applied inside scrollables, where the position of the content
+/// isn't known.
+///
+/// At most 16 backdrops are blurred per frame, counting the interfaces drawn below and above
+/// `bevy_ui` separately: the ones displayed after them are drawn without blur, and a
+/// warning is logged.
+pub struct Backdrop<'a, Message, Theme, Renderer> {
+    content: Element<'a, Message, Theme, Renderer>,
+    radius: f32,
+}
+
+/// Creates a [`Backdrop`] blurring the scene behind `content` by `radius` logical pixels.
+pub fn backdrop_blur<'a, Message, Theme, Renderer>(
+    content: impl Into<Element<'a, Message, Theme, Renderer>>,
+    radius: f32,
+) -> Backdrop<'a, Message, Theme, Renderer> {
+    Backdrop {
+        content: content.into(),
+        radius,
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Backdrop<'a, Message, Theme, Renderer>
+where
+    Renderer: iced_core::Renderer,
+{
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.content.as_widget().size_hint()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content.as_widget().layout(tree, renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content
+            .as_widget()
+            .draw(tree, renderer, theme, style, layout, cursor, viewport);
+    }
+
+    fn tag(&self) -> tree::Tag {
+        self.content.as_widget().tag()
+    }
+
+    fn state(&self) -> tree::State {
+        self.content.as_widget().state()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.content.as_widget().children()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        self.content.as_widget().diff(tree);
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        let mut region = BackdropRegion {
+            bounds: layout.bounds(),
+            radius: self.radius,
+        };
+        operation.custom(&mut region, None);
+        self.content
+            .as_widget()
+            .operate(tree, layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        self.content.as_widget_mut().on_event(
+            tree, event, layout, cursor, renderer, clipboard, shell, viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content
+            .as_widget()
+            .mouse_interaction(tree, layout, cursor, viewport, renderer)
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(tree, layout, renderer, translation)
+    }
+}
+
+impl<'a, Message: 'a, Theme: 'a, Renderer> From<Backdrop<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Renderer: iced_core::Renderer + 'a,
+{
+    fn from(backdrop: Backdrop<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(backdrop)
+    }
+}
+
+// An area to blur behind an interface, in logical pixels.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct BackdropRegion {
+    pub bounds: Rectangle,
+    pub radius: f32,
+}
+
+// An operation collecting the regions of the `Backdrop`s of an interface.
+#[derive(Default)]
+pub(crate) struct FindBackdrops {
+    pub regions: Vec<BackdropRegion>,
+    // Whether the next container is the content of a scrollable.
+    entering_scrollable: bool,
+    scrollables: usize,
+}
+
+impl<T> Operation<T> for FindBackdrops {
+    fn container(
+        &mut self,
+        _id: Option<&Id>,
+        _bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+        let scrollable = std::mem::take(&mut self.entering_scrollable);
+        self.scrollables += usize::from(scrollable);
+        operate_on_children(self);
+        self.scrollables -= usize::from(scrollable);
+    }
+
+    // Scrollables don't translate the layout of their content, so it can't be trusted.
+    fn scrollable(
+        &mut self,
+        _state: &mut dyn iced_core::widget::operation::Scrollable,
+        _id: Option<&Id>,
+        _bounds: Rectangle,
+        _translation: Vector,
+    ) {
+        self.entering_scrollable = true;
+    }
+
+    fn custom(&mut self, state: &mut dyn Any, _id: Option<&Id>) {
+        if let (Some(region), 0) = (state.downcast_ref::<BackdropRegion>(), self.scrollables) {
+            self.regions.push(*region);
+        }
+    }
+}
+
+// The passes blurring the backdrop regions of a frame, into the texture interfaces are then
+// presented to.
+pub(crate) struct BlurPass {
+    format: TextureFormat,
+    size: wgpu::Extent3d,
+    pipeline: wgpu::RenderPipeline,
+    layout: wgpu::BindGroupLayout,
+    // The scene blurred horizontally, which the vertical pass reads.
+    intermediate: wgpu::TextureView,
+}
+
+impl BlurPass {
+    // Get the pass in `slot`, (re)creating what is needed for `format` and `size`.
+    pub fn prepare<'a>(
+        slot: &'a mut Option<Self>,
+        device: &wgpu::Device,
+        format: TextureFormat,
+        size: wgpu::Extent3d,
+    ) -> &'a Self {
+        if slot.as_ref().is_some_and(|pass| pass.format != format) {
+            *slot = None;
+        }
+        let pass = slot.get_or_insert_with(|| Self::new(device, format, size));
+        if pass.size != size {
+            pass.intermediate = Self::texture(device, format, size);
+            pass.size = size;
+        }
+        pass
+    }
+
+    fn new(device: &wgpu::Device, format: TextureFormat, size: wgpu::Extent3d) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("bevy_iced backdrop shader"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("backdrop.wgsl"))),
+        });
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("bevy_iced backdrop bind group layout"),
+            entries: &[
+                texture_entry(0),
+                texture_entry(1),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("bevy_iced backdrop pipeline layout"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("bevy_iced backdrop pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+        Self {
+            format,
+            size,
+            pipeline,
+            layout,
+            intermediate: Self::texture(device, format, size),
+        }
+    }
+
+    fn texture(
+        device: &wgpu::Device,
+        format: TextureFormat,
+        size: wgpu::Extent3d,
+    ) -> wgpu::TextureView {
+        device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some("bevy_iced backdrop texture"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default())
+    }
+
+    // Write `source` to `destination`, blurred inside `regions`, which are scaled by
+    // `scale_factor` to physical pixels.
+    pub fn run(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        source: &wgpu::TextureView,
+        destination: &wgpu::TextureView,
+        regions: &[BackdropRegion],
+        scale_factor: f32,
+    ) {
+        if regions.len() > MAX_REGIONS && !WARNED_MAX_REGIONS.swap(true, Ordering::Relaxed) {
+            warn!(
+                "{} backdrops were displayed in a frame, only the first {MAX_REGIONS} are blurred",
+                regions.len()
+            );
+        }
+        self.pass(
+            device,
+            encoder,
+            [source, source],
+            &self.intermediate,
+            [1.0, 0.0],
+            regions,
+            scale_factor,
+        );
+        self.pass(
+            device,
+            encoder,
+            [source, &self.intermediate],
+            destination,
+            [0.0, 1.0],
+            regions,
+            scale_factor,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn pass(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        [source, input]: [&wgpu::TextureView; 2],
+        target: &wgpu::TextureView,
+        direction: [f32; 2],
+        regions: &[BackdropRegion],
+        scale_factor: f32,
+    ) {
+        // The layout of `Params` in `backdrop.wgsl`.
+        let mut params = Vec::with_capacity(16 + MAX_REGIONS * 32);
+        params.extend(direction.iter().flat_map(|v| v.to_le_bytes()));
+        params.extend((regions.len().min(MAX_REGIONS) as u32).to_le_bytes());
+        params.extend(0u32.to_le_bytes());
+        for region in regions.iter().take(MAX_REGIONS) {
+            let bounds = region.bounds * scale_factor;
+            let rect = [
+                bounds.x,
+                bounds.y,
+                bounds.x + bounds.width,
+                bounds.y + bounds.height,
+            ];
+            let radius = [region.radius * scale_factor, 0.0, 0.0, 0.0];
+            params.extend(rect.iter().chain(&radius).flat_map(|v| v.to_le_bytes()));
+        }
+        params.resize(16 + MAX_REGIONS * 32, 0);
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("bevy_iced backdrop params"),
+            contents: &params,
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("bevy_iced backdrop bind group"),
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(source),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(input),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: buffer.as_entire_binding(),
+                },
+            ],
+        });
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("bevy_iced backdrop pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+}
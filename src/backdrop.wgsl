@@ -0,0 +1,65 @@
+// One pass of a separable gaussian blur, applied inside the backdrop regions only.
+
+struct Region {
+    // The bounds of the region, as min x, min y, max x, max y in physical pixels.
+    rect: vec4<f32>,
+    // The blur radius in physical pixels, in x.
+    radius: vec4<f32>,
+}
+
+struct Params {
+    // (1, 0) for the horizontal pass, (0, 1) for the vertical one.
+    direction: vec2<f32>,
+    count: u32,
+    _padding: u32,
+    regions: array<Region, 16>,
+}
+
+// The scene, copied as is outside of the regions.
+@group(0) @binding(0) var source: texture_2d<f32>;
+// The texture blurred in this pass.
+@group(0) @binding(1) var input: texture_2d<f32>;
+@group(0) @binding(2) var<uniform> params: Params;
+
+// A triangle covering the whole target.
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+}
+
+// The blur radius at `position`, or 0 outside of the regions. The horizontal pass covers
+// the rows the vertical pass reads around the regions as well.
+fn blur_radius(position: vec2<f32>) -> f32 {
+    var radius = 0.0;
+    for (var i = 0u; i < params.count; i++) {
+        let region = params.regions[i];
+        let margin = params.direction.yx * region.radius.x;
+        if all(position >= region.rect.xy - margin) && all(position < region.rect.zw + margin) {
+            radius = region.radius.x;
+        }
+    }
+    return radius;
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let coords = vec2<i32>(position.xy);
+    let radius = blur_radius(position.xy);
+    if radius < 0.5 {
+        return textureLoad(source, coords, 0);
+    }
+    let size = vec2<i32>(textureDimensions(input)) - 1;
+    let sigma = radius / 2.0;
+    let taps = i32(ceil(radius));
+    let direction = vec2<i32>(params.direction);
+    var color = vec4<f32>(0.0);
+    var total = 0.0;
+    for (var i = -taps; i <= taps; i++) {
+        let weight = exp(-f32(i * i) / (2.0 * sigma * sigma));
+        let sample = clamp(coords + direction * i, vec2<i32>(0), size);
+        color += textureLoad(input, sample, 0) * weight;
+        total += weight;
+    }
+    return color / total;
+}
@@ -0,0 +1,91 @@
+use std::marker::PhantomData;
+
+use bevy_app::{App, Plugin, Update};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::event::Event;
+use bevy_ecs::prelude::EventWriter;
+use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_input::keyboard::KeyCode;
+use bevy_input::ButtonInput;
+use bevy_render::render_asset::RenderAssetUsages;
+use bevy_render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy_render::texture::Image;
+use bevy_utils::tracing::warn;
+
+/// Turns images pasted from the system clipboard into messages of type `M`.
+///
+/// Insert this resource while a widget or region accepting images is active (for example
+/// while it is hovered or selected): pressing `Ctrl+V` (`Cmd+V` on macOS) then adds the
+/// clipboard image to [`Assets<Image>`] and sends the message built by the callback.
+/// Remove it when the region is inactive so regular text pasting is unaffected.
+///
+/// Requires [`ClipboardImagePlugin<M>`] to be added to the app.
+#[derive(Resource)]
+pub struct PasteImageTarget<M> {
+    on_paste: Box<dyn Fn(Handle<Image>) -> M + Send + Sync>,
+}
+
+impl<M> PasteImageTarget<M> {
+    /// Create a target producing messages with `on_paste`.
+    pub fn new(on_paste: impl Fn(Handle<Image>) -> M + Send + Sync + 'static) -> Self {
+        Self {
+            on_paste: Box::new(on_paste),
+        }
+    }
+}
+
+/// Adds clipboard image pasting for the message type `M`. See [`PasteImageTarget`].
+pub struct ClipboardImagePlugin<M>(PhantomData<fn() -> M>);
+
+impl<M> Default for ClipboardImagePlugin<M> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<M: Event> Plugin for ClipboardImagePlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, paste_image::<M>);
+    }
+}
+
+fn paste_image<M: Event>(
+    target: Option<Res<PasteImageTarget<M>>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut images: ResMut<Assets<Image>>,
+    mut messages: EventWriter<M>,
+) {
+    let Some(target) = target else {
+        return;
+    };
+    let modifier = keys.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    if !modifier || !keys.just_pressed(KeyCode::KeyV) {
+        return;
+    }
+
+    let data = match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_image()) {
+        Ok(data) => data,
+        Err(arboard::Error::ContentNotAvailable) => return,
+        Err(err) => {
+            warn!("Failed to read an image from the clipboard: {err}");
+            return;
+        }
+    };
+    let image = Image::new(
+        Extent3d {
+            width: data.width as u32,
+            height: data.height as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data.bytes.into_owned(),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    messages.send((target.on_paste)(images.add(image)));
+}
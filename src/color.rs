@@ -0,0 +1,78 @@
+use bevy_render::color::Color as BevyColor;
+use bevy_utils::tracing::warn;
+use iced_core::Color;
+use iced_wgpu::wgpu::TextureFormat;
+
+use crate::compat;
+
+/// Convert a Bevy color to an Iced one.
+///
+/// Iced colors hold gamma-encoded sRGB components, like [`BevyColor::rgba`]: the color is
+/// converted to sRGB first, whatever space it was specified in.
+pub fn to_iced(color: BevyColor) -> Color {
+    let [r, g, b, a] = color.as_rgba_f32();
+    Color::from_rgba(r, g, b, a)
+}
+
+/// Convert an Iced color to a Bevy one, in sRGB.
+pub fn to_bevy(color: Color) -> BevyColor {
+    BevyColor::rgba(color.r, color.g, color.b, color.a)
+}
+
+/// Convert linear RGBA components, as computed by shaders or read from
+/// [`BevyColor::as_linear_rgba_f32`], to an Iced color.
+pub fn from_linear(linear: [f32; 4]) -> Color {
+    to_iced(BevyColor::rgba_linear(
+        linear[0], linear[1], linear[2], linear[3],
+    ))
+}
+
+/// The components of an Iced color in linear space.
+pub fn to_linear(color: Color) -> [f32; 4] {
+    color.into_linear()
+}
+
+/// The space color values are written or stored in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Gamma-encoded sRGB.
+    Srgb,
+    /// Linear sRGB.
+    Linear,
+}
+
+/// The color space Iced renders interfaces in.
+///
+/// This is [`ColorSpace::Linear`], unless the `web-colors` feature of Iced is enabled by a
+/// crate of the dependency graph, in which case colors are written as they are specified.
+pub fn output_color_space() -> ColorSpace {
+    compat::output_color_space()
+}
+
+/// The color space that values written to a texture of `format` must be in for the texture
+/// to be displayed correctly.
+///
+/// sRGB formats encode linear values when they are written, and the floating point formats
+/// of HDR cameras hold linear values until tonemapping. Other formats store what is written
+/// and are displayed as sRGB.
+pub fn target_color_space(format: TextureFormat) -> ColorSpace {
+    match format {
+        TextureFormat::Rgba16Float | TextureFormat::Rgba32Float | TextureFormat::Rg11b10Float => {
+            ColorSpace::Linear
+        }
+        format if format.is_srgb() => ColorSpace::Linear,
+        _ => ColorSpace::Srgb,
+    }
+}
+
+// Interfaces presented to a target of another color space than the output of Iced look too
+// dark (linear values displayed as sRGB) or washed out (sRGB values encoded again).
+pub(crate) fn check_target(format: TextureFormat) {
+    let output = output_color_space();
+    if target_color_space(format) != output {
+        warn!(
+            "Iced: interfaces are rendered in {output:?} colors, which a {format:?} target \
+             displays incorrectly"
+        );
+    }
+}
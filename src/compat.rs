@@ -0,0 +1,308 @@
+// Calls into the Iced renderer whose signatures change between Iced releases, kept
+// together in one place.
+
+use iced_core::{Background, Rectangle, Transformation};
+use iced_wgpu::wgpu;
+use iced_wgpu::wgpu::TextureFormat;
+use iced_widget::graphics::Viewport;
+
+use crate::color::ColorSpace;
+use crate::{iced, IcedRenderStats, Renderer};
+
+pub fn new_backend(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    settings: iced::Settings,
+    format: TextureFormat,
+) -> iced_wgpu::Backend {
+    crate::color::check_target(format);
+    iced_wgpu::Backend::new(device, queue, settings, format)
+}
+
+pub fn output_color_space() -> ColorSpace {
+    if iced_widget::graphics::color::GAMMA_CORRECTION {
+        ColorSpace::Linear
+    } else {
+        ColorSpace::Srgb
+    }
+}
+
+pub fn new_renderer(backend: iced_wgpu::Backend, settings: &iced::Settings) -> Renderer {
+    Renderer::Wgpu(iced_wgpu::Renderer::new(
+        backend,
+        settings.default_font,
+        settings.default_text_size,
+    ))
+}
+
+pub type Primitive = iced_wgpu::Primitive;
+
+/// Copy the primitives drawn into `renderer` since it was last cleared.
+pub fn primitives(renderer: &mut Renderer) -> Vec<Primitive> {
+    let Renderer::Wgpu(renderer) = renderer else {
+        return Vec::new();
+    };
+    renderer.with_primitives(|_, primitives| primitives.to_vec())
+}
+
+/// Group `primitives` behind a reference count, so that copies of them are cheap.
+pub fn share(primitives: Vec<Primitive>) -> Primitive {
+    Primitive::Cache {
+        content: std::sync::Arc::new(Primitive::Group { primitives }),
+    }
+}
+
+/// Count the primitives in `primitives`, including nested ones, into `stats`.
+pub fn count_primitives(primitives: &[Primitive], stats: &mut IcedRenderStats) {
+    for primitive in primitives {
+        match primitive {
+            Primitive::Quad { .. } => stats.quads += 1,
+            Primitive::Text { .. } | Primitive::Paragraph { .. } | Primitive::Editor { .. } => {
+                stats.text_sections += 1
+            }
+            Primitive::Group { primitives } => count_primitives(primitives, stats),
+            Primitive::Clip { content, .. } | Primitive::Transform { content, .. } => {
+                count_primitives(std::slice::from_ref(content), stats)
+            }
+            Primitive::Cache { content } => {
+                count_primitives(std::slice::from_ref(&**content), stats)
+            }
+            _ => stats.other_primitives += 1,
+        }
+    }
+}
+
+/// Collect the bounds of the visible parts of `primitives` (filled or bordered quads, text,
+/// images...) into `regions`, transformed by `transformation` and clipped to `clip`.
+pub fn visible_regions(
+    primitives: &[Primitive],
+    transformation: Transformation,
+    clip: Option<Rectangle>,
+    regions: &mut Vec<Rectangle>,
+) {
+    for primitive in primitives {
+        let bounds = match primitive {
+            Primitive::Quad {
+                bounds,
+                background,
+                border,
+                ..
+            } => {
+                let filled = match background {
+                    Background::Color(color) => color.a > 0.0,
+                    Background::Gradient(_) => true,
+                };
+                let bordered = border.width > 0.0 && border.color.a > 0.0;
+                (filled || bordered).then_some(*bounds)
+            }
+            Primitive::Text { clip_bounds, .. }
+            | Primitive::Paragraph { clip_bounds, .. }
+            | Primitive::Editor { clip_bounds, .. } => Some(*clip_bounds),
+            Primitive::Image { bounds, .. } | Primitive::Svg { bounds, .. } => Some(*bounds),
+            Primitive::Group { primitives } => {
+                visible_regions(primitives, transformation, clip, regions);
+                None
+            }
+            Primitive::Clip { bounds, content } => {
+                let bounds = *bounds * transformation;
+                let clip = match clip {
+                    Some(clip) => clip.intersection(&bounds),
+                    None => Some(bounds),
+                };
+                if let Some(clip) = clip {
+                    visible_regions(
+                        std::slice::from_ref(content),
+                        transformation,
+                        Some(clip),
+                        regions,
+                    );
+                }
+                None
+            }
+            Primitive::Transform {
+                transformation: inner,
+                content,
+            } => {
+                let transformation = transformation * *inner;
+                visible_regions(std::slice::from_ref(content), transformation, clip, regions);
+                None
+            }
+            Primitive::Cache { content } => {
+                visible_regions(
+                    std::slice::from_ref(&**content),
+                    transformation,
+                    clip,
+                    regions,
+                );
+                None
+            }
+            _ => None,
+        };
+        let Some(bounds) = bounds.map(|bounds| bounds * transformation) else {
+            continue;
+        };
+        match clip {
+            Some(clip) => regions.extend(clip.intersection(&bounds)),
+            None => regions.push(bounds),
+        }
+    }
+}
+
+/// Present `primitives` to `view`, drawing over its current contents unless a
+/// `clear_color` is given.
+#[allow(clippy::too_many_arguments)]
+pub fn present(
+    backend: &mut iced_wgpu::Backend,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    encoder: &mut wgpu::CommandEncoder,
+    clear_color: Option<iced_core::Color>,
+    format: TextureFormat,
+    view: &wgpu::TextureView,
+    primitives: &[Primitive],
+    viewport: &Viewport,
+    overlay: &[String],
+) {
+    backend.present(
+        device,
+        queue,
+        encoder,
+        clear_color,
+        format,
+        view,
+        primitives,
+        viewport,
+        overlay,
+    );
+}
+
+#[cfg(feature = "tiny_skia")]
+pub type SoftwarePrimitive = iced_tiny_skia::Primitive;
+
+/// Convert `primitive` for the software renderer. Meshes and custom primitives have no
+/// software equivalent, and are dropped.
+#[cfg(feature = "tiny_skia")]
+pub fn to_software(primitive: &Primitive) -> Option<SoftwarePrimitive> {
+    Some(match primitive {
+        Primitive::Text {
+            content,
+            bounds,
+            color,
+            size,
+            line_height,
+            font,
+            horizontal_alignment,
+            vertical_alignment,
+            shaping,
+            clip_bounds,
+        } => SoftwarePrimitive::Text {
+            content: content.clone(),
+            bounds: *bounds,
+            color: *color,
+            size: *size,
+            line_height: *line_height,
+            font: *font,
+            horizontal_alignment: *horizontal_alignment,
+            vertical_alignment: *vertical_alignment,
+            shaping: *shaping,
+            clip_bounds: *clip_bounds,
+        },
+        Primitive::Paragraph {
+            paragraph,
+            position,
+            color,
+            clip_bounds,
+        } => SoftwarePrimitive::Paragraph {
+            paragraph: paragraph.clone(),
+            position: *position,
+            color: *color,
+            clip_bounds: *clip_bounds,
+        },
+        Primitive::Editor {
+            editor,
+            position,
+            color,
+            clip_bounds,
+        } => SoftwarePrimitive::Editor {
+            editor: editor.clone(),
+            position: *position,
+            color: *color,
+            clip_bounds: *clip_bounds,
+        },
+        Primitive::RawText(raw) => SoftwarePrimitive::RawText(raw.clone()),
+        Primitive::Quad {
+            bounds,
+            background,
+            border,
+            shadow,
+        } => SoftwarePrimitive::Quad {
+            bounds: *bounds,
+            background: *background,
+            border: *border,
+            shadow: *shadow,
+        },
+        Primitive::Image {
+            handle,
+            filter_method,
+            bounds,
+        } => SoftwarePrimitive::Image {
+            handle: handle.clone(),
+            filter_method: *filter_method,
+            bounds: *bounds,
+        },
+        Primitive::Svg {
+            handle,
+            color,
+            bounds,
+        } => SoftwarePrimitive::Svg {
+            handle: handle.clone(),
+            color: *color,
+            bounds: *bounds,
+        },
+        Primitive::Group { primitives } => SoftwarePrimitive::Group {
+            primitives: primitives.iter().filter_map(to_software).collect(),
+        },
+        Primitive::Clip { bounds, content } => SoftwarePrimitive::Clip {
+            bounds: *bounds,
+            content: Box::new(to_software(content)?),
+        },
+        Primitive::Transform {
+            transformation,
+            content,
+        } => SoftwarePrimitive::Transform {
+            transformation: *transformation,
+            content: Box::new(to_software(content)?),
+        },
+        Primitive::Cache { content } => SoftwarePrimitive::Cache {
+            content: std::sync::Arc::new(to_software(content)?),
+        },
+        Primitive::Custom(_) => return None,
+    })
+}
+
+#[cfg(feature = "tiny_skia")]
+pub fn new_software_backend() -> iced_tiny_skia::Backend {
+    iced_tiny_skia::Backend::new()
+}
+
+/// Draw `primitives` into `pixels`, cleared to transparent first.
+#[cfg(feature = "tiny_skia")]
+pub fn draw_software(
+    backend: &mut iced_tiny_skia::Backend,
+    pixels: &mut tiny_skia::PixmapMut<'_>,
+    clip_mask: &mut tiny_skia::Mask,
+    primitives: &[SoftwarePrimitive],
+    viewport: &Viewport,
+    overlay: &[String],
+) {
+    let damage = [Rectangle::with_size(viewport.logical_size())];
+    backend.draw(
+        pixels,
+        clip_mask,
+        primitives,
+        viewport,
+        &damage,
+        iced_core::Color::TRANSPARENT,
+        overlay,
+    );
+}
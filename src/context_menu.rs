@@ -0,0 +1,353 @@
+use std::any::Any;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use iced_core::keyboard::{self, key::Named, Key};
+use iced_core::widget::tree::{self, Tree};
+use iced_core::widget::{Operation, Widget};
+use iced_core::{
+    event, layout, mouse, overlay, renderer, touch, Clipboard, Event, Layout, Length, Point,
+    Rectangle, Shell, Size, Vector,
+};
+use iced_widget::style::theme::{self, Theme};
+
+use crate::ui_types::Element;
+use crate::widgets;
+use crate::Renderer;
+
+// The width of context menus, in logical pixels.
+const MENU_WIDTH: f32 = 200.0;
+
+// A context menu opened with `IcedContext::context_menu`, until an item is selected or it
+// is dismissed.
+pub(crate) struct OpenMenu {
+    // Where the menu was opened, in logical pixels of the interface.
+    position: Point,
+    // Set by the menu when it is closed.
+    closed: Arc<AtomicBool>,
+    // A `Box<dyn BuildMenu<M>>` for the message type of the menu.
+    items: Box<dyn Any + Send + Sync>,
+}
+
+impl OpenMenu {
+    pub fn new<M: Clone + Send + Sync + 'static>(position: Point, items: Vec<(String, M)>) -> Self {
+        let items: Box<dyn BuildMenu<M>> = Box::new(items);
+        Self {
+            position,
+            closed: Arc::new(AtomicBool::new(false)),
+            items: Box::new(items),
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Relaxed)
+    }
+}
+
+// Builds the items of a menu, whose messages have to be cloned.
+trait BuildMenu<M>: Send + Sync {
+    fn build<'a>(&self) -> Element<'a, M>
+    where
+        M: 'a;
+}
+
+impl<M: Clone + Send + Sync> BuildMenu<M> for Vec<(String, M)> {
+    fn build<'a>(&self) -> Element<'a, M>
+    where
+        M: 'a,
+    {
+        let items = self
+            .iter()
+            .map(|(label, message)| {
+                widgets::button(widgets::text(label))
+                    .width(Length::Fill)
+                    .style(theme::Button::Text)
+                    .on_press(message.clone())
+                    .into()
+            })
+            .collect();
+        widgets::container(widgets::column(items))
+            .width(MENU_WIDTH)
+            .padding(4)
+            .style(theme::Container::Box)
+            .into()
+    }
+}
+
+// Wrap the root of an interface, to show `menu` over it when one is open.
+//
+// Interfaces sharing a widget state are always wrapped, so that the state of their widgets
+// is kept when a menu opens or closes.
+pub(crate) fn host<'a, M: 'static>(
+    content: Element<'a, M>,
+    menu: Option<&OpenMenu>,
+) -> Element<'a, M> {
+    let menu = menu.and_then(|menu| {
+        let items = menu.items.downcast_ref::<Box<dyn BuildMenu<M>>>()?;
+        Some((items.build(), menu.position, menu.closed.clone()))
+    });
+    Element::new(MenuHost { content, menu })
+}
+
+struct MenuHost<'a, M> {
+    content: Element<'a, M>,
+    menu: Option<(Element<'a, M>, Point, Arc<AtomicBool>)>,
+}
+
+impl<'a, M> Widget<M, Theme, Renderer> for MenuHost<'a, M> {
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.content.as_widget().size_hint()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::stateless()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        std::iter::once(&self.content)
+            .chain(self.menu.as_ref().map(|(menu, ..)| menu))
+            .map(|child| Tree::new(child.as_widget()))
+            .collect()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        let children: Vec<_> = std::iter::once(&self.content)
+            .chain(self.menu.as_ref().map(|(menu, ..)| menu))
+            .collect();
+        tree.diff_children_custom(
+            &children,
+            |tree, child| tree.diff(child.as_widget()),
+            |child| Tree::new(child.as_widget()),
+        );
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<M>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, M>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, M, Theme, Renderer>> {
+        let (content_tree, menu_tree) = tree.children.split_at_mut(1);
+        match (&mut self.menu, menu_tree.first_mut()) {
+            // The menu takes the place of the overlays of the content, e.g. an open pick list.
+            (Some((menu, position, closed)), Some(tree)) => {
+                Some(overlay::Element::new(Box::new(MenuOverlay {
+                    menu,
+                    tree,
+                    closed,
+                    position: *position + translation,
+                })))
+            }
+            _ => self.content.as_widget_mut().overlay(
+                &mut content_tree[0],
+                layout,
+                renderer,
+                translation,
+            ),
+        }
+    }
+}
+
+struct MenuOverlay<'a, 'b, M> {
+    menu: &'b mut Element<'a, M>,
+    tree: &'b mut Tree,
+    closed: &'b AtomicBool,
+    // Where the menu opens, in logical pixels of the interface.
+    position: Point,
+}
+
+impl<'a, 'b, M> overlay::Overlay<M, Theme, Renderer> for MenuOverlay<'a, 'b, M> {
+    fn layout(&mut self, renderer: &Renderer, bounds: Size) -> layout::Node {
+        let position = self.position;
+        let node = self.menu.as_widget().layout(
+            self.tree,
+            renderer,
+            &layout::Limits::new(Size::ZERO, bounds),
+        );
+        let size = node.size();
+        // Open towards the inside of the interface near its right and bottom edges.
+        let flip = |position: f32, length: f32, bound: f32| {
+            if position + length > bound {
+                (position - length).max(0.0)
+            } else {
+                position
+            }
+        };
+        node.move_to(Point::new(
+            flip(position.x, size.width, bounds.width),
+            flip(position.y, size.height, bounds.height),
+        ))
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+    ) {
+        self.menu.as_widget().draw(
+            self.tree,
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            &layout.bounds(),
+        );
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, M>,
+    ) -> event::Status {
+        let bounds = layout.bounds();
+        let status = self.menu.as_widget_mut().on_event(
+            self.tree,
+            event.clone(),
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            &bounds,
+        );
+        match event {
+            // Clicks outside of the menu dismiss it, without reaching the interface.
+            Event::Mouse(mouse::Event::ButtonPressed(_))
+            | Event::Touch(touch::Event::FingerPressed { .. })
+                if !cursor.is_over(bounds) =>
+            {
+                self.closed.store(true, Ordering::Relaxed);
+                event::Status::Captured
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: Key::Named(Named::Escape),
+                ..
+            }) => {
+                self.closed.store(true, Ordering::Relaxed);
+                event::Status::Captured
+            }
+            // Buttons capture the release that selects them.
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerLifted { .. })
+                if status == event::Status::Captured =>
+            {
+                self.closed.store(true, Ordering::Relaxed);
+                status
+            }
+            _ => status,
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.menu.as_widget().mouse_interaction(
+            self.tree,
+            layout,
+            cursor,
+            &layout.bounds(),
+            renderer,
+        )
+    }
+}
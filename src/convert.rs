@@ -2,12 +2,162 @@ use crate::iced::{
     touch::{self, Finger},
     Point,
 };
-use bevy_input::keyboard::Key as BevyKey;
+use bevy_input::keyboard::{Key as BevyKey, KeyCode};
+use bevy_input::mouse::{MouseScrollUnit, MouseWheel};
 use bevy_input::prelude::MouseButton;
 use bevy_input::touch::{TouchInput, TouchPhase};
-use bevy_math::Vec2;
-use iced_core::keyboard::Key as IcedKey;
+use bevy_math::{Rect, Vec2};
+use bevy_render::color::Color as BevyColor;
+use bevy_window::CursorIcon;
+use iced_core::keyboard::{Key as IcedKey, Location};
+use iced_core::{mouse, Color, Rectangle, Size, Vector};
 
+/// Convert a Bevy type to its Iced counterpart.
+///
+/// ```ignore
+/// let position: Point = transform.translation.truncate().to_iced();
+/// ```
+pub trait ToIced {
+    /// The Iced type.
+    type Iced;
+
+    /// Convert `self` to the Iced type.
+    fn to_iced(self) -> Self::Iced;
+}
+
+/// Convert an Iced type to its Bevy counterpart.
+pub trait ToBevy {
+    /// The Bevy type.
+    type Bevy;
+
+    /// Convert `self` to the Bevy type.
+    fn to_bevy(self) -> Self::Bevy;
+}
+
+/// Positions are in the same coordinates on both sides, e.g. logical pixels from the top
+/// left corner of the window.
+impl ToIced for Vec2 {
+    type Iced = Point;
+
+    fn to_iced(self) -> Point {
+        Point::new(self.x, self.y)
+    }
+}
+
+impl ToBevy for Point {
+    type Bevy = Vec2;
+
+    fn to_bevy(self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+}
+
+impl ToBevy for Vector {
+    type Bevy = Vec2;
+
+    fn to_bevy(self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+}
+
+impl ToBevy for Size {
+    type Bevy = Vec2;
+
+    fn to_bevy(self) -> Vec2 {
+        Vec2::new(self.width, self.height)
+    }
+}
+
+impl ToIced for Rect {
+    type Iced = Rectangle;
+
+    fn to_iced(self) -> Rectangle {
+        Rectangle::new(self.min.to_iced(), Size::new(self.width(), self.height()))
+    }
+}
+
+impl ToBevy for Rectangle {
+    type Bevy = Rect;
+
+    fn to_bevy(self) -> Rect {
+        Rect::new(self.x, self.y, self.x + self.width, self.y + self.height)
+    }
+}
+
+/// Colors are gamma-encoded sRGB on the Iced side, see [`crate::color`].
+impl ToIced for BevyColor {
+    type Iced = Color;
+
+    fn to_iced(self) -> Color {
+        crate::color::to_iced(self)
+    }
+}
+
+impl ToBevy for Color {
+    type Bevy = BevyColor;
+
+    fn to_bevy(self) -> BevyColor {
+        crate::color::to_bevy(self)
+    }
+}
+
+impl ToIced for MouseButton {
+    type Iced = mouse::Button;
+
+    fn to_iced(self) -> mouse::Button {
+        mouse_button(self)
+    }
+}
+
+impl ToBevy for mouse::Button {
+    type Bevy = MouseButton;
+
+    fn to_bevy(self) -> MouseButton {
+        match self {
+            mouse::Button::Left => MouseButton::Left,
+            mouse::Button::Right => MouseButton::Right,
+            mouse::Button::Middle => MouseButton::Middle,
+            mouse::Button::Back => MouseButton::Back,
+            mouse::Button::Forward => MouseButton::Forward,
+            mouse::Button::Other(val) => MouseButton::Other(val),
+        }
+    }
+}
+
+impl ToIced for &BevyKey {
+    type Iced = IcedKey;
+
+    fn to_iced(self) -> IcedKey {
+        key_code(self)
+    }
+}
+
+/// Physical keys convert to the key they have on a US layout, and their location.
+impl ToIced for KeyCode {
+    type Iced = (IcedKey, Location);
+
+    fn to_iced(self) -> (IcedKey, Location) {
+        (physical_key(self), key_location(self))
+    }
+}
+
+impl ToIced for &TouchInput {
+    type Iced = touch::Event;
+
+    fn to_iced(self) -> touch::Event {
+        touch_event(self)
+    }
+}
+
+impl ToBevy for mouse::Interaction {
+    type Bevy = CursorIcon;
+
+    fn to_bevy(self) -> CursorIcon {
+        cursor_icon(self)
+    }
+}
+
+/// The Iced key of a logical Bevy key, [`IcedKey::Unidentified`] for keys Iced lacks.
 pub fn key_code(virtual_keycode: &BevyKey) -> IcedKey {
     use iced_core::keyboard::key::Named;
     match virtual_keycode {
@@ -323,6 +473,169 @@ pub fn key_code(virtual_keycode: &BevyKey) -> IcedKey {
     }
 }
 
+/// The Iced key of a physical Bevy key, as it is on a US layout, or
+/// [`IcedKey::Unidentified`] for keys without an equivalent.
+///
+/// Shortcuts matched on these keys stay in the same place whatever the layout, unlike the
+/// logical keys of [`key_code`].
+pub fn physical_key(code: KeyCode) -> IcedKey {
+    let character = match code {
+        KeyCode::KeyA => "a",
+        KeyCode::KeyB => "b",
+        KeyCode::KeyC => "c",
+        KeyCode::KeyD => "d",
+        KeyCode::KeyE => "e",
+        KeyCode::KeyF => "f",
+        KeyCode::KeyG => "g",
+        KeyCode::KeyH => "h",
+        KeyCode::KeyI => "i",
+        KeyCode::KeyJ => "j",
+        KeyCode::KeyK => "k",
+        KeyCode::KeyL => "l",
+        KeyCode::KeyM => "m",
+        KeyCode::KeyN => "n",
+        KeyCode::KeyO => "o",
+        KeyCode::KeyP => "p",
+        KeyCode::KeyQ => "q",
+        KeyCode::KeyR => "r",
+        KeyCode::KeyS => "s",
+        KeyCode::KeyT => "t",
+        KeyCode::KeyU => "u",
+        KeyCode::KeyV => "v",
+        KeyCode::KeyW => "w",
+        KeyCode::KeyX => "x",
+        KeyCode::KeyY => "y",
+        KeyCode::KeyZ => "z",
+        KeyCode::Digit0 | KeyCode::Numpad0 => "0",
+        KeyCode::Digit1 | KeyCode::Numpad1 => "1",
+        KeyCode::Digit2 | KeyCode::Numpad2 => "2",
+        KeyCode::Digit3 | KeyCode::Numpad3 => "3",
+        KeyCode::Digit4 | KeyCode::Numpad4 => "4",
+        KeyCode::Digit5 | KeyCode::Numpad5 => "5",
+        KeyCode::Digit6 | KeyCode::Numpad6 => "6",
+        KeyCode::Digit7 | KeyCode::Numpad7 => "7",
+        KeyCode::Digit8 | KeyCode::Numpad8 => "8",
+        KeyCode::Digit9 | KeyCode::Numpad9 => "9",
+        KeyCode::Backquote => "`",
+        KeyCode::Minus => "-",
+        KeyCode::Equal => "=",
+        KeyCode::BracketLeft => "[",
+        KeyCode::BracketRight => "]",
+        KeyCode::Backslash => "\\",
+        KeyCode::IntlBackslash => "\\",
+        KeyCode::Semicolon => ";",
+        KeyCode::Quote => "'",
+        KeyCode::Comma => ",",
+        KeyCode::Period => ".",
+        KeyCode::Slash => "/",
+        KeyCode::NumpadAdd => "+",
+        KeyCode::NumpadSubtract => "-",
+        KeyCode::NumpadMultiply => "*",
+        KeyCode::NumpadDivide => "/",
+        KeyCode::NumpadDecimal => ".",
+        KeyCode::NumpadComma => ",",
+        KeyCode::NumpadEqual => "=",
+        _ => {
+            return match named_key(code) {
+                Some(named) => IcedKey::Named(named),
+                None => IcedKey::Unidentified,
+            }
+        }
+    };
+    IcedKey::Character(character.into())
+}
+
+// The named Iced key of a physical Bevy key, for the keys without a character.
+fn named_key(code: KeyCode) -> Option<iced_core::keyboard::key::Named> {
+    use iced_core::keyboard::key::Named;
+    Some(match code {
+        KeyCode::AltLeft | KeyCode::AltRight => Named::Alt,
+        KeyCode::ControlLeft | KeyCode::ControlRight => Named::Control,
+        KeyCode::ShiftLeft | KeyCode::ShiftRight => Named::Shift,
+        KeyCode::SuperLeft | KeyCode::SuperRight => Named::Super,
+        KeyCode::CapsLock => Named::CapsLock,
+        KeyCode::NumLock => Named::NumLock,
+        KeyCode::ScrollLock => Named::ScrollLock,
+        KeyCode::Enter | KeyCode::NumpadEnter => Named::Enter,
+        KeyCode::Tab => Named::Tab,
+        KeyCode::Space => Named::Space,
+        KeyCode::Backspace | KeyCode::NumpadBackspace => Named::Backspace,
+        KeyCode::Delete => Named::Delete,
+        KeyCode::Insert => Named::Insert,
+        KeyCode::Home => Named::Home,
+        KeyCode::End => Named::End,
+        KeyCode::PageUp => Named::PageUp,
+        KeyCode::PageDown => Named::PageDown,
+        KeyCode::ArrowUp => Named::ArrowUp,
+        KeyCode::ArrowDown => Named::ArrowDown,
+        KeyCode::ArrowLeft => Named::ArrowLeft,
+        KeyCode::ArrowRight => Named::ArrowRight,
+        KeyCode::Escape => Named::Escape,
+        KeyCode::PrintScreen => Named::PrintScreen,
+        KeyCode::Pause => Named::Pause,
+        KeyCode::ContextMenu => Named::ContextMenu,
+        KeyCode::F1 => Named::F1,
+        KeyCode::F2 => Named::F2,
+        KeyCode::F3 => Named::F3,
+        KeyCode::F4 => Named::F4,
+        KeyCode::F5 => Named::F5,
+        KeyCode::F6 => Named::F6,
+        KeyCode::F7 => Named::F7,
+        KeyCode::F8 => Named::F8,
+        KeyCode::F9 => Named::F9,
+        KeyCode::F10 => Named::F10,
+        KeyCode::F11 => Named::F11,
+        KeyCode::F12 => Named::F12,
+        KeyCode::F13 => Named::F13,
+        KeyCode::F14 => Named::F14,
+        KeyCode::F15 => Named::F15,
+        KeyCode::F16 => Named::F16,
+        KeyCode::F17 => Named::F17,
+        KeyCode::F18 => Named::F18,
+        KeyCode::F19 => Named::F19,
+        KeyCode::F20 => Named::F20,
+        KeyCode::F21 => Named::F21,
+        KeyCode::F22 => Named::F22,
+        KeyCode::F23 => Named::F23,
+        KeyCode::F24 => Named::F24,
+        _ => return None,
+    })
+}
+
+/// The location of a physical Bevy key, telling apart the left and right modifiers and the
+/// keys of the numeric keypad.
+pub const fn key_location(code: KeyCode) -> Location {
+    match code {
+        KeyCode::AltLeft | KeyCode::ControlLeft | KeyCode::ShiftLeft | KeyCode::SuperLeft => {
+            Location::Left
+        }
+        KeyCode::AltRight | KeyCode::ControlRight | KeyCode::ShiftRight | KeyCode::SuperRight => {
+            Location::Right
+        }
+        KeyCode::Numpad0
+        | KeyCode::Numpad1
+        | KeyCode::Numpad2
+        | KeyCode::Numpad3
+        | KeyCode::Numpad4
+        | KeyCode::Numpad5
+        | KeyCode::Numpad6
+        | KeyCode::Numpad7
+        | KeyCode::Numpad8
+        | KeyCode::Numpad9
+        | KeyCode::NumpadAdd
+        | KeyCode::NumpadBackspace
+        | KeyCode::NumpadComma
+        | KeyCode::NumpadDecimal
+        | KeyCode::NumpadDivide
+        | KeyCode::NumpadEnter
+        | KeyCode::NumpadEqual
+        | KeyCode::NumpadMultiply
+        | KeyCode::NumpadSubtract => Location::Numpad,
+        _ => Location::Standard,
+    }
+}
+
+/// The Iced counterpart of a Bevy mouse button.
 pub const fn mouse_button(button: MouseButton) -> iced_core::mouse::Button {
     use iced_core::mouse::Button;
     match button {
@@ -335,6 +648,38 @@ pub const fn mouse_button(button: MouseButton) -> iced_core::mouse::Button {
     }
 }
 
+/// The Iced scroll delta of a Bevy wheel event, scaled by `sensitivity`.
+pub fn scroll_delta(wheel: &MouseWheel, sensitivity: f32) -> iced_core::mouse::ScrollDelta {
+    use iced_core::mouse::ScrollDelta;
+    let (x, y) = (wheel.x * sensitivity, wheel.y * sensitivity);
+    match wheel.unit {
+        MouseScrollUnit::Line => ScrollDelta::Lines { x, y },
+        MouseScrollUnit::Pixel => ScrollDelta::Pixels { x, y },
+    }
+}
+
+/// The Bevy cursor icon showing an Iced mouse interaction.
+pub const fn cursor_icon(interaction: iced_core::mouse::Interaction) -> CursorIcon {
+    use iced_core::mouse::Interaction;
+    match interaction {
+        Interaction::Idle => CursorIcon::Default,
+        Interaction::Pointer => CursorIcon::Pointer,
+        Interaction::Grab => CursorIcon::Grab,
+        Interaction::Text => CursorIcon::Text,
+        Interaction::Crosshair => CursorIcon::Crosshair,
+        Interaction::Working => CursorIcon::Progress,
+        Interaction::Grabbing => CursorIcon::Grabbing,
+        Interaction::ResizingHorizontally => CursorIcon::EwResize,
+        Interaction::ResizingVertically => CursorIcon::NsResize,
+        Interaction::NotAllowed => CursorIcon::NotAllowed,
+        Interaction::ZoomIn => CursorIcon::ZoomIn,
+        // Patch releases of iced add interactions, e.g. `ZoomIn` in 0.12.3.
+        #[allow(unreachable_patterns)]
+        _ => CursorIcon::Default,
+    }
+}
+
+/// The Iced event of a Bevy touch input.
 pub const fn touch_event(bevy_touch_input: &TouchInput) -> touch::Event {
     match *bevy_touch_input {
         TouchInput {
@@ -375,3 +720,66 @@ pub const fn touch_event(bevy_touch_input: &TouchInput) -> touch::Event {
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_round_trip() {
+        let position = Vec2::new(12.5, -3.0);
+        assert_eq!(position.to_iced(), Point::new(12.5, -3.0));
+        assert_eq!(position.to_iced().to_bevy(), position);
+    }
+
+    #[test]
+    fn rectangles_round_trip() {
+        let rect = Rect::new(10.0, 20.0, 110.0, 70.0);
+        let rectangle = rect.to_iced();
+        assert_eq!(
+            rectangle,
+            Rectangle::new(Point::new(10.0, 20.0), Size::new(100.0, 50.0))
+        );
+        assert_eq!(rectangle.to_bevy(), rect);
+    }
+
+    #[test]
+    fn mouse_buttons_round_trip() {
+        for button in [
+            MouseButton::Left,
+            MouseButton::Right,
+            MouseButton::Middle,
+            MouseButton::Back,
+            MouseButton::Forward,
+            MouseButton::Other(7),
+        ] {
+            assert_eq!(button.to_iced().to_bevy(), button);
+        }
+    }
+
+    #[test]
+    fn colors_round_trip() {
+        let color = BevyColor::rgba(0.2, 0.4, 0.6, 0.8);
+        assert_eq!(color.to_iced(), Color::from_rgba(0.2, 0.4, 0.6, 0.8));
+        assert_eq!(color.to_iced().to_bevy(), color);
+    }
+
+    #[test]
+    fn physical_keys() {
+        use iced_core::keyboard::key::Named;
+        assert_eq!(
+            KeyCode::KeyA.to_iced(),
+            (IcedKey::Character("a".into()), Location::Standard)
+        );
+        assert_eq!(
+            KeyCode::Numpad7.to_iced(),
+            (IcedKey::Character("7".into()), Location::Numpad)
+        );
+        assert_eq!(
+            KeyCode::ShiftRight.to_iced(),
+            (IcedKey::Named(Named::Shift), Location::Right)
+        );
+        assert_eq!(KeyCode::F12.to_iced().0, IcedKey::Named(Named::F12));
+        assert_eq!(KeyCode::Lang1.to_iced().0, IcedKey::Unidentified);
+    }
+}
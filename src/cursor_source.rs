@@ -0,0 +1,89 @@
+use std::marker::PhantomData;
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::{IntoSystemConfigs, Res, ResMut, Resource};
+use bevy_ecs::system::Local;
+use bevy_math::Vec2;
+use iced_core::{mouse, Event as IcedEvent, Point};
+
+use crate::systems::{self, IcedInteraction};
+use crate::IcedEventQueue;
+
+/// A resource providing the cursor position of the interfaces, instead of the mouse.
+///
+/// This is meant for setups where the pointer isn't the one of the OS: the ray of an XR
+/// controller hitting the quad interfaces are rendered to, a laser pointer, an eye tracker...
+///
+/// ```ignore
+/// #[derive(Resource, Default)]
+/// struct ControllerRay(Option<Vec2>);
+///
+/// impl IcedCursorSource for ControllerRay {
+///     fn cursor_position(&self) -> Option<Vec2> {
+///         self.0
+///     }
+/// }
+///
+/// app.init_resource::<ControllerRay>()
+///     .add_plugins(IcedCursorSourcePlugin::<ControllerRay>::default());
+/// ```
+///
+/// While the resource exists, it replaces the window cursor in every interface, except
+/// when an active [`IcedVirtualCursor`](crate::virtual_cursor::IcedVirtualCursor) takes
+/// precedence. Buttons aren't part of the source: send them with
+/// [`IcedContext::inject_event`](crate::IcedContext::inject_event). Setting
+/// [`InputForwarding::mouse`](crate::InputForwarding::mouse) to `false` keeps the mouse of
+/// the window from interfering, without affecting the source.
+pub trait IcedCursorSource: Resource {
+    /// The position of the cursor in logical pixels of the viewport, or `None` when it
+    /// points outside of it, in which case interfaces see no cursor.
+    fn cursor_position(&self) -> Option<Vec2>;
+}
+
+/// Feeds the position of the [`IcedCursorSource`] `S` to the interfaces, while the resource
+/// exists.
+pub struct IcedCursorSourcePlugin<S>(PhantomData<fn() -> S>);
+
+impl<S> Default for IcedCursorSourcePlugin<S> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S: IcedCursorSource> Plugin for IcedCursorSourcePlugin<S> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            apply_cursor_source::<S>.in_set(systems::InputSet::Sources),
+        );
+    }
+}
+
+fn apply_cursor_source<S: IcedCursorSource>(
+    source: Option<Res<S>>,
+    mut last: Local<Option<Vec2>>,
+    mut event_queue: ResMut<IcedEventQueue>,
+    mut interaction: ResMut<IcedInteraction>,
+) {
+    // The window cursor is back once the resource is removed.
+    let Some(source) = source else {
+        interaction.cursor_source = None;
+        *last = None;
+        return;
+    };
+    let position = source.cursor_position();
+    // Widgets react to the movements of the cursor, not to its position.
+    if position != *last {
+        event_queue.push(IcedEvent::Mouse(match position {
+            Some(position) => mouse::Event::CursorMoved {
+                position: Point::new(position.x, position.y),
+            },
+            None => mouse::Event::CursorLeft,
+        }));
+        *last = position;
+    }
+    interaction.cursor_source = Some(match position {
+        Some(position) => mouse::Cursor::Available(Point::new(position.x, position.y)),
+        None => mouse::Cursor::Unavailable,
+    });
+}
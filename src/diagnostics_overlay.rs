@@ -0,0 +1,178 @@
+use bevy_app::{App, Last, Plugin, Update};
+use bevy_diagnostic::{
+    DiagnosticPath, DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin,
+};
+use bevy_ecs::prelude::{
+    Condition, Event, EventReader, IntoSystemConfigs, Local, Res, ResMut, Resource,
+};
+use bevy_time::Time;
+use iced_core::alignment::Horizontal;
+use std::time::Duration;
+
+use crate::render::IcedRenderStats;
+use crate::systems::IcedInteraction;
+use crate::ui_types::{Border, Color, Element, Font, Length};
+use crate::widgets::{self, PanelStyle};
+use crate::{iced_needs_update, IcedContext, UiFrameStats};
+
+// How often the overlay is refreshed, slow enough for its figures to be readable.
+const REFRESH_PERIOD: Duration = Duration::from_millis(500);
+
+/// The performance figures shown by [`diagnostics_overlay`], updated every frame.
+///
+/// The frame rate, frame time and entity count come from `bevy_diagnostic`, and are `None`
+/// until the [`FrameTimeDiagnosticsPlugin`] and [`EntityCountDiagnosticsPlugin`] measured
+/// them. The timings of the interfaces are the sums of the [`UiFrameStats`] of the last frame
+/// they were updated in.
+///
+/// Requires the [`IcedDiagnosticsPlugin`].
+#[derive(Resource, Clone, Debug, Default)]
+pub struct IcedDiagnostics {
+    /// Whether the overlay is displayed over every interface, in the top right corner.
+    ///
+    /// It is refreshed twice a second, updating the other interfaces along with it.
+    pub show_overlay: bool,
+    /// The smoothed number of frames per second.
+    pub fps: Option<f64>,
+    /// The smoothed duration of a frame, in milliseconds.
+    pub frame_time_ms: Option<f64>,
+    /// The number of entities.
+    pub entities: Option<f64>,
+    /// The time spent building and laying out interfaces, in milliseconds.
+    pub build_ms: f32,
+    /// The time spent processing the input of interfaces, in milliseconds.
+    pub layout_ms: f32,
+    /// The time spent drawing interfaces, in milliseconds.
+    pub draw_ms: f32,
+    /// The time spent presenting interfaces, in milliseconds, from the [`IcedRenderStats`].
+    pub present_ms: f32,
+}
+
+/// Creates a panel listing the [`IcedDiagnostics`], to push into an interface.
+pub fn diagnostics_overlay<'a, Message: 'a>(diagnostics: &IcedDiagnostics) -> Element<'a, Message> {
+    let measured = |value: Option<f64>, unit: &str| match value {
+        Some(value) => format!("{value:.1}{unit}"),
+        None => "-".to_string(),
+    };
+    let lines = [
+        ("FPS", measured(diagnostics.fps, "")),
+        ("Frame", measured(diagnostics.frame_time_ms, " ms")),
+        (
+            "Entities",
+            measured(diagnostics.entities.map(f64::round), ""),
+        ),
+        ("UI build", format!("{:.2} ms", diagnostics.build_ms)),
+        ("UI layout", format!("{:.2} ms", diagnostics.layout_ms)),
+        ("UI draw", format!("{:.2} ms", diagnostics.draw_ms)),
+        ("UI present", format!("{:.2} ms", diagnostics.present_ms)),
+    ];
+    let lines = lines
+        .into_iter()
+        .map(|(label, value)| {
+            widgets::row(vec![
+                widgets::text(label)
+                    .font(Font::MONOSPACE)
+                    .width(Length::Fill)
+                    .into(),
+                widgets::text(value).font(Font::MONOSPACE).into(),
+            ])
+            .into()
+        })
+        .collect();
+    widgets::container(widgets::column(lines).spacing(2))
+        .width(180)
+        .padding(8)
+        .style(PanelStyle {
+            background: Some(Color::from_rgba(0.0, 0.0, 0.0, 0.7).into()),
+            border: Border::with_radius(4.0),
+            text_color: Some(Color::WHITE),
+            ..Default::default()
+        })
+        .into()
+}
+
+/// Gathers the [`IcedDiagnostics`], and displays the overlay when asked to.
+///
+/// The [`FrameTimeDiagnosticsPlugin`] and [`EntityCountDiagnosticsPlugin`] are added
+/// unless they already were.
+#[derive(Default)]
+pub struct IcedDiagnosticsPlugin {
+    /// Whether the overlay is displayed from the start, see
+    /// [`IcedDiagnostics::show_overlay`].
+    pub show_overlay: bool,
+}
+
+impl Plugin for IcedDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+        if !app.is_plugin_added::<EntityCountDiagnosticsPlugin>() {
+            app.add_plugins(EntityCountDiagnosticsPlugin);
+        }
+        app.insert_resource(IcedDiagnostics {
+            show_overlay: self.show_overlay,
+            ..Default::default()
+        })
+        .add_event::<OverlayMessage>()
+        .add_systems(
+            Update,
+            display_overlay.run_if(overlay_shown.and_then(iced_needs_update)),
+        )
+        .add_systems(Last, gather_diagnostics);
+    }
+}
+
+// The overlay has no interaction.
+#[derive(Event, Clone, Debug)]
+struct OverlayMessage;
+
+fn overlay_shown(diagnostics: Res<IcedDiagnostics>) -> bool {
+    diagnostics.show_overlay
+}
+
+fn display_overlay(diagnostics: Res<IcedDiagnostics>, mut ctx: IcedContext<OverlayMessage>) {
+    let overlay = widgets::container(diagnostics_overlay(&diagnostics))
+        .width(Length::Fill)
+        .padding(8)
+        .align_x(Horizontal::Right);
+    // Over every interface but the virtual cursor.
+    ctx.display_with_layer(i32::MAX - 1, overlay);
+}
+
+fn gather_diagnostics(
+    store: Res<DiagnosticsStore>,
+    render_stats: Res<IcedRenderStats>,
+    time: Res<Time>,
+    mut frame_stats: EventReader<UiFrameStats>,
+    mut diagnostics: ResMut<IcedDiagnostics>,
+    mut interaction: ResMut<IcedInteraction>,
+    mut last_refresh: Local<Duration>,
+) {
+    if diagnostics.show_overlay && time.elapsed() >= *last_refresh + REFRESH_PERIOD {
+        interaction.update_next_frame = true;
+        *last_refresh = time.elapsed();
+    }
+    let smoothed =
+        |path: &DiagnosticPath| store.get(path).and_then(|diagnostic| diagnostic.smoothed());
+    diagnostics.fps = smoothed(&FrameTimeDiagnosticsPlugin::FPS);
+    diagnostics.frame_time_ms = smoothed(&FrameTimeDiagnosticsPlugin::FRAME_TIME);
+    diagnostics.entities = smoothed(&EntityCountDiagnosticsPlugin::ENTITY_COUNT);
+    // Frames where no interface was updated keep the timings of the last one that was.
+    if !frame_stats.is_empty() {
+        (
+            diagnostics.build_ms,
+            diagnostics.layout_ms,
+            diagnostics.draw_ms,
+        ) = frame_stats
+            .read()
+            .fold((0.0, 0.0, 0.0), |(build, layout, draw), stats| {
+                (
+                    build + stats.build_ms,
+                    layout + stats.layout_ms,
+                    draw + stats.draw_ms,
+                )
+            });
+    }
+    diagnostics.present_ms = render_stats.present_time.as_secs_f32() * 1000.0;
+}
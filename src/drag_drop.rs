@@ -0,0 +1,190 @@
+use std::marker::PhantomData;
+
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy_ecs::prelude::{
+    Event, EventWriter, Has, IntoSystemConfigs, Query, Res, ResMut, Resource, With,
+};
+use bevy_input::mouse::MouseButton;
+use bevy_input::touch::Touches;
+use bevy_input::{ButtonInput, InputSystem};
+use bevy_math::primitives::Plane3d;
+use bevy_math::{Ray3d, Vec2, Vec3};
+use bevy_render::camera::Camera;
+use bevy_transform::components::GlobalTransform;
+use bevy_window::{PrimaryWindow, Window};
+
+use crate::{systems, IcedCamera, IcedPointerOverUi};
+
+/// The payload being dragged between interfaces and the world, if any.
+///
+/// A drag is started with [`start`](Self::start), either by the handler of a widget message
+/// (e.g. pressing an inventory slot) or by gameplay code (e.g. picking an entity). While
+/// the payload is dragged, [`cursor`](Self::cursor) can be used to draw it under the cursor.
+///
+/// When the mouse button or finger is released, drop targets accept the payload with
+/// [`take`](Self::take): in interfaces, this is usually done by the handler of a
+/// `mouse_area` release message, which must run in [`Update`](bevy_app::Update) after the
+/// system displaying the interface. A payload nobody took is considered dropped into the
+/// world, and sent in an [`IcedDropped`] event, unless it was released over an interface
+/// (see [`IcedPointerOverUi`]): the drag is then cancelled.
+///
+/// Requires the [`IcedDragPlugin`] for the payload type.
+#[derive(Resource)]
+pub struct IcedDragState<P> {
+    payload: Option<P>,
+    cursor: Option<Vec2>,
+    released: bool,
+}
+
+impl<P> Default for IcedDragState<P> {
+    fn default() -> Self {
+        Self {
+            payload: None,
+            cursor: None,
+            released: false,
+        }
+    }
+}
+
+impl<P> IcedDragState<P> {
+    /// Start dragging `payload`, replacing the payload being dragged.
+    pub fn start(&mut self, payload: P) {
+        self.payload = Some(payload);
+        self.released = false;
+    }
+
+    /// Whether a payload is being dragged.
+    pub fn is_dragging(&self) -> bool {
+        self.payload.is_some()
+    }
+
+    /// The payload being dragged.
+    pub fn payload(&self) -> Option<&P> {
+        self.payload.as_ref()
+    }
+
+    /// The position of the cursor, in logical pixels of the window.
+    pub fn cursor(&self) -> Option<Vec2> {
+        self.cursor
+    }
+
+    /// Whether the payload was released this frame, and can be taken by drop targets.
+    pub fn is_released(&self) -> bool {
+        self.released && self.payload.is_some()
+    }
+
+    /// Accept the payload, ending the drag.
+    pub fn take(&mut self) -> Option<P> {
+        self.released = false;
+        self.payload.take()
+    }
+
+    /// End the drag without dropping the payload anywhere.
+    pub fn cancel(&mut self) {
+        self.payload = None;
+        self.released = false;
+    }
+}
+
+/// Sent when a payload of an [`IcedDragState`] is released over the world without being
+/// taken by a drop target.
+#[derive(Event)]
+pub struct IcedDropped<P> {
+    /// The payload that was dragged.
+    pub payload: P,
+    /// The position of the cursor, in logical pixels of the window.
+    pub cursor: Vec2,
+    /// The ray going through the cursor from the [`IcedCamera`] (or the first active camera),
+    /// for picking the entity or position the payload was dropped on.
+    pub ray: Option<Ray3d>,
+}
+
+impl<P> IcedDropped<P> {
+    /// The point of the plane going through `origin` with the given `normal` the payload was
+    /// dropped on, e.g. the ground.
+    pub fn point_on_plane(&self, origin: Vec3, normal: Vec3) -> Option<Vec3> {
+        let ray = self.ray?;
+        let distance = ray.intersect_plane(origin, Plane3d::new(normal))?;
+        Some(ray.get_point(distance))
+    }
+}
+
+/// Adds dragging payloads of type `P` between interfaces and the world. See
+/// [`IcedDragState`].
+pub struct IcedDragPlugin<P>(PhantomData<fn() -> P>);
+
+impl<P> Default for IcedDragPlugin<P> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<P: Send + Sync + 'static> Plugin for IcedDragPlugin<P> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<IcedDragState<P>>()
+            .add_event::<IcedDropped<P>>()
+            .add_systems(PreUpdate, track_drag::<P>.after(InputSystem))
+            .add_systems(
+                PostUpdate,
+                drop_payload::<P>.after(systems::update_pointer_over_ui),
+            );
+    }
+}
+
+fn track_drag<P: Send + Sync + 'static>(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    mut state: ResMut<IcedDragState<P>>,
+) {
+    if !state.is_dragging() {
+        return;
+    }
+    let touch = touches
+        .iter()
+        .chain(touches.iter_just_released())
+        .next()
+        .map(|touch| touch.position());
+    if let Some(cursor) = windows
+        .get_single()
+        .ok()
+        .and_then(Window::cursor_position)
+        .or(touch)
+    {
+        state.cursor = Some(cursor);
+    }
+    state.released = mouse.just_released(MouseButton::Left) || touches.any_just_released();
+}
+
+fn drop_payload<P: Send + Sync + 'static>(
+    cameras: Query<(&Camera, &GlobalTransform, Has<IcedCamera>)>,
+    over_ui: Res<IcedPointerOverUi>,
+    mut state: ResMut<IcedDragState<P>>,
+    mut dropped: EventWriter<IcedDropped<P>>,
+) {
+    if !state.is_released() {
+        return;
+    }
+    // Released over an interface that didn't take it, not over the world.
+    if **over_ui {
+        state.cancel();
+        return;
+    }
+    let cursor = state.cursor.unwrap_or_default();
+    let payload = state.take().unwrap();
+    let camera = cameras
+        .iter()
+        .filter(|(camera, ..)| camera.is_active)
+        .max_by_key(|(.., iced_camera)| *iced_camera);
+    let ray = camera.and_then(|(camera, transform, _)| {
+        let offset = camera
+            .logical_viewport_rect()
+            .map_or(Vec2::ZERO, |rect| rect.min);
+        camera.viewport_to_world(transform, cursor - offset)
+    });
+    dropped.send(IcedDropped {
+        payload,
+        cursor,
+        ray,
+    });
+}
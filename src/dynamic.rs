@@ -0,0 +1,213 @@
+use bevy_app::{App, Plugin, Update};
+use bevy_ecs::prelude::{Event, Res};
+use bevy_ecs::system::Resource;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::ui_types::Element;
+use crate::widgets;
+use crate::IcedContext;
+
+/// A message sent by a dynamic interface.
+///
+/// Buttons send the value of their `on_press` field as is. Widgets holding a value (text
+/// inputs, sliders) send an object `{ "event": <on_* field>, "value": <new value> }`.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct IcedDynamicMessage(pub Value);
+
+/// A node of an interface described at runtime, e.g. by a script.
+///
+/// Nodes are (de)serialized as objects tagged by their `type`:
+///
+/// ```json
+/// { "type": "column", "spacing": 8, "children": [
+///     { "type": "text", "content": "Hello" },
+///     { "type": "button", "label": "Play", "on_press": "play" }
+/// ] }
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UiNode {
+    /// A piece of text.
+    Text {
+        /// The text to display.
+        content: String,
+        /// The size of the text, in logical pixels.
+        #[serde(default)]
+        size: Option<f32>,
+    },
+    /// A button with a text label.
+    Button {
+        /// The text of the button.
+        label: String,
+        /// The message sent when the button is pressed. The button is disabled without it.
+        #[serde(default)]
+        on_press: Option<Value>,
+    },
+    /// A vertical list of nodes.
+    Column {
+        /// The nodes of the column.
+        children: Vec<UiNode>,
+        /// The space between nodes, in logical pixels.
+        #[serde(default)]
+        spacing: f32,
+        /// The space around the nodes, in logical pixels.
+        #[serde(default)]
+        padding: f32,
+    },
+    /// A horizontal list of nodes.
+    Row {
+        /// The nodes of the row.
+        children: Vec<UiNode>,
+        /// The space between nodes, in logical pixels.
+        #[serde(default)]
+        spacing: f32,
+        /// The space around the nodes, in logical pixels.
+        #[serde(default)]
+        padding: f32,
+    },
+    /// A box around a single node.
+    Container {
+        /// The node inside the container.
+        child: Box<UiNode>,
+        /// The space around the node, in logical pixels.
+        #[serde(default)]
+        padding: f32,
+    },
+    /// A vertically scrollable area.
+    Scrollable {
+        /// The scrolled node.
+        child: Box<UiNode>,
+    },
+    /// A field that can be filled with text.
+    TextInput {
+        /// The text shown while the field is empty.
+        #[serde(default)]
+        placeholder: String,
+        /// The current text of the field.
+        value: String,
+        /// The event of the messages sent when the text changes. The field is read-only
+        /// without it.
+        #[serde(default)]
+        on_input: Option<Value>,
+    },
+    /// A horizontal bar to select a value in a range.
+    Slider {
+        /// The smallest value.
+        min: f32,
+        /// The largest value.
+        max: f32,
+        /// The current value.
+        value: f32,
+        /// The event of the messages sent when the value changes.
+        on_change: Value,
+    },
+    /// An empty amount of space, in logical pixels.
+    Space {
+        /// The width of the space.
+        #[serde(default)]
+        width: f32,
+        /// The height of the space.
+        #[serde(default)]
+        height: f32,
+    },
+}
+
+impl UiNode {
+    /// Build the [`Element`] described by this node.
+    pub fn view(&self) -> Element<'_, IcedDynamicMessage> {
+        match self {
+            Self::Text { content, size } => {
+                let text = widgets::text(content);
+                match size {
+                    Some(size) => text.size(*size).into(),
+                    None => text.into(),
+                }
+            }
+            Self::Button { label, on_press } => widgets::button(widgets::text(label))
+                .on_press_maybe(on_press.clone().map(IcedDynamicMessage))
+                .into(),
+            Self::Column {
+                children,
+                spacing,
+                padding,
+            } => widgets::column(children.iter().map(Self::view).collect())
+                .spacing(*spacing)
+                .padding(*padding)
+                .into(),
+            Self::Row {
+                children,
+                spacing,
+                padding,
+            } => widgets::row(children.iter().map(Self::view).collect())
+                .spacing(*spacing)
+                .padding(*padding)
+                .into(),
+            Self::Container { child, padding } => {
+                widgets::container(child.view()).padding(*padding).into()
+            }
+            Self::Scrollable { child } => widgets::scrollable(child.view()).into(),
+            Self::TextInput {
+                placeholder,
+                value,
+                on_input,
+            } => {
+                let input = widgets::text_input(placeholder, value);
+                match on_input {
+                    Some(event) => input
+                        .on_input(move |value| {
+                            IcedDynamicMessage(json!({ "event": event, "value": value }))
+                        })
+                        .into(),
+                    None => input.into(),
+                }
+            }
+            Self::Slider {
+                min,
+                max,
+                value,
+                on_change,
+            } => widgets::slider(*min..=*max, *value, move |value| {
+                IcedDynamicMessage(json!({ "event": on_change, "value": value }))
+            })
+            .into(),
+            Self::Space { width, height } => widgets::space(*width, *height).into(),
+        }
+    }
+}
+
+/// The interface displayed by the [`DynamicIcedUiPlugin`].
+///
+/// Scripts drive the interface by replacing the root node, for example from JSON with
+/// [`DynamicIcedUi::from_json`], and react to the [`IcedDynamicMessage`]s it sends.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct DynamicIcedUi {
+    /// The root of the interface, or `None` to display nothing.
+    pub root: Option<UiNode>,
+}
+
+impl DynamicIcedUi {
+    /// Parse an interface from the JSON description of its root [`UiNode`].
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        Ok(Self {
+            root: Some(serde_json::from_str(json)?),
+        })
+    }
+}
+
+/// Displays the [`DynamicIcedUi`] every frame, and registers [`IcedDynamicMessage`].
+pub struct DynamicIcedUiPlugin;
+
+impl Plugin for DynamicIcedUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<IcedDynamicMessage>()
+            .init_resource::<DynamicIcedUi>()
+            .add_systems(Update, display_dynamic_ui);
+    }
+}
+
+fn display_dynamic_ui(ui: Res<DynamicIcedUi>, mut ctx: IcedContext<IcedDynamicMessage>) {
+    if let Some(root) = &ui.root {
+        ctx.display(root.view());
+    }
+}
@@ -0,0 +1,77 @@
+use std::fmt;
+
+use bevy_ecs::event::Event;
+use bevy_ecs::prelude::EventWriter;
+use bevy_ecs::system::{Local, SystemParam};
+use bevy_utils::tracing::warn;
+
+/// A problem encountered while displaying an Iced interface.
+///
+/// Instead of panicking, `bevy_iced` works around the problem (skipping the affected
+/// frame if needed), logs a warning the first time it occurs, and sends this event on
+/// every frame it persists.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub enum IcedError {
+    /// There is no window to display interfaces in, for example because it was closed.
+    ///
+    /// This is either the primary window, or the [`IcedWindow`](crate::IcedWindow) given to
+    /// [`IcedContext::display_in`](crate::IcedContext::display_in).
+    NoWindow,
+    /// A thread panicked while using the renderer, for example in a widget.
+    ///
+    /// The renderer has been recovered, and the interface that was being displayed
+    /// starts over from a fresh state.
+    Poisoned,
+    /// The message type of an interface, named here, was not registered with
+    /// `app.add_event()`, so its messages were discarded.
+    ///
+    /// See [`IcedSettings::auto_register_messages`](crate::IcedSettings::auto_register_messages).
+    UnregisteredMessage(&'static str),
+    /// Several interfaces of the message type named here were displayed in the same frame
+    /// without distinct keys.
+    ///
+    /// Their widget state is matched by call order, and moves to another interface when
+    /// one of them isn't displayed. Give each one a key with
+    /// [`IcedContext::display_keyed`](crate::IcedContext::display_keyed).
+    DuplicateDisplay(&'static str),
+}
+
+impl fmt::Display for IcedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoWindow => write!(f, "the window to display in is not available"),
+            Self::Poisoned => write!(f, "recovered the Iced renderer after a panic"),
+            Self::UnregisteredMessage(name) => write!(
+                f,
+                "discarded messages of type `{name}`, which is not registered with `add_event`"
+            ),
+            Self::DuplicateDisplay(name) => write!(
+                f,
+                "displayed several interfaces of type `{name}` without distinct keys"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IcedError {}
+
+#[derive(SystemParam)]
+pub struct ErrorReporter<'w, 's> {
+    events: EventWriter<'w, IcedError>,
+    // The errors already logged, which are logged again once resolved.
+    reported: Local<'s, Vec<IcedError>>,
+}
+
+impl<'w, 's> ErrorReporter<'w, 's> {
+    pub fn report(&mut self, error: IcedError) {
+        if !self.reported.contains(&error) {
+            warn!("Iced: {error}");
+            self.reported.push(error.clone());
+        }
+        self.events.send(error);
+    }
+
+    pub fn resolve(&mut self, error: &IcedError) {
+        self.reported.retain(|reported| reported != error);
+    }
+}
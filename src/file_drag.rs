@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use bevy_app::{App, Plugin, Update};
+use bevy_ecs::event::Event;
+use bevy_ecs::prelude::{EventReader, EventWriter, Query, With};
+use bevy_ecs::system::{NonSend, Res, Resource};
+use bevy_utils::tracing::warn;
+use bevy_window::{PrimaryWindow, RawHandleWrapper};
+
+/// Start dragging files out of the application, so they can be dropped in other
+/// applications (a file manager, an image editor...).
+///
+/// Send this from the handler of the message produced when a drag starts in the
+/// interface (for example from a `mouse_area`'s `on_press`), while the mouse button is
+/// still held.
+#[derive(Event, Clone, Debug)]
+pub struct StartFileDrag {
+    /// The files being dragged.
+    pub paths: Vec<PathBuf>,
+    /// An image shown under the cursor during the drag. Defaults to the first file.
+    pub icon: Option<PathBuf>,
+}
+
+/// Sent when a drag started with [`StartFileDrag`] ends.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FileDragFinished {
+    /// Whether the files were dropped somewhere, as opposed to the drag being cancelled.
+    pub dropped: bool,
+}
+
+/// Adds dragging files out of the application. See [`StartFileDrag`].
+///
+/// This is supported on Windows and macOS.
+pub struct FileDragPlugin;
+
+impl Plugin for FileDragPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<StartFileDrag>()
+            .add_event::<FileDragFinished>()
+            .init_resource::<FileDragResults>()
+            .init_non_send_resource::<MainThread>()
+            .add_systems(Update, (start_file_drag, finish_file_drag));
+    }
+}
+
+// Results reported by the OS, which may call back from another thread.
+#[derive(Resource, Default)]
+struct FileDragResults(Arc<Mutex<Vec<bool>>>);
+
+// Taking this keeps `start_file_drag` on the main thread, as the OS requires.
+#[derive(Default)]
+struct MainThread;
+
+#[allow(unsafe_code)]
+fn start_file_drag(
+    _main_thread: NonSend<MainThread>,
+    mut requests: EventReader<StartFileDrag>,
+    windows: Query<&RawHandleWrapper, With<PrimaryWindow>>,
+    results: Res<FileDragResults>,
+) {
+    let Ok(wrapper) = windows.get_single() else {
+        requests.clear();
+        return;
+    };
+    for request in requests.read() {
+        let Some(icon) = request.icon.as_ref().or(request.paths.first()) else {
+            continue;
+        };
+        // SAFETY: this system runs on the main thread, which owns the window.
+        let handle = unsafe { wrapper.get_handle() };
+        let results = results.0.clone();
+        let started = drag::start_drag(
+            &handle,
+            drag::DragItem::Files(request.paths.clone()),
+            drag::Image::File(icon.clone()),
+            move |result, _| {
+                let dropped = matches!(result, drag::DragResult::Dropped);
+                results.lock().unwrap().push(dropped);
+            },
+            drag::Options::default(),
+        );
+        if let Err(err) = started {
+            warn!("Failed to start dragging files: {err}");
+        }
+    }
+}
+
+fn finish_file_drag(results: Res<FileDragResults>, mut finished: EventWriter<FileDragFinished>) {
+    finished.send_batch(
+        results
+            .0
+            .lock()
+            .unwrap()
+            .drain(..)
+            .map(|dropped| FileDragFinished { dropped }),
+    );
+}
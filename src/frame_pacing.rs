@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::prelude::{IntoSystemConfigs, Local, Res, ResMut};
+use bevy_winit::{UpdateMode, WinitSettings};
+
+use crate::systems::{self, IcedInteraction};
+
+/// Wakes reactive applications up when their interfaces are due for an update.
+///
+/// With the reactive modes of [`WinitSettings`], e.g. [`WinitSettings::desktop_app`], Bevy
+/// only updates when input arrives, a [`RequestRedraw`](bevy_window::RequestRedraw) is sent,
+/// or the wait of the mode runs out. Interfaces request a redraw by themselves when they need
+/// to be updated on the next frame (for example after sending messages, to show the state
+/// they changed), but animations like the blinking cursor of a text input ask to be updated
+/// at a given time. This plugin shortens the wait of the reactive modes until that time, and
+/// restores it afterwards, so that animations run without updating continuously.
+///
+/// Changes made to [`WinitSettings`] by the application are kept: the wait is only ever
+/// shortened from the one it set.
+///
+/// Interfaces showing state that changes without input, like the progress of a download,
+/// should send a `RequestRedraw` from the system changing it.
+pub struct IcedFramePacingPlugin;
+
+impl Plugin for IcedFramePacingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, pace_frames.after(systems::request_redraw));
+    }
+}
+
+// The modes set by the application, and the ones last applied in their place.
+struct Pacing {
+    set: [UpdateMode; 2],
+    applied: [UpdateMode; 2],
+}
+
+fn pace_frames(
+    interaction: Res<IcedInteraction>,
+    mut settings: ResMut<WinitSettings>,
+    mut pacing: Local<Option<Pacing>>,
+) {
+    let current = [settings.focused_mode, settings.unfocused_mode];
+    let set = match &*pacing {
+        Some(pacing) if same_modes(&pacing.applied, &current) => pacing.set,
+        // The application changed the settings, or they were never applied.
+        _ => current,
+    };
+    let wait = interaction
+        .update_at
+        .zip(interaction.now)
+        .map(|(at, now)| at.saturating_duration_since(now));
+    let applied = set.map(|mode| shorten(mode, wait));
+    if !same_modes(&applied, &current) {
+        [settings.focused_mode, settings.unfocused_mode] = applied;
+    }
+    *pacing = Some(Pacing { set, applied });
+}
+
+// Whether `a` and `b` hold the same modes, which don't implement `PartialEq`.
+fn same_modes(a: &[UpdateMode; 2], b: &[UpdateMode; 2]) -> bool {
+    a.iter().zip(b).all(|modes| match modes {
+        (UpdateMode::Continuous, UpdateMode::Continuous) => true,
+        (UpdateMode::Reactive { wait: a }, UpdateMode::Reactive { wait: b })
+        | (UpdateMode::ReactiveLowPower { wait: a }, UpdateMode::ReactiveLowPower { wait: b }) => {
+            a == b
+        }
+        _ => false,
+    })
+}
+
+fn shorten(mode: UpdateMode, wait: Option<Duration>) -> UpdateMode {
+    let Some(until_update) = wait else {
+        return mode;
+    };
+    match mode {
+        UpdateMode::Continuous => UpdateMode::Continuous,
+        UpdateMode::Reactive { wait } => UpdateMode::Reactive {
+            wait: wait.min(until_update),
+        },
+        UpdateMode::ReactiveLowPower { wait } => UpdateMode::ReactiveLowPower {
+            wait: wait.min(until_update),
+        },
+    }
+}
@@ -0,0 +1,345 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+use bevy_app::{App, Plugin};
+use bevy_asset::AssetId;
+use bevy_ecs::prelude::IntoSystemConfigs;
+use bevy_ecs::system::{Res, Resource};
+use bevy_render::render_asset::RenderAssets;
+use bevy_render::render_resource::TextureView;
+use bevy_render::texture::Image;
+use bevy_render::{Render, RenderApp, RenderSet};
+use bevy_utils::HashMap;
+use iced_core::{mouse, Rectangle, Size};
+use iced_wgpu::wgpu;
+use iced_wgpu::wgpu::util::DeviceExt;
+use iced_widget::shader::{Primitive, Program, Shader, Storage};
+
+/// The textures interfaces can display straight from the GPU with
+/// [`IcedTextures::image`], without reading them back to the CPU.
+///
+/// Any [`Image`] asset can be displayed, including the render targets of cameras. Texture
+/// views created in the render world can also be displayed with
+/// [`insert`](Self::insert), by cloning this resource from the render world.
+///
+/// Requires the [`IcedTexturesPlugin`].
+#[derive(Resource, Clone, Default)]
+pub struct IcedTextures {
+    views: Arc<RwLock<HashMap<AssetId<Image>, Option<TextureView>>>>,
+}
+
+impl IcedTextures {
+    /// Create a widget displaying the texture of `image`, stretched over the bounds of the
+    /// widget.
+    ///
+    /// The texture is looked up when the frame is rendered, so it follows the image when it
+    /// is resized or recreated. Nothing is displayed until the image is on the GPU.
+    pub fn image<Message>(&self, image: impl Into<AssetId<Image>>) -> Shader<Message, GpuImage> {
+        let id = image.into();
+        self.views
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(id)
+            .or_default();
+        Shader::new(GpuImage {
+            id,
+            textures: self.clone(),
+        })
+    }
+
+    /// Display `view` in the widgets created for `id`, instead of the texture of the image
+    /// with this id.
+    pub fn insert(&self, id: impl Into<AssetId<Image>>, view: TextureView) {
+        self.views
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(id.into(), Some(view));
+    }
+
+    /// Stop displaying the texture of `id`.
+    pub fn remove(&self, id: impl Into<AssetId<Image>>) {
+        self.views
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&id.into());
+    }
+
+    fn view(&self, id: AssetId<Image>) -> Option<TextureView> {
+        self.views
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&id)
+            .cloned()
+            .flatten()
+    }
+}
+
+/// Adds the [`IcedTextures`] resource to the main and render worlds, keeping the
+/// textures of the displayed images up to date.
+pub struct IcedTexturesPlugin;
+
+impl Plugin for IcedTexturesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<IcedTextures>();
+    }
+
+    fn finish(&self, app: &mut App) {
+        let textures = app.world.resource::<IcedTextures>().clone();
+        app.sub_app_mut(RenderApp)
+            .insert_resource(textures)
+            .add_systems(
+                Render,
+                update_textures
+                    .in_set(RenderSet::PrepareResources)
+                    .after(RenderSet::PrepareAssets),
+            );
+    }
+}
+
+// Point the displayed images to their current textures. Views inserted for ids without an
+// image are left as they are.
+fn update_textures(textures: Res<IcedTextures>, images: Res<RenderAssets<Image>>) {
+    let mut views = textures
+        .views
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    for (id, view) in views.iter_mut() {
+        if let Some(image) = images.get(*id) {
+            *view = Some(image.texture_view.clone());
+        }
+    }
+}
+
+/// The program of the widgets created by [`IcedTextures::image`].
+pub struct GpuImage {
+    id: AssetId<Image>,
+    textures: IcedTextures,
+}
+
+impl<Message> Program<Message> for GpuImage {
+    type State = ();
+    type Primitive = GpuImagePrimitive;
+
+    fn draw(&self, _state: &(), _cursor: mouse::Cursor, _bounds: Rectangle) -> GpuImagePrimitive {
+        GpuImagePrimitive {
+            id: self.id,
+            textures: self.textures.clone(),
+            prepared: Mutex::default(),
+        }
+    }
+}
+
+/// The primitive drawing a [`GpuImage`].
+pub struct GpuImagePrimitive {
+    id: AssetId<Image>,
+    textures: IcedTextures,
+    prepared: Mutex<Option<Prepared>>,
+}
+
+impl fmt::Debug for GpuImagePrimitive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GpuImagePrimitive")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+// The resources of a primitive for the frame being rendered.
+struct Prepared {
+    format: wgpu::TextureFormat,
+    bind_group: wgpu::BindGroup,
+}
+
+impl Primitive for GpuImagePrimitive {
+    fn prepare(
+        &self,
+        format: wgpu::TextureFormat,
+        device: &wgpu::Device,
+        _queue: &wgpu::Queue,
+        bounds: Rectangle,
+        target_size: Size<u32>,
+        scale_factor: f32,
+        storage: &mut Storage,
+    ) {
+        if !storage.has::<Pipelines>() {
+            storage.store(Pipelines::new(device));
+        }
+        let pipelines = storage.get_mut::<Pipelines>().unwrap();
+        pipelines.prepare(device, format);
+
+        let mut prepared = self.prepared.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(view) = self.textures.view(self.id) else {
+            *prepared = None;
+            return;
+        };
+        // The bounds of the widget, in normalized device coordinates.
+        let bounds = bounds * scale_factor;
+        let (width, height) = (target_size.width as f32, target_size.height as f32);
+        let rect = [
+            bounds.x / width * 2.0 - 1.0,
+            1.0 - bounds.y / height * 2.0,
+            (bounds.x + bounds.width) / width * 2.0 - 1.0,
+            1.0 - (bounds.y + bounds.height) / height * 2.0,
+        ];
+        let contents: Vec<u8> = rect.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("bevy_iced gpu image rect"),
+            contents: &contents,
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("bevy_iced gpu image"),
+            layout: &pipelines.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&pipelines.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: buffer.as_entire_binding(),
+                },
+            ],
+        });
+        *prepared = Some(Prepared { format, bind_group });
+    }
+
+    fn render(
+        &self,
+        storage: &Storage,
+        target: &wgpu::TextureView,
+        _target_size: Size<u32>,
+        viewport: Rectangle<u32>,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let prepared = self.prepared.lock().unwrap_or_else(PoisonError::into_inner);
+        let (Some(prepared), Some(pipelines)) = (prepared.as_ref(), storage.get::<Pipelines>())
+        else {
+            return;
+        };
+        let Some(pipeline) = pipelines.pipelines.get(&prepared.format) else {
+            return;
+        };
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("bevy_iced gpu image"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        // The viewport is the part of the widget that isn't clipped.
+        pass.set_scissor_rect(viewport.x, viewport.y, viewport.width, viewport.height);
+        pass.set_pipeline(pipeline);
+        pass.set_bind_group(0, &prepared.bind_group, &[]);
+        pass.draw(0..4, 0..1);
+    }
+}
+
+// The pipelines shared by every `GpuImagePrimitive`, for each target format.
+struct Pipelines {
+    shader: wgpu::ShaderModule,
+    layout: wgpu::BindGroupLayout,
+    pipeline_layout: wgpu::PipelineLayout,
+    sampler: wgpu::Sampler,
+    pipelines: HashMap<wgpu::TextureFormat, wgpu::RenderPipeline>,
+}
+
+impl Pipelines {
+    fn new(device: &wgpu::Device) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("bevy_iced gpu image"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("gpu_image.wgsl"))),
+        });
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("bevy_iced gpu image"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("bevy_iced gpu image"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("bevy_iced gpu image"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        Self {
+            shader,
+            layout,
+            pipeline_layout,
+            sampler,
+            pipelines: HashMap::default(),
+        }
+    }
+
+    fn prepare(&mut self, device: &wgpu::Device, format: wgpu::TextureFormat) {
+        self.pipelines.entry(format).or_insert_with(|| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("bevy_iced gpu image"),
+                layout: Some(&self.pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &self.shader,
+                    entry_point: "vs_main",
+                    buffers: &[],
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &self.shader,
+                    entry_point: "fs_main",
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleStrip,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+            })
+        });
+    }
+}
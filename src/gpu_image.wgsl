@@ -0,0 +1,27 @@
+struct Rect {
+    min: vec2<f32>,
+    max: vec2<f32>,
+}
+
+@group(0) @binding(0) var image: texture_2d<f32>;
+@group(0) @binding(1) var image_sampler: sampler;
+@group(0) @binding(2) var<uniform> rect: Rect;
+
+struct Output {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> Output {
+    let uv = vec2<f32>(f32(index & 1u), f32(index >> 1u));
+    var out: Output;
+    out.position = vec4<f32>(mix(rect.min, rect.max, uv), 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+@fragment
+fn fs_main(in: Output) -> @location(0) vec4<f32> {
+    return textureSample(image, image_sampler, in.uv);
+}
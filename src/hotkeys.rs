@@ -0,0 +1,195 @@
+use std::marker::PhantomData;
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::{resource_exists, Event, EventWriter, IntoSystemConfigs};
+use bevy_ecs::system::{Res, ResMut, Resource};
+use iced_core::keyboard::{self, key::Named, Key, Modifiers};
+use iced_core::Event as IcedEvent;
+
+use crate::systems;
+use crate::{IcedCacheRef, IcedEventQueue};
+
+/// A key pressed with a set of modifiers, like `Ctrl+Z` or `F5`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyChord {
+    /// The modifiers that must be held, and no others.
+    pub modifiers: Modifiers,
+    /// The key that must be pressed. Characters are matched regardless of their case.
+    pub key: Key,
+}
+
+impl KeyChord {
+    /// A chord of `key` pressed alone.
+    pub fn new(key: impl Into<KeyChordKey>) -> Self {
+        Self {
+            modifiers: Modifiers::empty(),
+            key: key.into().0,
+        }
+    }
+
+    /// Require `modifiers` to be held as well.
+    pub fn with(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers |= modifiers;
+        self
+    }
+
+    // Whether the chord types text, which is left to a focused text input.
+    fn types_text(&self) -> bool {
+        matches!(self.key, Key::Character(_))
+            && !self
+                .modifiers
+                .intersects(Modifiers::CTRL | Modifiers::ALT | Modifiers::LOGO)
+    }
+
+    fn matches(&self, key: &Key, modifiers: Modifiers) -> bool {
+        modifiers == self.modifiers
+            && match (&self.key, key) {
+                (Key::Character(a), Key::Character(b)) => a.to_lowercase() == b.to_lowercase(),
+                (a, b) => a == b,
+            }
+    }
+}
+
+/// A key a [`KeyChord`] can be built from: a character or a [`Named`] key.
+pub struct KeyChordKey(Key);
+
+impl From<char> for KeyChordKey {
+    fn from(c: char) -> Self {
+        Self(Key::Character(c.to_lowercase().collect::<String>().into()))
+    }
+}
+
+impl From<Named> for KeyChordKey {
+    fn from(named: Named) -> Self {
+        Self(Key::Named(named))
+    }
+}
+
+/// `key` pressed alone, e.g. `key(Named::F5)`.
+pub fn key(key: impl Into<KeyChordKey>) -> KeyChord {
+    KeyChord::new(key)
+}
+
+/// `key` pressed with Ctrl, e.g. `ctrl('z')`.
+pub fn ctrl(key: impl Into<KeyChordKey>) -> KeyChord {
+    KeyChord::new(key).with(Modifiers::CTRL)
+}
+
+/// `key` pressed with Shift.
+pub fn shift(key: impl Into<KeyChordKey>) -> KeyChord {
+    KeyChord::new(key).with(Modifiers::SHIFT)
+}
+
+/// `key` pressed with Alt.
+pub fn alt(key: impl Into<KeyChordKey>) -> KeyChord {
+    KeyChord::new(key).with(Modifiers::ALT)
+}
+
+/// The keyboard shortcuts of the interfaces with messages of type `M`.
+///
+/// A shortcut sends its message whenever its chord is pressed, whether an interface is
+/// displayed or not, and the key press is then hidden from the interfaces. While a text
+/// input has focus, chords typing text (without Ctrl, Alt or Logo) are left to the input.
+///
+/// ```ignore
+/// app.insert_resource(
+///     IcedHotkeys::<UiMessage>::new()
+///         .add(ctrl('z'), || UiMessage::Undo)
+///         .add(key(Named::F5), || UiMessage::Reload),
+/// );
+/// ```
+///
+/// Requires the [`IcedHotkeysPlugin`] for `M`.
+#[derive(Resource)]
+pub struct IcedHotkeys<M> {
+    /// Whether the shortcuts are active, e.g. `false` while a modal dialog is open.
+    pub enabled: bool,
+    shortcuts: Vec<(KeyChord, Box<dyn Fn() -> M + Send + Sync>)>,
+}
+
+impl<M> Default for IcedHotkeys<M> {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            shortcuts: Vec::new(),
+        }
+    }
+}
+
+impl<M> IcedHotkeys<M> {
+    /// Create a registry without shortcuts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send the message built by `message` when `chord` is pressed.
+    pub fn add(mut self, chord: KeyChord, message: impl Fn() -> M + Send + Sync + 'static) -> Self {
+        self.shortcuts.push((chord, Box::new(message)));
+        self
+    }
+
+    /// Remove the shortcuts of `chord`.
+    pub fn remove(&mut self, chord: &KeyChord) {
+        self.shortcuts.retain(|(shortcut, _)| shortcut != chord);
+    }
+}
+
+/// Sends the messages of the [`IcedHotkeys`] of `M`, when the resource exists.
+pub struct IcedHotkeysPlugin<M>(PhantomData<fn() -> M>);
+
+impl<M> Default for IcedHotkeysPlugin<M> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<M: Event> Plugin for IcedHotkeysPlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            dispatch_hotkeys::<M>
+                .run_if(resource_exists::<IcedHotkeys<M>>)
+                .after(systems::InputSet::Replay)
+                .before(systems::detect_updates),
+        );
+    }
+}
+
+fn dispatch_hotkeys<M: Event>(
+    hotkeys: Res<IcedHotkeys<M>>,
+    cache: IcedCacheRef,
+    mut event_queue: ResMut<IcedEventQueue>,
+    mut messages: EventWriter<M>,
+) {
+    if !hotkeys.enabled {
+        return;
+    }
+    let typing = cache.has_focused_input();
+    let shortcut = |key: &Key, modifiers: Modifiers| {
+        hotkeys
+            .shortcuts
+            .iter()
+            .filter(|(chord, _)| !(typing && chord.types_text()))
+            .find(|(chord, _)| chord.matches(key, modifiers))
+    };
+    event_queue.retain(|event| {
+        let IcedEvent::Keyboard(keyboard::Event::KeyPressed {
+            key,
+            modifiers,
+            text,
+            ..
+        }) = event
+        else {
+            return true;
+        };
+        let Some((_, message)) = shortcut(key, *modifiers) else {
+            return true;
+        };
+        // Characters are sent twice, as a key press and as text: only the key press sends
+        // the message, and both are hidden.
+        if text.is_none() {
+            messages.send(message());
+        }
+        false
+    });
+}
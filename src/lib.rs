@@ -32,21 +32,44 @@ use std::any::{Any, TypeId};
 
 use std::borrow::Cow;
 use std::sync::Arc;
-use std::sync::Mutex;
+use std::sync::{LockResult, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
 
-use crate::render::{extract_iced_data, IcedNode, ViewportResource};
+use crate::backdrop::{BackdropRegion, FindBackdrops};
+use crate::render::{
+    extract_iced_data, extract_iced_layers, DrawnLayer, HitRegion, LayerTarget, PresentedFrame,
+    ViewStage, ViewportBounds,
+};
+use crate::utils::Stage;
 
-use bevy_app::{App, Plugin, Update};
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy_core_pipeline::core_2d::graph::{Core2d, Node2d};
+use bevy_core_pipeline::core_3d::graph::{Core3d, Node3d};
 use bevy_derive::{Deref, DerefMut};
-use bevy_ecs::prelude::{EventWriter, Query, With};
-use bevy_ecs::system::{NonSendMut, Res, ResMut, Resource, SystemParam};
+use bevy_ecs::prelude::{
+    Bundle, Component, Entity, Event, EventWriter, IntoSystemConfigs, IntoSystemSetConfigs, Query,
+    With, Without, World,
+};
+#[cfg(feature = "reflect")]
+use bevy_ecs::reflect::ReflectResource;
+use bevy_ecs::schedule::ScheduleLabel;
+use bevy_ecs::system::{NonSend, NonSendMut, Res, ResMut, Resource, SystemParam, SystemState};
 use bevy_input::touch::Touches;
-use bevy_render::render_graph::RenderGraph;
+use bevy_input::InputSystem;
+use bevy_math::{Rect, Vec2};
+#[cfg(feature = "reflect")]
+use bevy_reflect::std_traits::ReflectDefault;
+use bevy_render::render_graph::{RenderGraph, RenderLabel, ViewNodeRunner};
 use bevy_render::renderer::{RenderDevice, RenderQueue};
 use bevy_render::{ExtractSchedule, RenderApp};
+use bevy_utils::tracing::{debug, warn};
 use bevy_utils::HashMap;
-use bevy_window::{PrimaryWindow, Window};
+use bevy_window::{CursorGrabMode, PrimaryWindow, Window};
 use iced_core::mouse::Cursor;
+use iced_core::text::Renderer as _;
+use iced_core::time::Instant;
+use iced_core::Renderer as _;
+use iced_core::{Rectangle, Size, Vector};
 use iced_runtime::user_interface::UserInterface;
 use iced_widget::graphics::backend::Text;
 use iced_widget::graphics::Viewport;
@@ -58,56 +81,401 @@ use iced_widget::style::Theme;
 /// as much as possible.
 pub mod iced;
 
-mod conversions;
+/// Conversions between Bevy and Iced colors, and the color spaces interfaces are rendered in.
+pub mod color;
+
+/// Conversions between Bevy and Iced math, input and color types.
+///
+/// Neither side of these conversions is a type of `bevy_iced`, so they are provided by the
+/// [`ToIced`](convert::ToIced) and [`ToBevy`](convert::ToBevy) traits rather than `From`.
+pub mod convert;
+
+/// Confirm dialogs and text prompts displayed over the interfaces, resolving to messages.
+pub mod modals;
+
+/// Hooks seeing the input and messages of interfaces, for analytics, tutorials and the like.
+pub mod middleware;
+
+/// Paragraphs mixing text styles, colors and icons.
+pub mod rich_text;
+
+/// Hyperlinks for credits screens, error dialogs and the like.
+pub mod link;
+
+/// Text measurement and truncation helpers.
+///
+/// These shape text with the same engine used for rendering, so they stay correct with
+/// proportional fonts and non-Latin scripts.
+pub mod text;
+
+/// Common types used when building interfaces.
+///
+/// Unlike [`iced`], this module only contains types whose names and meaning are stable
+/// across Iced releases, so depending on it keeps upgrades of `bevy_iced` painless.
+pub mod ui_types;
+
+/// The extra widgets of [`iced_aw`] (tab bars, number inputs, date pickers, modals...).
+///
+/// The widgets render with `bevy_iced`'s [`Renderer`] and [`Theme`], and the icon font
+/// they use is loaded by the [`IcedPlugin`].
+#[cfg(feature = "iced_aw")]
+pub mod aw;
+
+/// Keyboard shortcuts sending messages, whether a widget has focus or not.
+pub mod hotkeys;
+
+/// Frosted glass panels, blurring the scene behind them.
+pub mod backdrop;
+
+/// Mirrored layouts, for right-to-left languages.
+pub mod mirror;
+
+/// Dragging payloads between interfaces and the world.
+pub mod drag_drop;
+
+/// Displaying Bevy images and render targets without copying them off the GPU.
+#[cfg(feature = "gpu_image")]
+pub mod gpu_image;
+
+/// Blocking `bevy_mod_picking` under interfaces.
+#[cfg(feature = "picking")]
+pub mod picking;
+
+/// Rendering interfaces on the CPU, for machines without a usable GPU.
+#[cfg(feature = "tiny_skia")]
+pub mod software;
+
+/// Presenting interfaces with a renderer of your own, instead of the built-in Iced backend.
+pub mod present_hook;
+
+/// A cursor driven by a gamepad, for console and couch play.
+pub mod virtual_cursor;
+
+/// Cursors driven by something else than the mouse, like the controllers of XR headsets.
+pub mod cursor_source;
+
+/// Events for the widget interactions games play sounds for, like hovers and clicks.
+pub mod ui_sound;
+
+/// Window icon, taskbar progress and attention requests, and the regions of custom window
+/// decorations.
+#[cfg(feature = "window_shell")]
+pub mod window_shell;
+
+/// Waking reactive applications up for the animations of their interfaces.
+#[cfg(feature = "frame_pacing")]
+pub mod frame_pacing;
+
+/// Saving scroll positions and focus across restarts.
+#[cfg(feature = "persistence")]
+pub mod persistence;
+
+/// Scale factors chosen per monitor, following the monitor the window is on.
+#[cfg(feature = "monitor_scale")]
+pub mod monitor_scale;
+
+/// Dragging files out of the application into other programs.
+#[cfg(all(feature = "file_drag", any(target_os = "windows", target_os = "macos")))]
+pub mod file_drag;
+
+/// Textured panels and buttons, stretching nine-slice images from Bevy texture atlases.
+#[cfg(feature = "nine_slice")]
+pub mod nine_slice;
+
+/// Pasting images from the system clipboard as Bevy [`Image`](bevy_render::texture::Image)s.
+#[cfg(feature = "clipboard_image")]
+pub mod clipboard_image;
+
+/// Interfaces described at runtime, for scripting.
+///
+/// Their message type can't be named at compile time, so messages are delivered as JSON
+/// values in [`IcedDynamicMessage`](dynamic::IcedDynamicMessage) events.
+#[cfg(feature = "dynamic")]
+pub mod dynamic;
+
+/// Interfaces loaded from JSON or RON assets, with hot reloading.
+#[cfg(feature = "ui_assets")]
+pub mod ui_asset;
+
+/// Capturing the interfaces alone, for bug reports and marketing material.
+#[cfg(feature = "screenshot")]
+pub mod screenshot;
+
+/// Interfaces embedded in `bevy_ui` layouts, rendered into the textures of their nodes.
+#[cfg(feature = "ui_node")]
+pub mod ui_node;
+
+/// Interfaces on panels in the world, rendered into textures, for diegetic and XR interfaces.
+#[cfg(feature = "world_panel")]
+pub mod world_panel;
+
+/// Recording the input of interfaces and replaying it, to reproduce bugs.
+#[cfg(feature = "replay")]
+pub mod replay;
+
+/// A ready-made overlay of performance figures, fed by `bevy_diagnostic`.
+#[cfg(feature = "diagnostics_overlay")]
+pub mod diagnostics_overlay;
+
+/// Loading and saving the contents of text editors, for in-game editors and consoles.
+#[cfg(feature = "text_files")]
+pub mod text_file;
+
+/// Translation of interfaces with Fluent, and runtime language switching.
+#[cfg(feature = "localization")]
+pub mod localization;
+
+/// Discovery and loading of the fonts installed on the system.
+#[cfg(feature = "system_fonts")]
+pub mod system_fonts;
+
+/// Deriving the theme of interfaces from Bevy resources, such as
+/// [`ClearColor`](bevy_render::camera::ClearColor).
+pub mod theme_sync;
+
+/// Lists of many rows, of which only the visible ones are built.
+pub mod virtual_list;
+
+/// Constructors for the most used widgets.
+///
+/// These are thin wrappers over the Iced widgets with `bevy_iced`'s [`Theme`] and
+/// [`Renderer`] filled in, kept source-compatible when the underlying Iced version changes.
+pub mod widgets;
+
+mod compat;
+mod context_menu;
+mod error;
+mod messages;
 mod render;
+mod scaled;
 mod systems;
 mod utils;
 
-use systems::IcedEventQueue;
+use error::ErrorReporter;
+pub use error::IcedError;
+use messages::MessageSender;
+pub use render::{
+    IcedActiveInterfaces, IcedNode, IcedPass, IcedRenderStats, IcedUpscaledPass, IcedViewPass,
+    ViewportResource,
+};
+use systems::IcedInteraction;
+pub use systems::{
+    iced_needs_update, EventQueuePolicy, IcedEventQueue, IcedIgnoredEvents, IcedNeedsUpdate,
+    IcedPointerOverUi, IcedUpdateFrequency, InputForwarding, KeyRepeat, KineticScroll,
+};
 
 /// The default renderer.
 pub type Renderer = iced_renderer::Renderer;
 
+/// A drawing primitive of the [`Renderer`], for [`IcedContext::draw_primitives`].
+pub type Primitive = compat::Primitive;
+
 /// The main feature of `bevy_iced`.
 /// Add this to your [`App`] by calling `app.add_plugin(bevy_iced::IcedPlugin::default())`.
+///
+/// The input of a frame is processed in [`PreUpdate`], after [`InputSystem`], then the
+/// [`IcedUpdate`] schedule runs. Interfaces displayed in [`Update`](bevy_app::Update) see
+/// the input of the same frame, but input events sent from `Update` (e.g. synthetic
+/// keyboard events) reach them on the next frame: send them in [`PreUpdate`] before
+/// [`InputSystem`] instead, or queue them with [`IcedContext::inject_event`].
 #[derive(Default)]
 pub struct IcedPlugin {
     /// The settings that Iced should use.
     pub settings: iced::Settings,
     /// Font file contents
     pub fonts: Vec<&'static [u8]>,
+    /// Where interfaces are presented.
+    pub target: PresentTarget,
+    /// The clipboard used by text inputs, for platforms with their own (consoles, web
+    /// views...). Setting this to `None` uses a clipboard that is always empty.
+    pub clipboard: Option<IcedClipboard>,
+    /// When interfaces are rendered on the CPU instead of the GPU.
+    #[cfg(feature = "tiny_skia")]
+    pub software: software::SoftwareRendering,
+}
+
+/// A clipboard implementation shared with the [`IcedPlugin`].
+pub type IcedClipboard = Arc<Mutex<dyn iced_core::clipboard::Clipboard + Send>>;
+
+impl IcedPlugin {
+    /// Use `clipboard` for the text inputs of every interface.
+    pub fn with_clipboard(
+        mut self,
+        clipboard: impl iced_core::clipboard::Clipboard + Send + 'static,
+    ) -> Self {
+        self.clipboard = Some(Arc::new(Mutex::new(clipboard)));
+        self
+    }
+}
+
+/// Where the [`IcedPlugin`] presents interfaces.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PresentTarget {
+    /// Present to the primary window, over everything rendered to it (see [`IcedPass`]).
+    #[default]
+    Window,
+    /// Present into the main texture of the camera marked with [`IcedCamera`], as part of
+    /// its post-processing chain (see [`IcedViewPass`]).
+    ///
+    /// This lets post-processing effects, like antialiasing, be deliberately applied to
+    /// interfaces or not. When the camera renders at a lower resolution than its target
+    /// (dynamic resolution, upscalers), interfaces are instead presented after upscaling
+    /// (see [`IcedUpscaledPass`]), so they stay at the native resolution.
+    Camera,
 }
 
 impl Plugin for IcedPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (systems::process_input, render::update_viewport))
-            .insert_resource(DidDraw::default())
+        app.init_schedule(IcedUpdate)
+            .add_systems(
+                PreUpdate,
+                (
+                    reset_display_slots,
+                    systems::process_input,
+                    middleware::apply_event_filters,
+                    systems::track_lifecycle,
+                    render::update_viewport,
+                    systems::detect_updates,
+                    run_iced_update,
+                )
+                    .chain()
+                    .after(InputSystem),
+            )
+            .configure_sets(
+                PreUpdate,
+                (systems::InputSet::Sources, systems::InputSet::Replay)
+                    .chain()
+                    .after(systems::process_input)
+                    .before(middleware::apply_event_filters),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    systems::apply_cursor_icon,
+                    systems::apply_virtual_keyboard,
+                    systems::update_pointer_over_ui,
+                    systems::request_redraw,
+                ),
+            )
+            .add_event::<IcedError>()
+            .add_event::<UiFrameStats>()
+            .add_event::<IcedTextInputFocused>()
+            .insert_resource(IcedInteraction::default())
             .insert_resource(IcedSettings::default())
-            .insert_non_send_resource(IcedCache::default())
-            .insert_resource(IcedEventQueue::default());
+            .insert_resource(IcedEventQueue::default())
+            .init_resource::<middleware::IcedEventFilters>()
+            .init_resource::<systems::InputReaders>()
+            .insert_resource(IcedIgnoredEvents::default())
+            .insert_resource(IcedNeedsUpdate::default())
+            .insert_resource(IcedPointerOverUi::default())
+            .insert_resource(systems::IcedLifecycle::default())
+            .insert_resource(IcedRenderStats::default())
+            .init_resource::<IcedActiveInterfaces>();
+        app.insert_non_send_resource(IcedCache::default());
+
+        #[cfg(feature = "reflect")]
+        app.register_type::<IcedSettings>()
+            .register_type::<IcedTheme>()
+            .register_type::<IcedStyle>()
+            .register_type::<Compositing>()
+            .register_type::<EventQueuePolicy>()
+            .register_type::<InputForwarding>()
+            .register_type::<IcedUpdateFrequency>()
+            .register_type::<TextDirection>()
+            .register_type::<LockedCursor>();
     }
 
     fn finish(&self, app: &mut App) {
         let default_viewport = Viewport::with_physical_size(iced_core::Size::new(1600, 900), 1.0);
+        let default_bounds =
+            ViewportBounds::new(Rectangle::with_size(default_viewport.logical_size()));
         let default_viewport = ViewportResource(default_viewport);
         let iced_resource: IcedResource = IcedProps::new(app, self).into();
 
         app.insert_resource(default_viewport.clone())
-            .insert_resource(iced_resource.clone());
+            .insert_resource(default_bounds)
+            .insert_resource(iced_resource);
+        if self.target == PresentTarget::Camera {
+            app.add_systems(PostUpdate, render::warn_camera_compositing);
+        }
 
         let render_app = app.sub_app_mut(RenderApp);
+        #[allow(unused_mut)]
+        let mut presented = PresentedFrame::new(self.settings);
+        #[cfg(feature = "tiny_skia")]
+        {
+            presented.software = self.software.is_enabled(render_app.world.get_resource());
+            if presented.software {
+                bevy_utils::tracing::info!("Iced: rendering interfaces in software");
+            }
+        }
         render_app
             .insert_resource(default_viewport)
-            .insert_resource(iced_resource)
-            .add_systems(ExtractSchedule, extract_iced_data);
-        setup_pipeline(&mut render_app.world.get_resource_mut().unwrap());
+            .insert_resource(presented)
+            .add_systems(
+                ExtractSchedule,
+                (extract_iced_data, extract_iced_layers).chain(),
+            );
+        match self.target {
+            PresentTarget::Window => {
+                setup_pipeline(&mut render_app.world.get_resource_mut().unwrap())
+            }
+            PresentTarget::Camera => {
+                add_view_node(
+                    &mut render_app.world,
+                    IcedViewPass,
+                    ViewStage::PostProcessing,
+                    (Node2d::Tonemapping, Node3d::Tonemapping),
+                    Some((
+                        Node2d::EndMainPassPostProcessing,
+                        Node3d::EndMainPassPostProcessing,
+                    )),
+                );
+                add_view_node(
+                    &mut render_app.world,
+                    IcedUpscaledPass,
+                    ViewStage::Upscaled,
+                    (Node2d::Upscaling, Node3d::Upscaling),
+                    None::<(Node2d, Node3d)>,
+                );
+            }
+        }
+        #[cfg(feature = "bevy_ui")]
+        add_view_node(
+            &mut render_app.world,
+            render::IcedBelowUiPass,
+            ViewStage::BelowUi,
+            (
+                Node2d::EndMainPassPostProcessing,
+                Node3d::EndMainPassPostProcessing,
+            ),
+            Some((
+                bevy_ui::graph::NodeUi::UiPass,
+                bevy_ui::graph::NodeUi::UiPass,
+            )),
+        );
     }
 }
 
+// The state interfaces are built with, which only the main world uses.
+//
+// The render world never sees it: the layers drawn during a frame are moved out of it at
+// extraction (see `extract_iced_layers`), and presented with backends of its own.
 struct IcedProps {
+    // Lays out and draws interfaces, its backend is never presented with.
     renderer: Renderer,
     debug: iced_runtime::Debug,
-    clipboard: iced_core::clipboard::Null,
+    clipboard: IcedClipboard,
+    // The interfaces drawn during the frame being built, in display order.
+    layers: Vec<DrawnLayer>,
+    // The layers of the last extracted frame, presented again for the interfaces that
+    // aren't drawn on the frames they don't need an update.
+    presented_layers: Vec<DrawnLayer>,
+    // The visible areas of the layers of the last extracted frame, in logical pixels of
+    // the window.
+    hit_regions: Vec<HitRegion>,
+    #[cfg(feature = "persistence")]
+    persisted: persistence::PersistedState,
 }
 
 impl IcedProps {
@@ -119,69 +487,347 @@ impl IcedProps {
             .wgpu_device();
         let queue = render_world.get_resource::<RenderQueue>().unwrap();
         let mut backend =
-            iced_wgpu::Backend::new(device, queue.as_ref(), config.settings, render::TEXTURE_FMT);
+            compat::new_backend(device, queue.as_ref(), config.settings, render::TEXTURE_FMT);
         for font in &config.fonts {
             backend.load_font(Cow::Borrowed(*font));
         }
+        #[cfg(feature = "iced_aw")]
+        backend.load_font(Cow::Borrowed(iced_aw::BOOTSTRAP_FONT_BYTES));
 
         Self {
-            renderer: Renderer::Wgpu(iced_wgpu::Renderer::new(
-                backend,
-                config.settings.default_font,
-                config.settings.default_text_size,
-            )),
+            renderer: compat::new_renderer(backend, &config.settings),
             debug: iced_runtime::Debug::new(),
-            clipboard: iced_core::clipboard::Null,
+            clipboard: config
+                .clipboard
+                .clone()
+                .unwrap_or_else(|| Arc::new(Mutex::new(iced_core::clipboard::Null))),
+            layers: Vec::new(),
+            presented_layers: Vec::new(),
+            hit_regions: Vec::new(),
+            #[cfg(feature = "persistence")]
+            persisted: Default::default(),
         }
     }
 }
 
-#[derive(Resource, Clone)]
-struct IcedResource(Arc<Mutex<IcedProps>>);
+// The mutex is never contended: it only makes the props `Sync`, and lets an interface
+// that panicked while drawing leave them poisoned rather than half-updated.
+#[derive(Resource)]
+struct IcedResource(Mutex<IcedProps>);
 
 impl IcedResource {
-    fn lock(&self) -> std::sync::LockResult<std::sync::MutexGuard<IcedProps>> {
+    fn lock(&self) -> LockResult<MutexGuard<'_, IcedProps>> {
         self.0.lock()
     }
+
+    // Lock the props, clearing the poison left by a panic while they were locked.
+    //
+    // Nothing needs to be reset here: the renderer is cleared before every draw, and the
+    // cache of the interface that panicked was taken out of the `IcedCache` for the
+    // duration of its `display`, so it starts over from a fresh state on the next frame.
+    fn lock_or_recover(&self, errors: &mut ErrorReporter) -> MutexGuard<'_, IcedProps> {
+        self.0.lock().unwrap_or_else(|poisoned| {
+            self.0.clear_poison();
+            errors.report(IcedError::Poisoned);
+            poisoned.into_inner()
+        })
+    }
 }
 
 impl From<IcedProps> for IcedResource {
     fn from(value: IcedProps) -> Self {
-        Self(Arc::new(Mutex::new(value)))
+        Self(Mutex::new(value))
     }
 }
 
 fn setup_pipeline(graph: &mut RenderGraph) {
-    graph.add_node(render::IcedPass, IcedNode::new());
+    graph.add_node(IcedPass, IcedNode::new());
+
+    graph.add_node_edge(bevy_render::graph::CameraDriverLabel, IcedPass);
+}
+
+// Add a node presenting Iced layers to the 2D and 3D camera graphs, after the nodes in
+// `after` and before the ones in `before`.
+fn add_view_node(
+    world: &mut World,
+    label: impl RenderLabel + Clone,
+    stage: ViewStage,
+    after: (Node2d, Node3d),
+    before: Option<(impl RenderLabel, impl RenderLabel)>,
+) {
+    let node_2d = ViewNodeRunner::new(render::IcedViewNode::new(stage), world);
+    let node_3d = ViewNodeRunner::new(render::IcedViewNode::new(stage), world);
+    let (before_2d, before_3d) = before.unzip();
+    let mut graph = world.resource_mut::<RenderGraph>();
+    if let Some(graph) = graph.get_sub_graph_mut(Core2d) {
+        graph.add_node(label.clone(), node_2d);
+        let _ = graph.try_add_node_edge(after.0, label.clone());
+        if let Some(before) = before_2d {
+            let _ = graph.try_add_node_edge(label.clone(), before);
+        }
+    }
+    if let Some(graph) = graph.get_sub_graph_mut(Core3d) {
+        graph.add_node(label.clone(), node_3d);
+        let _ = graph.try_add_node_edge(after.1, label.clone());
+        if let Some(before) = before_3d {
+            let _ = graph.try_add_node_edge(label, before);
+        }
+    }
+}
 
-    graph.add_node_edge(bevy_render::graph::CameraDriverLabel, render::IcedPass);
+// Interface caches are kept per message type, key (for interfaces displayed with
+// `IcedContext::display_keyed`), and call order among the displays of a frame sharing both,
+// which is reported as `IcedError::DuplicateDisplay`.
+type CacheKey = (TypeId, Option<String>, usize);
+
+fn cache_key<M: Any>(key: Option<&str>, slot: usize) -> CacheKey {
+    (TypeId::of::<M>(), key.map(str::to_owned), slot)
 }
 
+// Where the widget state of interfaces is kept. Widgets like `memo`, `responsive` and the
+// text editor keep `Rc`s in their state, so it is a non-send resource.
+type IcedCacheMut<'w> = NonSendMut<'w, IcedCache>;
+type IcedCacheRef<'w> = NonSend<'w, IcedCache>;
+
+// The widget state of an interface between two displays.
+#[derive(Default)]
+struct WidgetCache(Option<iced_runtime::user_interface::Cache>);
+
 #[derive(Default)]
 struct IcedCache {
-    cache: HashMap<TypeId, Option<iced_runtime::user_interface::Cache>>,
+    cache: HashMap<CacheKey, WidgetCache>,
+    // Message buffers reused across frames, each holding a `Vec<M>`.
+    messages: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    // The bounds of the text input focused in each interface.
+    focused_inputs: HashMap<CacheKey, Rectangle>,
+    budgets: HashMap<CacheKey, BudgetState>,
+    // The number of displays of this frame for each message type and key.
+    slots: HashMap<(TypeId, Option<String>), usize>,
+    // The caches of the interfaces displayed in tool windows and `bevy_ui` nodes.
+    targets: HashMap<(Entity, TypeId), WidgetCache>,
+    // The context menu open for each message type.
+    context_menus: HashMap<TypeId, context_menu::OpenMenu>,
+}
+
+// How an interface fares with `IcedSettings::frame_budget`.
+#[derive(Default)]
+struct BudgetState {
+    // Whether the last build of the interface went over the budget.
+    over_budget: bool,
+    // Whether the last frame presented the previous build again.
+    skipped: bool,
+    // The primitives and backdrops of the last build, when it went over the budget.
+    primitives: Vec<Primitive>,
+    backdrops: Vec<BackdropRegion>,
+    // The input of skipped frames, processed by the next build.
+    deferred_events: Vec<iced::Event>,
 }
 
 impl IcedCache {
-    fn get<M: Any>(&mut self) -> &mut Option<iced_runtime::user_interface::Cache> {
-        let id = TypeId::of::<M>();
-        if !self.cache.contains_key(&id) {
-            self.cache.insert(id, Some(Default::default()));
+    fn get(&mut self, id: &CacheKey) -> &mut Option<iced_runtime::user_interface::Cache> {
+        &mut self
+            .cache
+            .entry(id.clone())
+            .or_insert_with(|| WidgetCache(Some(Default::default())))
+            .0
+    }
+
+    // The cache slot of the next display of this frame for a message type and key.
+    fn next_slot<M: Any>(&mut self, key: Option<&str>) -> usize {
+        let count = self
+            .slots
+            .entry((TypeId::of::<M>(), key.map(str::to_owned)))
+            .or_default();
+        *count += 1;
+        *count - 1
+    }
+
+    // Wrap an interface in the host of the context menu of its message type, shown over its
+    // first unkeyed interface.
+    fn with_context_menu<'a, M: 'static>(
+        &self,
+        id: &CacheKey,
+        element: iced_core::Element<'a, M, Theme, Renderer>,
+    ) -> iced_core::Element<'a, M, Theme, Renderer> {
+        let menu = match id {
+            (_, None, 0) => self.context_menus.get(&id.0),
+            _ => None,
+        };
+        context_menu::host(element, menu)
+    }
+
+    fn remove_closed_menu(&mut self, id: &CacheKey) {
+        if self
+            .context_menus
+            .get(&id.0)
+            .is_some_and(context_menu::OpenMenu::is_closed)
+        {
+            self.context_menus.remove(&id.0);
         }
-        self.cache.get_mut(&id).unwrap()
     }
+
+    fn messages<M: Any + Send + Sync>(&mut self) -> &mut Vec<M> {
+        self.messages
+            .entry(TypeId::of::<M>())
+            .or_insert_with(|| Box::<Vec<M>>::default())
+            .downcast_mut()
+            .unwrap()
+    }
+
+    // Record the focused text input of an interface, returning whether it changed.
+    fn set_focused_input(&mut self, id: &CacheKey, bounds: Option<Rectangle>) -> bool {
+        let previous = match bounds {
+            Some(bounds) => self.focused_inputs.insert(id.clone(), bounds),
+            None => self.focused_inputs.remove(id),
+        };
+        previous != bounds
+    }
+
+    // Whether a text input has focus in any interface.
+    fn has_focused_input(&self) -> bool {
+        !self.focused_inputs.is_empty()
+    }
+
+    fn budget(&mut self, id: &CacheKey) -> &mut BudgetState {
+        self.budgets.entry(id.clone()).or_default()
+    }
+
+    fn contains<M: Any>(&self) -> bool {
+        self.cache.contains_key(&cache_key::<M>(None, 0))
+    }
+}
+
+fn reset_display_slots(mut cache: IcedCacheMut) {
+    cache.slots.clear();
 }
 
 /// Settings used to independently customize Iced rendering.
+///
+/// With the `reflect` feature, they can be inspected and edited by reflection-based tools.
 #[derive(Clone, Resource)]
+#[cfg_attr(
+    feature = "reflect",
+    derive(bevy_reflect::Reflect),
+    reflect(Resource, Default)
+)]
 pub struct IcedSettings {
     /// The scale factor to use for rendering Iced elements.
     /// Setting this to `None` defaults to using the `Window`s scale factor.
     pub scale_factor: Option<f64>,
     /// The theme to use for rendering Iced elements.
-    pub theme: iced_widget::style::Theme,
+    pub theme: IcedTheme,
     /// The style to use for rendering Iced elements.
-    pub style: iced::Style,
+    pub style: IcedStyle,
+    /// The layout time above which [`IcedContext::display_progressive`] shows its
+    /// placeholder instead of the real interface on the first frame.
+    /// Setting this to `None` disables frame splitting.
+    pub split_threshold: Option<Duration>,
+    /// The time an interface may spend being built and updated every frame.
+    ///
+    /// A warning is logged when an interface goes over it, and [`UiFrameStats`] gives the
+    /// detailed timings. Setting this to `None` disables the budget.
+    pub frame_budget: Option<Duration>,
+    /// Whether interfaces going over [`frame_budget`](Self::frame_budget) are only rebuilt
+    /// every other frame.
+    ///
+    /// On the frames in between, the last build is presented again and the input is left
+    /// to the next build. This halves the cost of the interface, at the price of latency.
+    pub skip_over_budget: bool,
+    /// How the input of a frame is trimmed before interfaces process it.
+    pub event_queue: EventQueuePolicy,
+    /// The categories of input forwarded to interfaces.
+    pub input: InputForwarding,
+    /// How often interfaces are updated, independently of the frame rate.
+    ///
+    /// Systems displaying interfaces should run with the [`iced_needs_update`] condition:
+    /// on the frames in between updates, the last update is presented again, and the input
+    /// is left to the next update. This lets a complex interface update at 30 Hz while the
+    /// game renders at 144 Hz.
+    pub update_frequency: IcedUpdateFrequency,
+    /// How interfaces are composited with what Bevy rendered to the window.
+    pub compositing: Compositing,
+    /// Whether message types used by an [`IcedContext`] without being registered with
+    /// `app.add_event()` are registered automatically.
+    ///
+    /// When this is `false`, such messages are discarded and an [`IcedError`] is sent.
+    /// Otherwise, they are only discarded until the registration takes effect, at the end
+    /// of the system displaying the interface.
+    pub auto_register_messages: bool,
+    /// The area of the window, in logical pixels, that interfaces are confined to.
+    ///
+    /// Interfaces are laid out and clipped to this rectangle, and cursor positions are
+    /// mapped into it. This takes precedence over the viewport of an [`IcedCamera`].
+    /// Setting this to `None` uses the whole window.
+    pub bounds: Option<Rect>,
+    /// A fixed scale to rasterize interfaces at, before stretching them to the window.
+    ///
+    /// Text is rasterized for a given scale, so animating [`scale_factor`](Self::scale_factor)
+    /// (e.g. to zoom in and out) makes it shimmer. With a fixed scale, interfaces are
+    /// rendered to an offscreen texture that is scaled when presenting, which keeps zoom
+    /// animations stable at the cost of sharpness. Setting this to `None` rasterizes
+    /// interfaces at the scale factor directly.
+    pub fixed_scale: Option<f64>,
+    /// The opacity of all interfaces, from `0.0` (invisible) to `1.0` (opaque).
+    ///
+    /// Animate it from a system to fade interfaces in and out, e.g. around cutscenes. The
+    /// interfaces are faded as a whole when presenting, so overlapping widgets don't show
+    /// through each other, and they keep processing input. Below `1.0`, interfaces are
+    /// rendered to an offscreen texture first, like with [`fixed_scale`](Self::fixed_scale).
+    /// Single interfaces can be faded with
+    /// [`IcedContext::display_with_opacity`](crate::IcedContext::display_with_opacity).
+    pub opacity: f32,
+    /// The direction interfaces are written in, for right-to-left languages.
+    ///
+    /// Interfaces displayed with [`IcedContext::display`] read it with
+    /// [`IcedContext::text_direction`], and use the helpers of [`TextDirection`] and
+    /// [`widgets`] to lay themselves out. Interfaces displayed with
+    /// [`IcedContext::display_with_direction`] have their own direction, and their layout
+    /// is mirrored for them.
+    pub text_direction: TextDirection,
+    /// Whether the virtual keyboard of the OS is requested when a text input is focused.
+    ///
+    /// This enables IME input on the window while a text input has focus, which shows the
+    /// soft keyboard on platforms supporting it, and positions IME candidates below the
+    /// input on desktop. It defaults to `true` on Android and iOS only.
+    pub virtual_keyboard: bool,
+    /// A multiplier applied to mouse wheel deltas, in lines or pixels depending on the
+    /// device.
+    pub scroll_sensitivity: f32,
+    /// The cursor interfaces see while the window cursor is locked or hidden, e.g. for
+    /// first-person controls.
+    pub locked_cursor: LockedCursor,
+    /// Whether interfaces read the input sent since the last interface was displayed, right
+    /// before processing their input.
+    ///
+    /// Input is read once per frame in [`PreUpdate`], so events sent later in the frame,
+    /// e.g. by systems emulating the mouse with a gamepad or replaying recorded input, only
+    /// reach interfaces on the next frame. With this, interfaces read them as soon as they
+    /// are displayed, which removes that frame of latency for reactive HUDs. The late input
+    /// is trimmed and filtered like the rest, but doesn't set [`IcedNeedsUpdate`]: systems
+    /// skipped by [`iced_needs_update`] leave it to the next frame.
+    pub direct_input: bool,
+    /// Repeat the key held down while a text input is focused, for the platforms that only
+    /// report the first press, so that e.g. holding backspace keeps deleting.
+    ///
+    /// Repeats stop being synthesized as soon as the platform is seen repeating keys by
+    /// itself. Setting this to `None` leaves repeats to the platform.
+    pub key_repeat: Option<KeyRepeat>,
+    /// Whether touch drags keep scrolling after the finger is lifted, slowing down until
+    /// they stop or another touch interrupts them.
+    ///
+    /// The scrolling is sent to interfaces as wheel events under the point where the
+    /// finger was lifted, on the primary window. It defaults to `Some` on Android and iOS
+    /// only.
+    pub kinetic_scroll: Option<KineticScroll>,
+    /// The scale factors to use on given monitors, by name, in place of
+    /// [`scale_factor`](Self::scale_factor).
+    ///
+    /// The monitor of the primary window is followed by the
+    /// [`IcedMonitorScalePlugin`](monitor_scale::IcedMonitorScalePlugin), and the names of
+    /// monitors are the ones of [`IcedCurrentMonitor`](monitor_scale::IcedCurrentMonitor).
+    /// Tool windows aren't affected.
+    #[cfg(feature = "monitor_scale")]
+    pub monitor_scale_factors: HashMap<String, f64>,
 }
 
 impl IcedSettings {
@@ -195,17 +841,278 @@ impl Default for IcedSettings {
     fn default() -> Self {
         Self {
             scale_factor: None,
-            theme: iced_widget::style::Theme::Dark,
+            theme: iced_widget::style::Theme::Dark.into(),
             style: iced::Style {
                 text_color: iced_core::Color::WHITE,
-            },
+            }
+            .into(),
+            split_threshold: Some(Duration::from_millis(4)),
+            frame_budget: None,
+            skip_over_budget: false,
+            event_queue: EventQueuePolicy::default(),
+            input: InputForwarding::default(),
+            update_frequency: IcedUpdateFrequency::default(),
+            compositing: Compositing::default(),
+            auto_register_messages: false,
+            bounds: None,
+            fixed_scale: None,
+            opacity: 1.0,
+            text_direction: TextDirection::default(),
+            virtual_keyboard: cfg!(any(target_os = "android", target_os = "ios")),
+            scroll_sensitivity: 1.0,
+            locked_cursor: LockedCursor::default(),
+            direct_input: false,
+            key_repeat: None,
+            kinetic_scroll: cfg!(any(target_os = "android", target_os = "ios"))
+                .then(KineticScroll::default),
+            #[cfg(feature = "monitor_scale")]
+            monitor_scale_factors: HashMap::default(),
         }
     }
 }
 
-// An atomic flag for updating the draw state.
-#[derive(Resource, Deref, DerefMut, Default)]
-pub(crate) struct DidDraw(std::sync::atomic::AtomicBool);
+/// The [`Theme`] of [`IcedSettings`].
+///
+/// Iced types can't be reflected, so this wrapper is reflected as an opaque value with the
+/// `reflect` feature.
+#[derive(Clone, Debug, PartialEq, Deref, DerefMut)]
+#[cfg_attr(
+    feature = "reflect",
+    derive(bevy_reflect::Reflect),
+    reflect_value(Debug, PartialEq)
+)]
+pub struct IcedTheme(pub Theme);
+
+impl From<Theme> for IcedTheme {
+    fn from(theme: Theme) -> Self {
+        Self(theme)
+    }
+}
+
+/// The [`Style`](iced::Style) of [`IcedSettings`].
+///
+/// Iced types can't be reflected, so this wrapper is reflected as an opaque value with the
+/// `reflect` feature.
+#[derive(Clone, Copy, Debug, Deref, DerefMut)]
+#[cfg_attr(
+    feature = "reflect",
+    derive(bevy_reflect::Reflect),
+    reflect_value(Debug)
+)]
+pub struct IcedStyle(pub iced::Style);
+
+impl From<iced::Style> for IcedStyle {
+    fn from(style: iced::Style) -> Self {
+        Self(style)
+    }
+}
+
+/// How interfaces are composited with the contents of the window.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub enum Compositing {
+    /// Draw interfaces over what Bevy rendered to the window.
+    #[default]
+    Over,
+    /// Clear the window to transparent before drawing interfaces, discarding what Bevy
+    /// rendered to it.
+    ///
+    /// This is meant for overlay tools, whose window only shows widgets: combined with
+    /// [`Window::transparent`] and a non-opaque [`Window::composite_alpha_mode`], the
+    /// alpha of the widgets is preserved and the OS compositor shows what is behind the
+    /// window everywhere else. It only applies when presenting to
+    /// [`PresentTarget::Window`]: interfaces presented into a camera are always drawn over
+    /// its scene.
+    Transparent,
+}
+
+/// The cursor interfaces see while the window cursor is locked
+/// ([`CursorGrabMode::Locked`](bevy_window::CursorGrabMode::Locked)) or hidden.
+///
+/// The position reported by the window is meaningless then, so it isn't used by default.
+/// In every case, an active [`IcedVirtualCursor`](virtual_cursor::IcedVirtualCursor) or
+/// [`IcedCursorSource`](cursor_source::IcedCursorSource) takes precedence.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub enum LockedCursor {
+    /// Interfaces see no cursor: nothing is hovered, and clicks don't reach widgets.
+    #[default]
+    Unavailable,
+    /// The cursor is at the center of the viewport, like the crosshair of a first-person
+    /// game aiming at an in-game screen.
+    Centered,
+    /// The cursor is where the window reports it. Switch to this when a menu opens
+    /// without releasing the cursor.
+    Window,
+}
+
+/// The direction text and layouts flow in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub enum TextDirection {
+    /// Left to right, as in English.
+    #[default]
+    LeftToRight,
+    /// Right to left, as in Arabic or Hebrew.
+    RightToLeft,
+}
+
+impl TextDirection {
+    /// Whether this is [`TextDirection::RightToLeft`].
+    pub fn is_rtl(self) -> bool {
+        self == Self::RightToLeft
+    }
+
+    /// Put `items`, listed in reading order, in the left-to-right order of the screen.
+    pub fn order<T>(self, mut items: Vec<T>) -> Vec<T> {
+        if self.is_rtl() {
+            items.reverse();
+        }
+        items
+    }
+
+    /// The side reading starts from.
+    pub fn start(self) -> iced_core::alignment::Horizontal {
+        match self {
+            Self::LeftToRight => iced_core::alignment::Horizontal::Left,
+            Self::RightToLeft => iced_core::alignment::Horizontal::Right,
+        }
+    }
+
+    /// The side reading ends at.
+    pub fn end(self) -> iced_core::alignment::Horizontal {
+        match self {
+            Self::LeftToRight => iced_core::alignment::Horizontal::Right,
+            Self::RightToLeft => iced_core::alignment::Horizontal::Left,
+        }
+    }
+}
+
+// Per-call overrides of how an interface is displayed.
+#[derive(Default)]
+struct DisplayOptions<'o> {
+    theme: Option<&'o Theme>,
+    style: Option<&'o iced::Style>,
+    below_ui: bool,
+    layer: i32,
+    key: Option<&'o str>,
+    opacity: Option<f32>,
+    direction: Option<TextDirection>,
+    // Whether the interface is only updated, for `IcedContext::update`.
+    update_only: bool,
+}
+
+/// The schedule in which interfaces can be updated ahead of [`Update`](bevy_app::Update),
+/// with [`IcedContext::update`].
+///
+/// It runs in [`PreUpdate`], once the input of the frame has been processed.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IcedUpdate;
+
+fn run_iced_update(world: &mut World) {
+    world.run_schedule(IcedUpdate);
+}
+
+/// How an interface reacted to the input of a frame, as returned by
+/// [`IcedContext::display`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DisplayStatus {
+    /// The number of messages produced by the interface.
+    pub messages: usize,
+    /// Whether any event was captured by a widget.
+    pub captured: bool,
+}
+
+impl DisplayStatus {
+    /// Whether the interface produced any message.
+    pub fn has_messages(&self) -> bool {
+        self.messages > 0
+    }
+}
+
+/// The cost of displaying an interface, sent for every [`IcedContext::display`] call.
+///
+/// Games implementing adaptive quality can use it to simplify their interfaces when they
+/// exceed a budget on low-end hardware.
+#[derive(Event, Clone, Debug)]
+pub struct UiFrameStats {
+    /// The message type of the [`IcedContext`] that displayed the interface.
+    pub context: TypeId,
+    /// The time spent building the widget tree and laying it out, in milliseconds.
+    pub build_ms: f32,
+    /// The time spent processing input, including the new layout it may require, in
+    /// milliseconds.
+    pub layout_ms: f32,
+    /// The time spent drawing the interface, in milliseconds.
+    pub draw_ms: f32,
+    /// The number of primitives drawn.
+    pub primitives: usize,
+}
+
+impl UiFrameStats {
+    /// Whether these statistics are for the [`IcedContext`] with message type `M`.
+    pub fn is_for<M: 'static>(&self) -> bool {
+        self.context == TypeId::of::<M>()
+    }
+}
+
+/// Sent when the focused text input of an interface changes.
+///
+/// Touch platforms can use it to show their own on-screen keyboard, or to scroll the
+/// input into view; see also [`IcedSettings::virtual_keyboard`].
+#[derive(Event, Clone, Debug)]
+pub struct IcedTextInputFocused {
+    /// The message type of the [`IcedContext`] that displayed the interface.
+    pub context: TypeId,
+    /// The area of the focused input, in logical pixels of the window, or `None` when the
+    /// interface lost focus.
+    ///
+    /// Iced doesn't report the layout of focusable widgets, so this is the area of the
+    /// innermost container, row or column holding the input. Wrap the input in a
+    /// `container` to make it exact.
+    pub bounds: Option<Rectangle>,
+}
+
+/// Marks the camera that Iced interfaces are confined to.
+///
+/// When a camera with this component renders to a sub-rectangle of the window (for example
+/// to letterbox a fixed aspect ratio), interfaces are laid out and drawn inside that
+/// rectangle, and cursor positions are mapped into it.
+///
+/// Interfaces displayed with `IcedContext::display_below_ui`, and all interfaces when
+/// presenting to [`PresentTarget::Camera`], are drawn in the render graph of this camera.
+#[derive(Component, Default)]
+pub struct IcedCamera;
+
+/// Marks a window that only hosts Iced interfaces, like the detached panels of an editor.
+///
+/// Spawn one with an [`IcedWindowBundle`] at any time, then display interfaces in it with
+/// [`IcedContext::display_in`]. The window needs no camera: its interfaces are laid out to
+/// fill it and presented over the background of the theme. Its input only reaches the
+/// interfaces displayed in it, and closing it despawns the entity as usual.
+#[derive(Component, Default)]
+pub struct IcedWindow;
+
+/// The components of a window hosting Iced interfaces, see [`IcedWindow`].
+///
+/// ```ignore
+/// let panel = commands
+///     .spawn(IcedWindowBundle {
+///         window: Window {
+///             title: "Inspector".into(),
+///             ..default()
+///         },
+///         ..default()
+///     })
+///     .id();
+/// ```
+#[derive(Bundle, Default)]
+pub struct IcedWindowBundle {
+    /// The window itself.
+    pub window: Window,
+    /// The marker routing its input and rendering to Iced.
+    pub iced: IcedWindow,
+}
 
 /// The context for interacting with Iced. Add this as a parameter to your system.
 /// ```ignore
@@ -216,68 +1123,944 @@ pub(crate) struct DidDraw(std::sync::atomic::AtomicBool);
 /// ```
 ///
 /// `IcedContext<T>` requires an event system to be defined in the [`App`].
-/// Do so by invoking `app.add_event::<T>()` when constructing your App.
+/// Do so by invoking `app.add_event::<T>()` when constructing your App, or set
+/// [`IcedSettings::auto_register_messages`] to have it registered on first use.
+///
+/// Systems taking an `IcedContext` run on the main thread, where the widget state of
+/// interfaces is kept. Apps displaying every interface from a single exclusive system can
+/// use [`with_iced_context`] instead.
 #[derive(SystemParam)]
 pub struct IcedContext<'w, 's, Message: bevy_ecs::event::Event> {
     viewport: Res<'w, ViewportResource>,
+    bounds: Res<'w, ViewportBounds>,
     props: Res<'w, IcedResource>,
     settings: Res<'w, IcedSettings>,
     windows: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
-    events: ResMut<'w, IcedEventQueue>,
-    cache_map: NonSendMut<'w, IcedCache>,
-    messages: EventWriter<'w, Message>,
-    did_draw: ResMut<'w, DidDraw>,
+    events: systems::DirectInput<'w, 's>,
+    ignored_events: ResMut<'w, IcedIgnoredEvents>,
+    cache_map: IcedCacheMut<'w>,
+    messages: MessageSender<'w, 's, Message>,
+    interaction: ResMut<'w, IcedInteraction>,
     touches: Res<'w, Touches>,
+    errors: ErrorReporter<'w, 's>,
+    frame_stats: EventWriter<'w, UiFrameStats>,
+    input_focus: EventWriter<'w, IcedTextInputFocused>,
+    tool_windows: Query<'w, 's, &'static Window, (With<IcedWindow>, Without<PrimaryWindow>)>,
+}
+
+// The state of the `IcedContext` of an exclusive system, kept across frames like the one of
+// a regular system.
+#[derive(Resource)]
+struct ExclusiveContext<M: bevy_ecs::event::Event>(SystemState<IcedContext<'static, 'static, M>>);
+
+/// Run `ui` with an [`IcedContext`] built from the world, in an exclusive system.
+///
+/// An exclusive system displaying every interface of the app holds the widget state on its
+/// own, so that no regular system needs an `IcedContext`, and none of them is pinned to the
+/// main thread by it:
+///
+/// ```ignore
+/// fn ui_system(world: &mut World) {
+///     let elapsed = world.resource::<Time>().elapsed_seconds();
+///     with_iced_context::<UiMessage, _>(world, |ctx| {
+///         ctx.display(text(format!("Running for {elapsed:.2} seconds.")));
+///     });
+/// }
+/// ```
+///
+/// The commands queued by the context, like the registration of its message type, are
+/// applied before this returns.
+pub fn with_iced_context<M: bevy_ecs::event::Event, R>(
+    world: &mut World,
+    ui: impl FnOnce(&mut IcedContext<M>) -> R,
+) -> R {
+    let mut state = match world.remove_resource::<ExclusiveContext<M>>() {
+        Some(state) => state,
+        None => ExclusiveContext(SystemState::new(world)),
+    };
+    let result = ui(&mut state.0.get_mut(world));
+    state.0.apply(world);
+    world.insert_resource(state);
+    result
 }
 
 impl<'w, 's, M: bevy_ecs::event::Event> IcedContext<'w, 's, M> {
     /// Display an [`Element`] to the screen.
+    ///
+    /// This can be called several times per frame, from one or several systems: interfaces
+    /// are drawn in call order, each over the previous ones. Use
+    /// [`display_with_layer`](Self::display_with_layer) for a draw order that doesn't depend
+    /// on the order of the systems.
+    ///
+    /// **Each interface displayed in the same frame with the same message type needs its own
+    /// key**, given with [`display_keyed`](Self::display_keyed). Without one, their widget
+    /// state (scroll offsets, text cursors...) is matched by call order, and goes to the
+    /// wrong interface as soon as one of them isn't displayed or the systems run in another
+    /// order: this reports [`IcedError::DuplicateDisplay`]. The events captured by an interface
+    /// aren't processed by the following ones.
+    ///
+    /// The returned [`DisplayStatus`] describes how the interface reacted to this frame's
+    /// input, so the calling system can act on it without waiting for the messages.
     pub fn display<'a>(
         &'a mut self,
         element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
-    ) {
+    ) -> DisplayStatus {
+        self.display_with(element.into(), DisplayOptions::default())
+    }
+
+    /// Display an [`Element`] to the screen, using the given theme and style instead of
+    /// the ones in [`IcedSettings`].
+    ///
+    /// This lets several interfaces drawn in the same frame have distinct looks.
+    pub fn display_with_theme<'a>(
+        &'a mut self,
+        theme: &Theme,
+        style: &iced::Style,
+        element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
+    ) -> DisplayStatus {
+        self.display_with(
+            element.into(),
+            DisplayOptions {
+                theme: Some(theme),
+                style: Some(style),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Display an [`Element`] with its own widget state, identified by `key`.
+    ///
+    /// The widget state (scroll offsets, text cursors...) of interfaces displayed with
+    /// [`display`](Self::display) is kept per message type. Systems displaying distinct
+    /// interfaces with the same message type must give each one a different key.
+    pub fn display_keyed<'a>(
+        &'a mut self,
+        key: &str,
+        element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
+    ) -> DisplayStatus {
+        self.display_with(
+            element.into(),
+            DisplayOptions {
+                key: Some(key),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Display an [`Element`] on the layer `layer`, instead of the default layer `0`.
+    ///
+    /// Interfaces on higher layers are drawn over the ones on lower layers, whatever the
+    /// order in which the systems displaying them ran, e.g. `10` for a modal over a HUD on
+    /// layer `0`. Interfaces on the same layer are drawn in call order.
+    ///
+    /// Input is still processed in call order, but interfaces don't see the cursor where
+    /// interfaces on higher layers were drawn in the last frame, so a modal gets the clicks
+    /// meant for it whatever the order of the systems. Keyboard input goes to the focused
+    /// widgets, in call order.
+    pub fn display_with_layer<'a>(
+        &'a mut self,
+        layer: i32,
+        element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
+    ) -> DisplayStatus {
+        self.display_with(
+            element.into(),
+            DisplayOptions {
+                layer,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Display an [`Element`] written in `direction`, mirroring its layout for
+    /// [`TextDirection::RightToLeft`] (see [`Mirrored`](mirror::Mirrored)).
+    ///
+    /// This lets an interface be written in another direction than the one of
+    /// [`IcedSettings::text_direction`], e.g. a chat window showing Arabic in an English
+    /// game. The interface is written from left to right, without the row reordering
+    /// helpers of [`TextDirection`]: rows are laid out from the right once mirrored.
+    pub fn display_with_direction<'a>(
+        &'a mut self,
+        direction: TextDirection,
+        element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
+    ) -> DisplayStatus {
+        self.display_with(
+            element.into(),
+            DisplayOptions {
+                direction: Some(direction),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Display an [`Element`] faded by `opacity`, from `0.0` (invisible) to `1.0` (opaque).
+    ///
+    /// Like [`IcedSettings::opacity`], which it is combined with, the interface is faded as
+    /// a whole when presenting, and keeps processing input. Below `1.0`, it is rendered to
+    /// an offscreen texture first. The software renderer only applies
+    /// [`IcedSettings::opacity`].
+    pub fn display_with_opacity<'a>(
+        &'a mut self,
+        opacity: f32,
+        element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
+    ) -> DisplayStatus {
+        self.display_with(
+            element.into(),
+            DisplayOptions {
+                opacity: Some(opacity.clamp(0.0, 1.0)),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Display an [`Element`] below the `bevy_ui` nodes, instead of above them.
+    ///
+    /// This lets `bevy_ui` elements appear on top of an Iced HUD, while other interfaces
+    /// (e.g. a modal) are still displayed above them. The interface is drawn in the render
+    /// graph of the camera marked with [`IcedCamera`], right before the `bevy_ui` pass.
+    #[cfg(feature = "bevy_ui")]
+    pub fn display_below_ui<'a>(
+        &'a mut self,
+        element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
+    ) -> DisplayStatus {
+        self.display_with(
+            element.into(),
+            DisplayOptions {
+                below_ui: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Display an [`Element`] in the window `window`, spawned with an [`IcedWindowBundle`].
+    ///
+    /// The interface fills the window, and only processes the input sent to it. Several
+    /// interfaces can be displayed in a window, each over the previous ones, but each
+    /// message type has a single widget state per window.
+    pub fn display_in<'a>(
+        &'a mut self,
+        window: Entity,
+        element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
+    ) -> DisplayStatus {
+        let Ok(tool_window) = self.tool_windows.get(window) else {
+            self.errors.report(IcedError::NoWindow);
+            return DisplayStatus::default();
+        };
+        let scale_factor = self
+            .settings
+            .scale_factor
+            .unwrap_or_else(|| tool_window.scale_factor().into());
+        let size = Size::new(tool_window.physical_width(), tool_window.physical_height());
+        // The window is minimized: there is nothing to lay out.
+        if size.width == 0 || size.height == 0 || !scale_factor.is_finite() || scale_factor <= 0.0 {
+            return DisplayStatus::default();
+        }
+        let viewport = Viewport::with_physical_size(size, scale_factor);
+        let cursor = tool_window
+            .cursor_position()
+            .filter(|_| self.settings.input.mouse && tool_window.focused)
+            .map(|position| {
+                Cursor::Available(utils::process_cursor_position(
+                    position,
+                    viewport.logical_size(),
+                    tool_window,
+                ))
+            })
+            .unwrap_or(Cursor::Unavailable);
+        let target = LayerTarget {
+            entity: window,
+            viewport,
+            background: self.settings.theme.0.palette().background,
+        };
+        self.display_in_target(element.into(), target, cursor, true)
+    }
+
+    // Display an interface in a tool window or a `bevy_ui` node, which processes the input
+    // of the tool window, or the main input otherwise.
+    pub(crate) fn display_in_target(
+        &mut self,
+        element: iced_core::Element<'_, M, Theme, Renderer>,
+        target: LayerTarget,
+        cursor: Cursor,
+        window_input: bool,
+    ) -> DisplayStatus {
+        self.read_direct_input();
+        let mut props = self.props.lock_or_recover(&mut self.errors);
         let IcedProps {
             ref mut renderer,
-            ref mut clipboard,
+            ref mut debug,
+            ref clipboard,
+            ref mut layers,
+            #[cfg(feature = "persistence")]
+            ref mut persisted,
             ..
-        } = &mut *self.props.lock().unwrap();
-        let bounds = self.viewport.logical_size();
-
-        let element = element.into();
-
-        let cursor = {
-            let window = self.windows.single();
-            match window.cursor_position() {
-                Some(position) => {
-                    Cursor::Available(utils::process_cursor_position(position, bounds, window))
-                }
-                None => utils::process_touch_input(self)
-                    .map(Cursor::Available)
-                    .unwrap_or(Cursor::Unavailable),
-            }
+        } = &mut *props;
+        let entity = target.entity;
+        let bounds = target.viewport.logical_size();
+
+        let mut messages = std::mem::take(self.cache_map.messages::<M>());
+        let cache = self
+            .cache_map
+            .targets
+            .remove(&(entity, TypeId::of::<M>()))
+            .and_then(|cache| cache.0)
+            .unwrap_or_default();
+        let interface = std::any::type_name::<M>();
+        let mut ui = utils::trace_stage(debug, Stage::Layout, interface, || {
+            UserInterface::build(element, bounds, cache, renderer)
+        });
+        #[cfg(feature = "persistence")]
+        persisted.update(|operation| ui.operate(renderer, operation));
+        let events: &[iced::Event] = if window_input {
+            self.events.window(entity)
+        } else {
+            &self.events
+        };
+        let (state, event_statuses) = utils::trace_stage(debug, Stage::Event, interface, || {
+            ui.update(
+                events,
+                cursor,
+                renderer,
+                &mut *clipboard.lock().unwrap_or_else(PoisonError::into_inner),
+                &mut messages,
+            )
+        });
+        self.interaction
+            .schedule_update(&state, !messages.is_empty());
+
+        let status = DisplayStatus {
+            messages: messages.len(),
+            captured: event_statuses.contains(&iced_core::event::Status::Captured),
         };
 
-        let mut messages = Vec::<M>::new();
-        let cache_entry = self.cache_map.get::<M>();
-        let cache = cache_entry.take().unwrap();
-        let mut ui = UserInterface::build(element, bounds, cache, renderer);
-        let (_, _event_statuses) = ui.update(
-            self.events.as_slice(),
-            cursor,
-            renderer,
-            clipboard,
-            &mut messages,
+        renderer.clear();
+        utils::trace_stage(debug, Stage::Draw, interface, || {
+            ui.draw(
+                renderer,
+                &self.settings.theme.0,
+                &self.settings.style.0,
+                cursor,
+            );
+        });
+        layers.push(DrawnLayer {
+            source: TypeId::of::<M>(),
+            below_ui: false,
+            layer: 0,
+            opacity: 1.0,
+            hit_test: false,
+            target: Some(target),
+            backdrops: Vec::new(),
+            primitives: compat::primitives(renderer),
+        });
+
+        if window_input {
+            self.events.consume_window(entity, &event_statuses);
+        } else {
+            self.events
+                .consume(&event_statuses, &mut self.ignored_events);
+        }
+        self.cache_map.targets.insert(
+            (entity, TypeId::of::<M>()),
+            WidgetCache(Some(ui.into_cache())),
         );
+        let sent = self
+            .messages
+            .send(&mut messages, self.settings.auto_register_messages);
+        *self.cache_map.messages::<M>() = messages;
+        match sent {
+            Ok(()) => self
+                .errors
+                .resolve(&IcedError::UnregisteredMessage(std::any::type_name::<M>())),
+            Err(error) => self.errors.report(error),
+        }
+        status
+    }
+
+    fn display_with(
+        &mut self,
+        element: iced_core::Element<'_, M, Theme, Renderer>,
+        options: DisplayOptions,
+    ) -> DisplayStatus {
+        self.read_direct_input();
+        let Ok(window) = self.windows.get_single() else {
+            self.errors.report(IcedError::NoWindow);
+            return DisplayStatus::default();
+        };
+        // The window is minimized: there is nothing to lay out.
+        if self.bounds.hidden {
+            return DisplayStatus::default();
+        }
+        let mut props = self.props.lock_or_recover(&mut self.errors);
+        let IcedProps {
+            ref mut renderer,
+            ref mut debug,
+            ref clipboard,
+            ref mut layers,
+            ref hit_regions,
+            #[cfg(feature = "persistence")]
+            ref mut persisted,
+            ..
+        } = &mut *props;
+        let bounds = self.bounds.size();
+        let theme = options.theme.unwrap_or(&self.settings.theme.0);
+        let style = options.style.unwrap_or(&self.settings.style.0);
+
+        let offset = Vector::new(self.bounds.x, self.bounds.y);
+        // Interfaces are updated in call order, but the ones drawn over this one get the
+        // cursor first, wherever they were visible.
+        let order = (!options.below_ui, options.layer);
+        let cursor = match self.cursor(window) {
+            Cursor::Available(position)
+                if hit_regions.iter().any(|region| {
+                    region.order > order
+                        && region.source != TypeId::of::<M>()
+                        && region.bounds.contains(position + offset)
+                }) =>
+            {
+                Cursor::Unavailable
+            }
+            cursor => cursor,
+        };
+
+        // An interface updated ahead of `Update` is displayed again later in the frame, and
+        // doesn't take a slot of its own.
+        let slot = if options.update_only {
+            0
+        } else {
+            self.cache_map.next_slot::<M>(options.key)
+        };
+        if slot > 0 {
+            self.errors
+                .report(IcedError::DuplicateDisplay(std::any::type_name::<M>()));
+        }
+        let id = cache_key::<M>(options.key, slot);
+        let budget = self.cache_map.budget(&id);
+        if self.settings.skip_over_budget
+            && budget.over_budget
+            && !budget.skipped
+            && !options.update_only
+        {
+            budget.skipped = true;
+            // The input stays in the queue for the interfaces displayed after this one, and
+            // a copy is left to the next build, without the redraw tick sent every frame.
+            budget.deferred_events.extend(
+                self.events
+                    .iter()
+                    .filter(|event| {
+                        !matches!(
+                            event,
+                            iced::Event::Window(_, iced_core::window::Event::RedrawRequested(_))
+                        )
+                    })
+                    .cloned(),
+            );
+            layers.push(DrawnLayer {
+                source: TypeId::of::<M>(),
+                below_ui: options.below_ui,
+                layer: options.layer,
+                opacity: options.opacity.unwrap_or(1.0),
+                hit_test: true,
+                target: None,
+                backdrops: budget.backdrops.clone(),
+                primitives: budget.primitives.clone(),
+            });
+            return DisplayStatus::default();
+        }
+        // The input deferred by a skipped build is left to the next display.
+        let deferred = if options.update_only {
+            Vec::new()
+        } else {
+            budget.skipped = false;
+            std::mem::take(&mut budget.deferred_events)
+        };
+        let deferred_len = deferred.len();
+        let events: Cow<[iced::Event]> = if deferred.is_empty() {
+            Cow::Borrowed(self.events.as_slice())
+        } else {
+            Cow::Owned(
+                deferred
+                    .into_iter()
+                    .chain(self.events.iter().cloned())
+                    .collect(),
+            )
+        };
+        // The positions of the input are relative to the interface, like the cursor.
+        let events = if offset == Vector::ZERO {
+            events
+        } else {
+            Cow::Owned(
+                events
+                    .iter()
+                    .map(|event| utils::offset_event(event, offset))
+                    .collect(),
+            )
+        };
+
+        let element = match options.direction {
+            Some(direction) if direction.is_rtl() => mirror::mirrored(element).into(),
+            _ => element,
+        };
+        let element = self.cache_map.with_context_menu(&id, element);
+        let mut messages = std::mem::take(self.cache_map.messages::<M>());
+        let cache_entry = self.cache_map.get(&id);
+        let cache = cache_entry.take().unwrap_or_default();
+        let interface = std::any::type_name::<M>();
+        let start = Instant::now();
+        let mut ui = utils::trace_stage(debug, Stage::Layout, interface, || {
+            UserInterface::build(element, bounds, cache, renderer)
+        });
+        #[cfg(feature = "persistence")]
+        persisted.update(|operation| ui.operate(renderer, operation));
+        let built = Instant::now();
+        let (state, event_statuses) = utils::trace_stage(debug, Stage::Event, interface, || {
+            ui.update(
+                &events,
+                cursor,
+                renderer,
+                &mut *clipboard.lock().unwrap_or_else(PoisonError::into_inner),
+                &mut messages,
+            )
+        });
+        drop(events);
+        let updated = Instant::now();
+        self.interaction
+            .schedule_update(&state, !messages.is_empty());
+        let status = DisplayStatus {
+            messages: messages.len(),
+            captured: event_statuses.contains(&iced_core::event::Status::Captured),
+        };
+        if options.update_only {
+            self.events
+                .consume(&event_statuses, &mut self.ignored_events);
+            *cache_entry = Some(ui.into_cache());
+            self.cache_map.remove_closed_menu(&id);
+            drop(props);
+            self.send_messages(messages);
+            return status;
+        }
+
+        let mut focused_input = utils::FindFocusedInput::default();
+        ui.operate(renderer, &mut focused_input);
+        let mut backdrops = FindBackdrops::default();
+        ui.operate(renderer, &mut backdrops);
+        let backdrops: Vec<_> = backdrops
+            .regions
+            .into_iter()
+            .map(|region| BackdropRegion {
+                bounds: region.bounds + offset,
+                ..region
+            })
+            .collect();
 
-        messages.into_iter().for_each(|msg| {
-            self.messages.send(msg);
+        // Drawing clears the renderer, which must happen before the translation starts.
+        renderer.clear();
+        let mut interaction = iced_core::mouse::Interaction::Idle;
+        utils::trace_stage(debug, Stage::Draw, interface, || {
+            renderer.with_layer(**self.bounds, |renderer| {
+                renderer.with_translation(offset, |renderer| {
+                    interaction = ui.draw(renderer, theme, style, cursor);
+                });
+            });
+        });
+        if interaction != iced_core::mouse::Interaction::Idle {
+            self.interaction.current = interaction;
+        }
+        let primitives = compat::primitives(renderer);
+        let mut counts = IcedRenderStats::default();
+        compat::count_primitives(&primitives, &mut counts);
+        let over_budget = self
+            .settings
+            .frame_budget
+            .filter(|budget| updated - start > *budget);
+        let reused = if over_budget.is_some() && self.settings.skip_over_budget {
+            (primitives.clone(), backdrops.clone())
+        } else {
+            Default::default()
+        };
+        self.frame_stats.send(UiFrameStats {
+            context: TypeId::of::<M>(),
+            build_ms: (built - start).as_secs_f32() * 1000.0,
+            layout_ms: (updated - built).as_secs_f32() * 1000.0,
+            draw_ms: updated.elapsed().as_secs_f32() * 1000.0,
+            primitives: counts.quads + counts.text_sections + counts.other_primitives,
+        });
+        layers.push(DrawnLayer {
+            source: TypeId::of::<M>(),
+            below_ui: options.below_ui,
+            layer: options.layer,
+            opacity: options.opacity.unwrap_or(1.0),
+            hit_test: true,
+            target: None,
+            backdrops,
+            primitives,
         });
 
-        ui.draw(renderer, &self.settings.theme, &self.settings.style, cursor);
+        // The deferred input was already passed on to the interfaces of its frame.
+        self.events
+            .consume(&event_statuses[deferred_len..], &mut self.ignored_events);
+        *cache_entry = Some(ui.into_cache());
+        self.cache_map.remove_closed_menu(&id);
+        let budget = self.cache_map.budget(&id);
+        if let (Some(limit), false) = (over_budget, budget.over_budget) {
+            warn!(
+                "Iced: building and updating an interface of {} took {:.1} ms, over the \
+                 budget of {:.1} ms",
+                std::any::type_name::<M>(),
+                (updated - start).as_secs_f32() * 1000.0,
+                limit.as_secs_f32() * 1000.0,
+            );
+        }
+        budget.over_budget = over_budget.is_some();
+        (budget.primitives, budget.backdrops) = reused;
+        let focused_bounds = focused_input.bounds.map(|bounds| bounds + offset);
+        if self.cache_map.set_focused_input(&id, focused_bounds) {
+            self.input_focus.send(IcedTextInputFocused {
+                context: TypeId::of::<M>(),
+                bounds: focused_bounds,
+            });
+        }
+        drop(props);
+        self.send_messages(messages);
+        status
+    }
+
+    // Send the messages of an interface, keeping their buffer for the next frame.
+    fn send_messages(&mut self, mut messages: Vec<M>) {
+        let sent = self
+            .messages
+            .send(&mut messages, self.settings.auto_register_messages);
+        *self.cache_map.messages::<M>() = messages;
+        match sent {
+            Ok(()) => self
+                .errors
+                .resolve(&IcedError::UnregisteredMessage(std::any::type_name::<M>())),
+            Err(error) => self.errors.report(error),
+        }
+    }
 
-        self.events.clear();
+    // The cursor position, relative to the interface bounds.
+    fn cursor(&self, window: &Window) -> Cursor {
+        let viewport_size = self.viewport.logical_size();
+        let locked = window.cursor.grab_mode == CursorGrabMode::Locked || !window.cursor.visible;
+        let position = match (
+            self.settings.input.mouse,
+            locked,
+            self.settings.locked_cursor,
+        ) {
+            (false, _, _) | (true, true, LockedCursor::Unavailable) => None,
+            (true, true, LockedCursor::Centered) => {
+                Some(Vec2::new(window.width(), window.height()) / 2.0)
+            }
+            // An unfocused window keeps reporting the last cursor position it received.
+            _ => window.cursor_position().filter(|_| window.focused),
+        };
+        let interaction = &self.interaction;
+        let cursor = match (
+            interaction.virtual_cursor,
+            interaction.cursor_source,
+            position,
+        ) {
+            (Some(position), _, _) => Cursor::Available(position),
+            (None, Some(cursor), _) => cursor,
+            (None, None, Some(position)) => Cursor::Available(utils::process_cursor_position(
+                position,
+                viewport_size,
+                window,
+            )),
+            (None, None, None) if locked || !self.settings.input.touch => Cursor::Unavailable,
+            (None, None, None) => utils::process_touch_input(self)
+                .or(interaction.fling_cursor)
+                .map(Cursor::Available)
+                .unwrap_or(Cursor::Unavailable),
+        };
+        match cursor {
+            Cursor::Available(position) => {
+                Cursor::Available(position - Vector::new(self.bounds.x, self.bounds.y))
+            }
+            Cursor::Unavailable => Cursor::Unavailable,
+        }
+    }
+
+    /// Update an [`Element`] with this frame's input and send the resulting messages,
+    /// without drawing it.
+    ///
+    /// Call this from a system in the [`IcedUpdate`] schedule, so that message handlers
+    /// running in [`Update`](bevy_app::Update) see the messages of an interaction in the
+    /// same frame. The interface is then drawn later in the frame with
+    /// [`display`](Self::display) as usual: since the input has already been consumed, it
+    /// won't produce the messages twice.
+    pub fn update<'a>(
+        &'a mut self,
+        element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
+    ) -> DisplayStatus {
+        self.display_with(
+            element.into(),
+            DisplayOptions {
+                update_only: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Build and lay out an [`Element`] without drawing it to the screen.
+    ///
+    /// The widget state produced by the layout (including shaped text) is kept in the
+    /// cache for this message type, so calling this during a loading screen makes the
+    /// first [`display`](Self::display) of a large interface considerably cheaper.
+    pub fn warm_up<'a>(&mut self, element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>) {
+        if self.bounds.hidden {
+            return;
+        }
+        let mut props = self.props.lock_or_recover(&mut self.errors);
+        let IcedProps {
+            ref mut renderer,
+            ref mut debug,
+            ..
+        } = &mut *props;
+        let bounds = self.bounds.size();
+
+        let id = cache_key::<M>(None, 0);
+        let element = self.cache_map.with_context_menu(&id, element.into());
+        let cache_entry = self.cache_map.get(&id);
+        let cache = cache_entry.take().unwrap_or_default();
+        let ui = utils::trace_stage(debug, Stage::Layout, std::any::type_name::<M>(), || {
+            UserInterface::build(element, bounds, cache, renderer)
+        });
         *cache_entry = Some(ui.into_cache());
-        self.did_draw
-            .store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Measure the size of `content` when rendered without wrapping at the given size.
+    ///
+    /// `font` defaults to the default font of the renderer (see [`IcedPlugin::settings`]).
+    /// See the [`text`] module for more measurement helpers.
+    pub fn measure_text(
+        &self,
+        content: &str,
+        font: impl Into<Option<iced::Font>>,
+        size: impl Into<iced::Pixels>,
+    ) -> iced::Size {
+        let font = font.into().unwrap_or_else(|| {
+            self.props
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .renderer
+                .default_font()
+        });
+        text::measure(content, font, size)
+    }
+
+    /// Draw with the [`Renderer`] directly, bypassing widgets.
+    ///
+    /// This is meant for procedurally generated HUD elements, for which building widgets
+    /// is unnecessary overhead: `draw` can use the renderer traits of Iced (e.g.
+    /// [`fill_quad`](iced_core::Renderer::fill_quad)) with coordinates in logical pixels of
+    /// the window. The result is layered with the interfaces displayed this frame on layer
+    /// `0`, in call order.
+    pub fn draw(&mut self, draw: impl FnOnce(&mut Renderer)) {
+        let mut props = self.props.lock_or_recover(&mut self.errors);
+        let IcedProps {
+            ref mut renderer,
+            ref mut layers,
+            ..
+        } = &mut *props;
+        renderer.clear();
+        draw(renderer);
+        layers.push(DrawnLayer {
+            source: TypeId::of::<M>(),
+            below_ui: false,
+            layer: 0,
+            opacity: 1.0,
+            hit_test: true,
+            target: None,
+            backdrops: Vec::new(),
+            primitives: compat::primitives(renderer),
+        });
+    }
+
+    /// Draw already built [`Primitive`]s, in logical pixels of the window.
+    ///
+    /// Like [`draw`](Self::draw), the primitives are layered with the interfaces displayed
+    /// this frame, in call order.
+    pub fn draw_primitives(&mut self, primitives: impl IntoIterator<Item = Primitive>) {
+        let mut props = self.props.lock_or_recover(&mut self.errors);
+        props.layers.push(DrawnLayer {
+            source: TypeId::of::<M>(),
+            below_ui: false,
+            layer: 0,
+            opacity: 1.0,
+            hit_test: true,
+            target: None,
+            backdrops: Vec::new(),
+            primitives: primitives.into_iter().collect(),
+        });
+    }
+
+    /// Create a subtree that is only rebuilt when `key` changes, to save the cost of
+    /// rebuilding large, mostly static parts of an interface every frame.
+    ///
+    /// See [`widgets::memo`] for details.
+    pub fn memo<'a, K, E>(&self, key: K, view: impl Fn() -> E + 'a) -> ui_types::Element<'a, M>
+    where
+        K: std::hash::Hash + 'a,
+        E: Into<ui_types::Element<'static, M>> + 'static,
+    {
+        widgets::memo(key, view)
+    }
+
+    /// Whether the cursor is over a visible part of the interfaces presented in the last
+    /// frame: a filled or bordered quad, some text, an image...
+    ///
+    /// Unlike [`DisplayStatus::captured`], this is hit-tested against the layout of the
+    /// interfaces, so it holds while the cursor rests over them without sending any event.
+    /// This is meant for keeping camera controls from reacting to the cursor under
+    /// interfaces.
+    pub fn is_hovered(&self) -> bool {
+        let Ok(window) = self.windows.get_single() else {
+            return false;
+        };
+        if self.bounds.hidden {
+            return false;
+        }
+        let Cursor::Available(position) = self.cursor(window) else {
+            return false;
+        };
+        let position = position + Vector::new(self.bounds.x, self.bounds.y);
+        self.props
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .hit_regions
+            .iter()
+            .any(|region| region.bounds.contains(position))
+    }
+
+    /// The direction interfaces are written in, from [`IcedSettings::text_direction`].
+    pub fn text_direction(&self) -> TextDirection {
+        self.settings.text_direction
+    }
+
+    /// Log a message, e.g. one produced by an interface, to the `iced::message` target of
+    /// Bevy's logging and to the Iced debug overlay.
+    pub fn log_message(&mut self, message: &impl std::fmt::Debug) {
+        debug!(target: "iced::message", "{message:?}");
+        self.props
+            .lock_or_recover(&mut self.errors)
+            .debug
+            .log_message(message);
+    }
+
+    /// Open a context menu at the cursor, listing `items` as labels and the messages they
+    /// send when selected.
+    ///
+    /// The menu is shown over the interface of this message type displayed without a key,
+    /// from the next [`display`](Self::display) on, and flips to stay inside the interface
+    /// near its edges. It closes once an item is selected, or when clicking outside of it or
+    /// pressing `Escape`, which the interface doesn't receive. Opening another menu
+    /// replaces it. Nothing happens when the cursor is outside of the window.
+    ///
+    /// ```ignore
+    /// if mouse.just_pressed(MouseButton::Right) {
+    ///     ctx.context_menu([("Rename", UiMessage::Rename), ("Delete", UiMessage::Delete)]);
+    /// }
+    /// ```
+    pub fn context_menu(&mut self, items: impl IntoIterator<Item = (impl Into<String>, M)>)
+    where
+        M: Clone,
+    {
+        let Ok(window) = self.windows.get_single() else {
+            self.errors.report(IcedError::NoWindow);
+            return;
+        };
+        let Some(position) = self.cursor(window).position() else {
+            return;
+        };
+        let items = items
+            .into_iter()
+            .map(|(label, message)| (label.into(), message))
+            .collect();
+        self.cache_map.context_menus.insert(
+            TypeId::of::<M>(),
+            context_menu::OpenMenu::new(position, items),
+        );
+    }
+
+    // With `IcedSettings::direct_input`, read the input sent since it was last read, so the
+    // next interface processes it this frame.
+    fn read_direct_input(&mut self) {
+        if self.settings.direct_input {
+            self.events.read_late_input(&self.settings);
+        }
+    }
+
+    /// Serialize the scroll offsets and text input focus of the
+    /// [`persistent`](persistence::persistent) widgets displayed so far, to restore them
+    /// with [`load_state`](Self::load_state) after a restart.
+    ///
+    /// Text values aren't part of it: they are application state, saved by the application
+    /// (see [`Persistent`](persistence::Persistent)).
+    #[cfg(feature = "persistence")]
+    pub fn save_state(&mut self) -> Vec<u8> {
+        let props = self.props.lock_or_recover(&mut self.errors);
+        props.persisted.save()
+    }
+
+    /// Restore the scroll offsets and text input focus saved with
+    /// [`save_state`](Self::save_state).
+    ///
+    /// The state of each [`persistent`](persistence::persistent) widget is restored the
+    /// next time it is displayed, which can be after a tab or a panel is opened.
+    #[cfg(feature = "persistence")]
+    pub fn load_state(&mut self, bytes: &[u8]) -> Result<(), serde_json::Error> {
+        let mut props = self.props.lock_or_recover(&mut self.errors);
+        props.persisted.load(bytes)
+    }
+
+    /// Queue a synthetic event, processed by the next interface displayed this frame
+    /// like any real input.
+    pub fn inject_event(&mut self, event: iced::Event) {
+        self.events.push(event);
+    }
+
+    // Keep only the events of this frame for which `keep` is true, e.g. to block the input
+    // of the interfaces displayed after a modal.
+    pub(crate) fn retain_events(&mut self, keep: impl FnMut(&iced::Event) -> bool) {
+        self.events.retain(keep);
+    }
+
+    /// Load a font at runtime, making it available to every interface.
+    ///
+    /// This is the runtime counterpart of [`IcedPlugin::fonts`], for fonts that are
+    /// only known after startup (downloaded content, user-provided files...).
+    pub fn load_font(&mut self, bytes: impl Into<Cow<'static, [u8]>>) {
+        let mut props = self.props.lock_or_recover(&mut self.errors);
+        iced_core::text::Renderer::load_font(&mut props.renderer, bytes.into());
+    }
+
+    /// Display the [`Element`] built by `view`, spreading the cost of its first build
+    /// over two frames when it is expensive.
+    ///
+    /// The first time this is called for a message type, the interface is only laid out
+    /// (see [`warm_up`](Self::warm_up)). If that took longer than
+    /// [`IcedSettings::split_threshold`], `placeholder` is displayed for this frame and the
+    /// real interface is shown from the next one, once text has been shaped. Otherwise, the
+    /// interface is displayed right away.
+    pub fn display_progressive<'a, E, P>(
+        &'a mut self,
+        view: impl Fn() -> E,
+        placeholder: impl FnOnce() -> P,
+    ) -> DisplayStatus
+    where
+        E: Into<iced_core::Element<'a, M, Theme, Renderer>>,
+        P: Into<iced_core::Element<'a, M, Theme, Renderer>>,
+    {
+        if let (Some(threshold), false) = (
+            self.settings.split_threshold,
+            self.cache_map.contains::<M>(),
+        ) {
+            let start = Instant::now();
+            self.warm_up(view());
+            if start.elapsed() > threshold {
+                return self.display(placeholder());
+            }
+        }
+        self.display(view())
     }
 }
@@ -0,0 +1,56 @@
+use iced_core::{Background, Color};
+use iced_widget::button::{self, Appearance};
+use iced_widget::style::theme;
+use iced_widget::{Button, Text};
+
+use crate::{Renderer, Theme};
+
+/// Creates a link-styled button showing `label`, which produces `on_press` when clicked.
+///
+/// The cursor turns into a pointer while hovering the link. To open a web page, handle
+/// the message with [`open_url`] (requires the `open` feature).
+pub fn link<'a, Message: Clone + 'a>(
+    label: impl ToString,
+    on_press: Message,
+) -> Button<'a, Message, Theme, Renderer> {
+    Button::new(Text::new(label.to_string()))
+        .padding(0)
+        .style(theme::Button::custom(LinkStyle))
+        .on_press(on_press)
+}
+
+/// Open `url` with the default browser of the operating system.
+#[cfg(feature = "open")]
+pub fn open_url(url: &str) -> std::io::Result<()> {
+    open::that(url)
+}
+
+struct LinkStyle;
+
+impl button::StyleSheet for LinkStyle {
+    type Style = Theme;
+
+    fn active(&self, style: &Self::Style) -> Appearance {
+        Appearance {
+            background: None,
+            text_color: style.palette().primary,
+            ..Default::default()
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style) -> Appearance {
+        let Color { r, g, b, a } = style.palette().primary;
+        Appearance {
+            background: Some(Background::Color(Color {
+                a: 0.1 * a,
+                ..style.palette().primary
+            })),
+            text_color: Color::from_rgba(r * 1.2, g * 1.2, b * 1.2, a),
+            ..Default::default()
+        }
+    }
+
+    fn pressed(&self, style: &Self::Style) -> Appearance {
+        self.active(style)
+    }
+}
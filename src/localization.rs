@@ -0,0 +1,184 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::change_detection::DetectChanges;
+use bevy_ecs::prelude::{resource_exists, IntoSystemConfigs, Local, Res, ResMut, Resource};
+use bevy_utils::HashMap;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::FluentResource;
+use iced_core::text::Renderer as _;
+
+pub use fluent_bundle::FluentArgs;
+pub use unic_langid::LanguageIdentifier;
+
+use crate::error::ErrorReporter;
+use crate::{systems, IcedNeedsUpdate, IcedResource};
+
+/// Translate a Fluent message with an [`IcedLocale`], optionally passing it arguments.
+///
+/// ```ignore
+/// let title = tr!(locale, "menu-title");
+/// let greeting = tr!(locale, "greeting", name = player.name.as_str());
+/// ```
+#[macro_export]
+macro_rules! tr {
+    ($locale:expr, $key:expr) => {
+        $locale.translate($key, None)
+    };
+    ($locale:expr, $key:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = $crate::localization::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $locale.translate($key, Some(&args))
+    }};
+}
+
+/// The translations of the interfaces, and the language they are displayed in.
+///
+/// Changing the language with [`set_language`](Self::set_language) updates interfaces
+/// right away (see [`IcedNeedsUpdate`]), and loads the fonts registered for the language
+/// with [`add_font`](Self::add_font) once it is used, including fonts added afterwards.
+///
+/// Requires the [`IcedLocalePlugin`].
+#[derive(Resource)]
+pub struct IcedLocale {
+    language: LanguageIdentifier,
+    fallback: LanguageIdentifier,
+    bundles: HashMap<LanguageIdentifier, FluentBundle<FluentResource>>,
+    fonts: HashMap<LanguageIdentifier, Vec<Cow<'static, [u8]>>>,
+}
+
+impl IcedLocale {
+    /// Create a locale displaying `language`, which is also used for messages missing in
+    /// other languages.
+    pub fn new(language: LanguageIdentifier) -> Self {
+        Self {
+            fallback: language.clone(),
+            language,
+            bundles: HashMap::default(),
+            fonts: HashMap::default(),
+        }
+    }
+
+    /// Add the messages of a Fluent (`.ftl`) file to the translations of `language`.
+    pub fn add_translations(
+        &mut self,
+        language: LanguageIdentifier,
+        source: impl Into<String>,
+    ) -> Result<(), LocaleError> {
+        let resource = FluentResource::try_new(source.into()).map_err(|(_, errors)| {
+            LocaleError::Syntax(errors.iter().map(ToString::to_string).collect())
+        })?;
+        let bundle = self.bundles.entry(language.clone()).or_insert_with(|| {
+            let mut bundle = FluentBundle::new_concurrent(vec![language]);
+            // Iced draws the Unicode isolation marks Fluent adds around arguments.
+            bundle.set_use_isolating(false);
+            bundle
+        });
+        bundle.add_resource(resource).map_err(|errors| {
+            LocaleError::Conflict(errors.iter().map(ToString::to_string).collect())
+        })
+    }
+
+    /// Register a font to load when `language` is displayed, for scripts the default font
+    /// doesn't cover.
+    pub fn add_font(&mut self, language: LanguageIdentifier, bytes: impl Into<Cow<'static, [u8]>>) {
+        self.fonts.entry(language).or_default().push(bytes.into());
+    }
+
+    /// The language interfaces are displayed in.
+    pub fn language(&self) -> &LanguageIdentifier {
+        &self.language
+    }
+
+    /// Display interfaces in `language`.
+    pub fn set_language(&mut self, language: LanguageIdentifier) {
+        self.language = language;
+    }
+
+    /// Translate the message `key` in the current language, falling back to the language the
+    /// locale was created with, then to `key` itself.
+    pub fn translate(&self, key: &str, args: Option<&FluentArgs>) -> String {
+        [&self.language, &self.fallback]
+            .into_iter()
+            .filter_map(|language| self.bundles.get(language))
+            .find_map(|bundle| {
+                let pattern = bundle.get_message(key)?.value()?;
+                let mut errors = Vec::new();
+                Some(
+                    bundle
+                        .format_pattern(pattern, args, &mut errors)
+                        .into_owned(),
+                )
+            })
+            .unwrap_or_else(|| key.to_owned())
+    }
+}
+
+/// An error adding translations to an [`IcedLocale`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LocaleError {
+    /// The Fluent file has syntax errors.
+    Syntax(Vec<String>),
+    /// The Fluent file defines messages that already exist for the language.
+    Conflict(Vec<String>),
+}
+
+impl fmt::Display for LocaleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax(errors) => write!(f, "invalid Fluent file: {}", errors.join(", ")),
+            Self::Conflict(errors) => {
+                write!(f, "conflicting Fluent messages: {}", errors.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for LocaleError {}
+
+/// Applies changes of the [`IcedLocale`] resource, when it exists.
+pub struct IcedLocalePlugin;
+
+impl Plugin for IcedLocalePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            apply_locale
+                .run_if(resource_exists::<IcedLocale>)
+                .after(systems::detect_updates)
+                .before(crate::run_iced_update),
+        );
+    }
+}
+
+fn apply_locale(
+    locale: Res<IcedLocale>,
+    props: Option<Res<IcedResource>>,
+    mut needs_update: ResMut<IcedNeedsUpdate>,
+    mut errors: ErrorReporter,
+    // The number of fonts loaded for each language.
+    mut loaded: Local<HashMap<LanguageIdentifier, usize>>,
+) {
+    if locale.is_changed() {
+        **needs_update = true;
+    }
+
+    // Fonts are loaded even when the locale didn't change, in case the renderer didn't
+    // exist yet when it did.
+    let Some(props) = props else {
+        return;
+    };
+    let fonts = locale
+        .fonts
+        .get(&locale.language)
+        .map_or(&[][..], Vec::as_slice);
+    let loaded = loaded.entry(locale.language.clone()).or_default();
+    if *loaded < fonts.len() {
+        let mut props = props.lock_or_recover(&mut errors);
+        for font in &fonts[*loaded..] {
+            props.renderer.load_font(font.clone());
+        }
+        *loaded = fonts.len();
+    }
+}
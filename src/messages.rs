@@ -0,0 +1,55 @@
+use bevy_app::First;
+use bevy_ecs::event::{event_update_condition, event_update_system, Event, EventUpdates, Events};
+use bevy_ecs::prelude::{IntoSystemConfigs, World};
+use bevy_ecs::schedule::Schedules;
+use bevy_ecs::system::{Commands, ResMut, SystemParam};
+
+use crate::middleware::IcedMessageMiddlewares;
+use crate::IcedError;
+
+// Sends the messages of interfaces, without requiring their event type to be registered
+// when the system is initialized.
+#[derive(SystemParam)]
+pub struct MessageSender<'w, 's, M: Event> {
+    events: Option<ResMut<'w, Events<M>>>,
+    middlewares: Option<ResMut<'w, IcedMessageMiddlewares<M>>>,
+    commands: Commands<'w, 's>,
+}
+
+impl<'w, 's, M: Event> MessageSender<'w, 's, M> {
+    // Send `messages` through the middlewares, draining them. If the event type is not
+    // registered, the messages are discarded, and it is registered for the next frames when
+    // `auto_register` is set.
+    pub fn send(&mut self, messages: &mut Vec<M>, auto_register: bool) -> Result<(), IcedError> {
+        if let Some(middlewares) = &mut self.middlewares {
+            middlewares.apply(messages);
+        }
+        if let Some(events) = &mut self.events {
+            events.send_batch(messages.drain(..));
+            return Ok(());
+        }
+        messages.clear();
+        if auto_register {
+            self.commands.add(register_events::<M>);
+            Ok(())
+        } else {
+            Err(IcedError::UnregisteredMessage(std::any::type_name::<M>()))
+        }
+    }
+}
+
+// What `App::add_event` does, for event types registered while the app is running.
+fn register_events<M: Event>(world: &mut World) {
+    if world.contains_resource::<Events<M>>() {
+        return;
+    }
+    world.init_resource::<Events<M>>();
+    let mut schedules = world.resource_mut::<Schedules>();
+    if let Some(first) = schedules.get_mut(First) {
+        first.add_systems(
+            event_update_system::<M>
+                .in_set(EventUpdates)
+                .run_if(event_update_condition::<M>),
+        );
+    }
+}
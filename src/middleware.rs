@@ -0,0 +1,152 @@
+use bevy_ecs::event::Event;
+use bevy_ecs::system::{ResMut, Resource};
+use iced_core::Event as IcedEvent;
+
+use crate::IcedEventQueue;
+
+/// Inspects, modifies or drops the input of interfaces before they process it.
+///
+/// Closures taking a `&mut iced::Event` and returning whether to keep it implement this.
+pub trait IcedEventFilter: Send + Sync + 'static {
+    /// Inspect or modify `event`, returning `false` to drop it.
+    fn filter(&mut self, event: &mut IcedEvent) -> bool;
+}
+
+impl<F: FnMut(&mut IcedEvent) -> bool + Send + Sync + 'static> IcedEventFilter for F {
+    fn filter(&mut self, event: &mut IcedEvent) -> bool {
+        self(event)
+    }
+}
+
+/// Inspects, modifies or drops the messages of interfaces before they are sent.
+///
+/// Closures taking a message and returning the one to send, if any, implement this.
+pub trait IcedMessageMiddleware<M>: Send + Sync + 'static {
+    /// Inspect or modify `message`, returning `None` to drop it.
+    fn process(&mut self, message: M) -> Option<M>;
+}
+
+impl<M, F: FnMut(M) -> Option<M> + Send + Sync + 'static> IcedMessageMiddleware<M> for F {
+    fn process(&mut self, message: M) -> Option<M> {
+        self(message)
+    }
+}
+
+/// The [`IcedEventFilter`]s applied to the input of interfaces, in the order they were
+/// added.
+///
+/// They see the input of every window once per frame, as soon as it is read in
+/// [`PreUpdate`](bevy_app::PreUpdate), starting with the redraw tick that animations
+/// follow. Events injected afterwards, e.g. by the virtual cursor or with
+/// [`IcedContext::inject_event`](crate::IcedContext::inject_event), are not filtered.
+///
+/// ```ignore
+/// // Block clicks while the tutorial is explaining something.
+/// let explaining = Arc::new(AtomicBool::new(false));
+/// let blocked = explaining.clone();
+/// app.world
+///     .resource_mut::<IcedEventFilters>()
+///     .add(move |event: &mut iced::Event| match event {
+///         iced::Event::Mouse(mouse::Event::ButtonPressed(_)) => !blocked.load(Ordering::Relaxed),
+///         _ => true,
+///     });
+/// ```
+#[derive(Resource, Default)]
+pub struct IcedEventFilters {
+    filters: Vec<Box<dyn IcedEventFilter>>,
+}
+
+impl IcedEventFilters {
+    /// Add a filter, applied after the ones already added.
+    pub fn add(&mut self, filter: impl IcedEventFilter) -> &mut Self {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    /// Remove all the filters.
+    pub fn clear(&mut self) {
+        self.filters.clear();
+    }
+
+    /// Whether there is no filter.
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    pub(crate) fn apply(&mut self, events: &mut Vec<IcedEvent>) {
+        events.retain_mut(|event| self.filters.iter_mut().all(|filter| filter.filter(event)));
+    }
+}
+
+/// The [`IcedMessageMiddleware`]s applied to the messages of the interfaces of message
+/// type `M`, in the order they were added.
+///
+/// Insert it with `app.init_resource::<IcedMessageMiddlewares<M>>()`. Middlewares run when
+/// an interface sends its messages, so systems reading them in the same frame only see the
+/// messages the middlewares let through.
+///
+/// ```ignore
+/// // Count the clicks on every button, for analytics.
+/// let clicks = Arc::new(AtomicUsize::new(0));
+/// let counter = clicks.clone();
+/// app.world
+///     .resource_mut::<IcedMessageMiddlewares<UiMessage>>()
+///     .add(move |message: UiMessage| {
+///         counter.fetch_add(1, Ordering::Relaxed);
+///         Some(message)
+///     });
+/// ```
+#[derive(Resource)]
+pub struct IcedMessageMiddlewares<M: Event> {
+    middlewares: Vec<Box<dyn IcedMessageMiddleware<M>>>,
+}
+
+impl<M: Event> Default for IcedMessageMiddlewares<M> {
+    fn default() -> Self {
+        Self {
+            middlewares: Vec::new(),
+        }
+    }
+}
+
+impl<M: Event> IcedMessageMiddlewares<M> {
+    /// Add a middleware, applied to the messages the ones already added let through.
+    pub fn add(&mut self, middleware: impl IcedMessageMiddleware<M>) -> &mut Self {
+        self.middlewares.push(Box::new(middleware));
+        self
+    }
+
+    /// Remove all the middlewares.
+    pub fn clear(&mut self) {
+        self.middlewares.clear();
+    }
+
+    /// Whether there is no middleware.
+    pub fn is_empty(&self) -> bool {
+        self.middlewares.is_empty()
+    }
+
+    pub(crate) fn apply(&mut self, messages: &mut Vec<M>) {
+        if self.middlewares.is_empty() {
+            return;
+        }
+        let pending = std::mem::take(messages);
+        messages.extend(pending.into_iter().filter_map(|message| {
+            self.middlewares
+                .iter_mut()
+                .try_fold(message, |message, middleware| middleware.process(message))
+        }));
+    }
+}
+
+pub(crate) fn apply_event_filters(
+    mut filters: ResMut<IcedEventFilters>,
+    mut event_queue: ResMut<IcedEventQueue>,
+) {
+    if filters.is_empty() {
+        return;
+    }
+    for queue in event_queue.queues_mut() {
+        filters.apply(queue);
+    }
+}
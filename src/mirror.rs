@@ -0,0 +1,158 @@
+use iced_core::widget::tree::{self, Tree};
+use iced_core::widget::{Operation, Widget};
+use iced_core::{
+    event, layout, mouse, overlay, renderer, Clipboard, Element, Event, Layout, Length, Point,
+    Rectangle, Shell, Size, Vector,
+};
+
+/// A widget laying its content out from right to left, for right-to-left languages.
+///
+/// The layout of the content is mirrored horizontally: rows list their children from the
+/// right, and content aligned to the left is aligned to the right, and the other way
+/// around. Text keeps its shaping, but is still aligned within its own bounds: use
+/// [`text_with_direction`](crate::widgets::text_with_direction) for text filling a width.
+///
+/// This is what [`IcedContext::display_with_direction`](crate::IcedContext::display_with_direction)
+/// wraps interfaces in.
+pub struct Mirrored<'a, Message, Theme, Renderer> {
+    content: Element<'a, Message, Theme, Renderer>,
+}
+
+/// Creates a [`Mirrored`] widget, laying `content` out from right to left.
+pub fn mirrored<'a, Message, Theme, Renderer>(
+    content: impl Into<Element<'a, Message, Theme, Renderer>>,
+) -> Mirrored<'a, Message, Theme, Renderer> {
+    Mirrored {
+        content: content.into(),
+    }
+}
+
+// Mirror `node`, positioned in a parent `parent_width` wide, and its children.
+fn mirror(node: &layout::Node, parent_width: f32) -> layout::Node {
+    let bounds = node.bounds();
+    let children = node
+        .children()
+        .iter()
+        .map(|child| mirror(child, bounds.width))
+        .collect();
+    layout::Node::with_children(bounds.size(), children)
+        .move_to(Point::new(parent_width - bounds.x - bounds.width, bounds.y))
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Mirrored<'a, Message, Theme, Renderer>
+where
+    Renderer: iced_core::Renderer,
+{
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.content.as_widget().size_hint()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let node = self.content.as_widget().layout(tree, renderer, limits);
+        mirror(&node, node.size().width)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content
+            .as_widget()
+            .draw(tree, renderer, theme, style, layout, cursor, viewport);
+    }
+
+    fn tag(&self) -> tree::Tag {
+        self.content.as_widget().tag()
+    }
+
+    fn state(&self) -> tree::State {
+        self.content.as_widget().state()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.content.as_widget().children()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        self.content.as_widget().diff(tree);
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(tree, layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        self.content.as_widget_mut().on_event(
+            tree, event, layout, cursor, renderer, clipboard, shell, viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content
+            .as_widget()
+            .mouse_interaction(tree, layout, cursor, viewport, renderer)
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(tree, layout, renderer, translation)
+    }
+}
+
+impl<'a, Message: 'a, Theme: 'a, Renderer> From<Mirrored<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Renderer: iced_core::Renderer + 'a,
+{
+    fn from(mirrored: Mirrored<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(mirrored)
+    }
+}
@@ -0,0 +1,325 @@
+use std::marker::PhantomData;
+
+use bevy_app::{App, Plugin, PreUpdate, Update};
+use bevy_ecs::prelude::{
+    Condition, Event, EventReader, EventWriter, IntoSystemConfigs, Res, ResMut, Resource,
+};
+use iced_core::{Event as IcedEvent, Length};
+use iced_widget::style::theme;
+use iced_widget::MouseArea;
+
+use crate::ui_types::{Color, Element};
+use crate::widgets::{self, PanelStyle};
+use crate::{iced_needs_update, IcedContext, IcedUpdate};
+
+// Over every interface but the diagnostics overlay and the virtual cursor.
+const MODAL_LAYER: i32 = i32::MAX - 2;
+
+/// A dialog shown by [`IcedModals`], which resolves to a message of type `M`.
+///
+/// The dialog is closed when it is confirmed, cancelled with its cancel button, or
+/// cancelled by a click on the backdrop around it.
+pub struct IcedModal<M> {
+    title: String,
+    text: String,
+    kind: ModalKind<M>,
+    confirm_label: String,
+    cancel_label: String,
+    on_cancel: Option<M>,
+}
+
+enum ModalKind<M> {
+    Confirm(M),
+    Prompt {
+        value: String,
+        placeholder: String,
+        on_submit: Box<dyn Fn(String) -> M + Send + Sync>,
+    },
+}
+
+impl<M> IcedModal<M> {
+    /// A dialog asking to confirm an action, sending `on_confirm` when it is.
+    pub fn confirm(title: impl Into<String>, text: impl Into<String>, on_confirm: M) -> Self {
+        Self::new(title, text, ModalKind::Confirm(on_confirm))
+    }
+
+    /// A dialog asking for a line of text, sending the message built by `on_submit` from
+    /// it when it is confirmed.
+    pub fn prompt(
+        title: impl Into<String>,
+        text: impl Into<String>,
+        on_submit: impl Fn(String) -> M + Send + Sync + 'static,
+    ) -> Self {
+        Self::new(
+            title,
+            text,
+            ModalKind::Prompt {
+                value: String::new(),
+                placeholder: String::new(),
+                on_submit: Box::new(on_submit),
+            },
+        )
+    }
+
+    fn new(title: impl Into<String>, text: impl Into<String>, kind: ModalKind<M>) -> Self {
+        Self {
+            title: title.into(),
+            text: text.into(),
+            kind,
+            confirm_label: String::from("OK"),
+            cancel_label: String::from("Cancel"),
+            on_cancel: None,
+        }
+    }
+
+    /// Set the text a prompt starts with. This does nothing for confirm dialogs.
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        if let ModalKind::Prompt { value: current, .. } = &mut self.kind {
+            *current = value.into();
+        }
+        self
+    }
+
+    /// Set the placeholder of the text input of a prompt. This does nothing for confirm
+    /// dialogs.
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        if let ModalKind::Prompt {
+            placeholder: current,
+            ..
+        } = &mut self.kind
+        {
+            *current = placeholder.into();
+        }
+        self
+    }
+
+    /// Set the labels of the confirm and cancel buttons, `OK` and `Cancel` by default.
+    pub fn labels(mut self, confirm: impl Into<String>, cancel: impl Into<String>) -> Self {
+        self.confirm_label = confirm.into();
+        self.cancel_label = cancel.into();
+        self
+    }
+
+    /// Send `message` when the dialog is cancelled. By default, nothing is sent.
+    pub fn on_cancel(mut self, message: M) -> Self {
+        self.on_cancel = Some(message);
+        self
+    }
+}
+
+/// The stack of dialogs displayed over the interfaces, resolving to messages of type `M`.
+///
+/// Only the dialog on top of the stack is shown, over a backdrop that blocks the input of
+/// the interfaces displayed in [`Update`]. Once it is closed, the one under it is shown.
+///
+/// ```ignore
+/// fn quit_button(mut modals: ResMut<IcedModals<UiMessage>>, ...) {
+///     modals.push(IcedModal::confirm("Quit", "Unsaved progress will be lost.", UiMessage::Quit));
+/// }
+/// ```
+///
+/// Requires the [`IcedModalsPlugin`] of the message type.
+#[derive(Resource)]
+pub struct IcedModals<M: Event> {
+    stack: Vec<IcedModal<M>>,
+}
+
+impl<M: Event> Default for IcedModals<M> {
+    fn default() -> Self {
+        Self { stack: Vec::new() }
+    }
+}
+
+impl<M: Event> IcedModals<M> {
+    /// Show `modal` over the interfaces and the other dialogs.
+    pub fn push(&mut self, modal: IcedModal<M>) {
+        self.stack.push(modal);
+    }
+
+    /// Close the dialog on top of the stack without sending any message, returning it.
+    pub fn pop(&mut self) -> Option<IcedModal<M>> {
+        self.stack.pop()
+    }
+
+    /// Close every dialog without sending any message.
+    pub fn clear(&mut self) {
+        self.stack.clear();
+    }
+
+    /// Whether a dialog is shown.
+    pub fn is_open(&self) -> bool {
+        !self.stack.is_empty()
+    }
+
+    /// The number of dialogs in the stack.
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Whether the stack is empty, see [`is_open`](Self::is_open).
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+}
+
+/// Displays the [`IcedModals`] of the message type `M`, and sends the messages they
+/// resolve to.
+///
+/// The dialog processes the input of the frame in the [`IcedUpdate`] schedule, ahead of the
+/// interfaces of [`Update`]: the messages it resolves to are sent before `Update`.
+pub struct IcedModalsPlugin<M>(PhantomData<fn() -> M>);
+
+impl<M> Default for IcedModalsPlugin<M> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<M: Event> Plugin for IcedModalsPlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<IcedModals<M>>()
+            .add_event::<ModalMessage<M>>()
+            .add_systems(
+                IcedUpdate,
+                update_modal::<M>.run_if(modal_open::<M>.and_then(iced_needs_update)),
+            )
+            .add_systems(PreUpdate, resolve_modal::<M>.after(crate::run_iced_update))
+            .add_systems(
+                Update,
+                display_modal::<M>.run_if(modal_open::<M>.and_then(iced_needs_update)),
+            );
+    }
+}
+
+// The interaction of a dialog, turned into the message of its `IcedModal` once resolved.
+#[derive(Event)]
+struct ModalMessage<M> {
+    action: ModalAction,
+    _message: PhantomData<fn() -> M>,
+}
+
+#[derive(Clone)]
+enum ModalAction {
+    Confirm,
+    Cancel,
+    Input(String),
+    // Clicks on the dialog, kept from reaching the backdrop.
+    Ignore,
+}
+
+impl<M> ModalMessage<M> {
+    fn new(action: ModalAction) -> Self {
+        Self {
+            action,
+            _message: PhantomData,
+        }
+    }
+}
+
+impl<M> Clone for ModalMessage<M> {
+    fn clone(&self) -> Self {
+        Self::new(self.action.clone())
+    }
+}
+
+fn modal_open<M: Event>(modals: Res<IcedModals<M>>) -> bool {
+    modals.is_open()
+}
+
+fn view<M: Event>(modal: &IcedModal<M>) -> Element<'_, ModalMessage<M>> {
+    let mut content = vec![
+        widgets::text(&modal.title).size(20).into(),
+        widgets::text(&modal.text).into(),
+    ];
+    if let ModalKind::Prompt {
+        value, placeholder, ..
+    } = &modal.kind
+    {
+        content.push(
+            widgets::text_input(placeholder, value)
+                .on_input(|value| ModalMessage::new(ModalAction::Input(value)))
+                .on_submit(ModalMessage::new(ModalAction::Confirm))
+                .into(),
+        );
+    }
+    content.push(
+        widgets::row(vec![
+            widgets::space(Length::Fill, Length::Shrink).into(),
+            widgets::button(widgets::text(&modal.cancel_label))
+                .style(theme::Button::Secondary)
+                .on_press(ModalMessage::new(ModalAction::Cancel))
+                .into(),
+            widgets::button(widgets::text(&modal.confirm_label))
+                .on_press(ModalMessage::new(ModalAction::Confirm))
+                .into(),
+        ])
+        .spacing(8)
+        .into(),
+    );
+    let dialog = widgets::container(widgets::column(content).spacing(12))
+        .width(360)
+        .padding(16)
+        .style(theme::Container::Box);
+    let dialog = MouseArea::new(dialog).on_press(ModalMessage::new(ModalAction::Ignore));
+    let backdrop = widgets::container(dialog)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x()
+        .center_y()
+        .style(PanelStyle {
+            background: Some(Color::from_rgba(0.0, 0.0, 0.0, 0.5).into()),
+            ..Default::default()
+        });
+    MouseArea::new(backdrop)
+        .on_press(ModalMessage::new(ModalAction::Cancel))
+        .into()
+}
+
+fn update_modal<M: Event>(modals: Res<IcedModals<M>>, mut ctx: IcedContext<ModalMessage<M>>) {
+    if let Some(modal) = modals.stack.last() {
+        ctx.update(view(modal));
+    }
+    // The backdrop covers every other interface.
+    ctx.retain_events(|event| matches!(event, IcedEvent::Window(..)));
+}
+
+fn display_modal<M: Event>(modals: Res<IcedModals<M>>, mut ctx: IcedContext<ModalMessage<M>>) {
+    if let Some(modal) = modals.stack.last() {
+        ctx.display_with_layer(MODAL_LAYER, view(modal));
+    }
+}
+
+fn resolve_modal<M: Event>(
+    mut interactions: EventReader<ModalMessage<M>>,
+    mut modals: ResMut<IcedModals<M>>,
+    mut messages: EventWriter<M>,
+) {
+    for interaction in interactions.read() {
+        let Some(modal) = modals.stack.last_mut() else {
+            break;
+        };
+        match &interaction.action {
+            ModalAction::Input(input) => {
+                if let ModalKind::Prompt { value, .. } = &mut modal.kind {
+                    value.clone_from(input);
+                }
+            }
+            ModalAction::Confirm => {
+                let modal = modals.stack.pop().expect("the stack has a dialog");
+                messages.send(match modal.kind {
+                    ModalKind::Confirm(message) => message,
+                    ModalKind::Prompt {
+                        value, on_submit, ..
+                    } => on_submit(value),
+                });
+            }
+            ModalAction::Cancel => {
+                let modal = modals.stack.pop().expect("the stack has a dialog");
+                if let Some(message) = modal.on_cancel {
+                    messages.send(message);
+                }
+            }
+            ModalAction::Ignore => {}
+        }
+    }
+}
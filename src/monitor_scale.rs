@@ -0,0 +1,47 @@
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::{DetectChangesMut, Entity, IntoSystemConfigs, Query, ResMut, With};
+use bevy_ecs::system::{NonSend, Resource};
+use bevy_window::PrimaryWindow;
+use bevy_winit::WinitWindows;
+
+use crate::{render, systems};
+
+/// The name of the monitor the primary window is on, as reported by the OS.
+///
+/// It is `None` until the window is created, and on platforms that don't name monitors.
+/// List the names in a settings screen to fill
+/// [`IcedSettings::monitor_scale_factors`](crate::IcedSettings::monitor_scale_factors).
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq)]
+pub struct IcedCurrentMonitor(pub Option<String>);
+
+/// Follows the monitor the primary window is on, to scale interfaces with
+/// [`IcedSettings::monitor_scale_factors`](crate::IcedSettings::monitor_scale_factors).
+///
+/// The scale factor changes on the first frame the window is on another monitor, e.g.
+/// when the window is dragged across, or moved with a keyboard shortcut of the OS.
+pub struct IcedMonitorScalePlugin;
+
+impl Plugin for IcedMonitorScalePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<IcedCurrentMonitor>().add_systems(
+            PreUpdate,
+            track_monitor
+                .after(systems::process_input)
+                .before(render::update_viewport),
+        );
+    }
+}
+
+fn track_monitor(
+    windows: Query<Entity, With<PrimaryWindow>>,
+    winit_windows: NonSend<WinitWindows>,
+    mut monitor: ResMut<IcedCurrentMonitor>,
+) {
+    let name = windows
+        .get_single()
+        .ok()
+        .and_then(|entity| winit_windows.get_window(entity))
+        .and_then(|window| window.current_monitor())
+        .and_then(|monitor| monitor.name());
+    monitor.set_if_neq(IcedCurrentMonitor(name));
+}
@@ -0,0 +1,451 @@
+use bevy_ecs::system::Resource;
+use bevy_math::{Rect, URect};
+use bevy_render::render_resource::TextureFormat;
+use bevy_render::texture::Image;
+use iced_core::image::{self, FilterMethod};
+use iced_core::widget::tree::{self, Tree};
+use iced_core::widget::{Operation, Widget};
+use iced_core::{
+    event, layout, mouse, overlay, renderer, Clipboard, Element, Event, Layout, Length, Rectangle,
+    Shell, Size, Vector,
+};
+use iced_widget::style::theme;
+
+use crate::widgets;
+
+/// The widths of the borders of a [`NineSlice`], in texels of the source image.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SliceBorders {
+    /// The width of the left border.
+    pub left: u32,
+    /// The height of the top border.
+    pub top: u32,
+    /// The width of the right border.
+    pub right: u32,
+    /// The height of the bottom border.
+    pub bottom: u32,
+}
+
+impl SliceBorders {
+    /// Borders of `width` texels on every side.
+    pub const fn all(width: u32) -> Self {
+        Self {
+            left: width,
+            top: width,
+            right: width,
+            bottom: width,
+        }
+    }
+}
+
+/// A region of a Bevy [`Image`] cut into nine pieces, to stretch over widgets of any size
+/// without distorting its corners.
+///
+/// The corners keep their size, the edges are stretched along the sides, and the center
+/// fills the rest. The pieces are copied out of the image once, and drawn with the image
+/// pipeline of Iced.
+#[derive(Clone, Debug)]
+pub struct NineSlice {
+    // The pieces in reading order, `None` where a border is empty.
+    pieces: [Option<image::Handle>; 9],
+    borders: SliceBorders,
+    scale: f32,
+    filter_method: FilterMethod,
+}
+
+impl NineSlice {
+    /// Cut the `region` of `image` (e.g. a rectangle of a texture atlas layout, in texels)
+    /// into nine pieces with `borders`.
+    ///
+    /// `None` is returned unless the image is in an 8-bit RGBA format, and the region and
+    /// its borders fit in it.
+    pub fn new(image: &Image, region: Rect, borders: SliceBorders) -> Option<Self> {
+        if !matches!(
+            image.texture_descriptor.format,
+            TextureFormat::Rgba8UnormSrgb | TextureFormat::Rgba8Unorm
+        ) {
+            return None;
+        }
+        let region =
+            URect::from_corners(region.min.round().as_uvec2(), region.max.round().as_uvec2());
+        let size = image.size();
+        if region.max.x > size.x
+            || region.max.y > size.y
+            || borders.left + borders.right > region.width()
+            || borders.top + borders.bottom > region.height()
+        {
+            return None;
+        }
+        let columns = [
+            (region.min.x, borders.left),
+            (
+                region.min.x + borders.left,
+                region.width() - borders.left - borders.right,
+            ),
+            (region.max.x - borders.right, borders.right),
+        ];
+        let rows = [
+            (region.min.y, borders.top),
+            (
+                region.min.y + borders.top,
+                region.height() - borders.top - borders.bottom,
+            ),
+            (region.max.y - borders.bottom, borders.bottom),
+        ];
+        let mut pieces: [Option<image::Handle>; 9] = Default::default();
+        for (i, (y, height)) in rows.into_iter().enumerate() {
+            for (j, (x, width)) in columns.into_iter().enumerate() {
+                pieces[i * 3 + j] = piece(image, x, y, width, height);
+            }
+        }
+        Some(Self {
+            pieces,
+            borders,
+            scale: 1.0,
+            filter_method: FilterMethod::Linear,
+        })
+    }
+
+    /// Set the number of logical pixels a texel of the borders covers, `1.0` by default.
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set how the pieces are filtered when stretched, e.g. [`FilterMethod::Nearest`] for
+    /// pixel art. Defaults to [`FilterMethod::Linear`].
+    pub fn filter_method(mut self, filter_method: FilterMethod) -> Self {
+        self.filter_method = filter_method;
+        self
+    }
+
+    fn draw<Renderer>(&self, renderer: &mut Renderer, bounds: Rectangle)
+    where
+        Renderer: image::Renderer<Handle = image::Handle>,
+    {
+        let borders = self.borders;
+        let horizontal = (borders.left + borders.right) as f32 * self.scale;
+        let vertical = (borders.top + borders.bottom) as f32 * self.scale;
+        // Borders wider than the widget are shrunk to fit it.
+        let fit = |available: f32, borders: f32| {
+            if borders > available {
+                available / borders
+            } else {
+                1.0
+            }
+        };
+        let scale = self.scale * fit(bounds.width, horizontal).min(fit(bounds.height, vertical));
+        let (left, right) = (borders.left as f32 * scale, borders.right as f32 * scale);
+        let (top, bottom) = (borders.top as f32 * scale, borders.bottom as f32 * scale);
+        let columns = [
+            (bounds.x, left),
+            (bounds.x + left, bounds.width - left - right),
+            (bounds.x + bounds.width - right, right),
+        ];
+        let rows = [
+            (bounds.y, top),
+            (bounds.y + top, bounds.height - top - bottom),
+            (bounds.y + bounds.height - bottom, bottom),
+        ];
+        for (i, (y, height)) in rows.into_iter().enumerate() {
+            for (j, (x, width)) in columns.into_iter().enumerate() {
+                let Some(piece) = &self.pieces[i * 3 + j] else {
+                    continue;
+                };
+                if width > 0.0 && height > 0.0 {
+                    renderer.draw(
+                        piece.clone(),
+                        self.filter_method,
+                        Rectangle {
+                            x,
+                            y,
+                            width,
+                            height,
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
+
+// Copy a rectangle of texels of `image` into an Iced image.
+fn piece(image: &Image, x: u32, y: u32, width: u32, height: u32) -> Option<image::Handle> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let stride = image.width() as usize * 4;
+    let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+    for row in y..y + height {
+        let start = row as usize * stride + x as usize * 4;
+        pixels.extend_from_slice(image.data.get(start..start + width as usize * 4)?);
+    }
+    Some(image::Handle::from_pixels(width, height, pixels))
+}
+
+/// A widget drawing a [`NineSlice`] behind its content, stretched over its bounds.
+///
+/// Other slices can be drawn while the cursor is over the widget, and while it is pressed,
+/// for buttons.
+pub struct NineSliceBox<'a, Message, Theme, Renderer> {
+    content: Element<'a, Message, Theme, Renderer>,
+    normal: NineSlice,
+    hovered: Option<NineSlice>,
+    pressed: Option<NineSlice>,
+}
+
+/// Creates a [`NineSliceBox`] drawing `slice` behind `content`.
+pub fn nine_slice<'a, Message, Theme, Renderer>(
+    slice: &NineSlice,
+    content: impl Into<Element<'a, Message, Theme, Renderer>>,
+) -> NineSliceBox<'a, Message, Theme, Renderer> {
+    NineSliceBox {
+        content: content.into(),
+        normal: slice.clone(),
+        hovered: None,
+        pressed: None,
+    }
+}
+
+impl<'a, Message, Theme, Renderer> NineSliceBox<'a, Message, Theme, Renderer> {
+    /// Draw `slice` while the cursor is over the widget.
+    pub fn hovered(mut self, slice: &NineSlice) -> Self {
+        self.hovered = Some(slice.clone());
+        self
+    }
+
+    /// Draw `slice` while the widget is pressed.
+    pub fn pressed(mut self, slice: &NineSlice) -> Self {
+        self.pressed = Some(slice.clone());
+        self
+    }
+}
+
+// Whether the widget was pressed and not released yet.
+#[derive(Default)]
+struct State {
+    pressed: bool,
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for NineSliceBox<'a, Message, Theme, Renderer>
+where
+    Renderer: image::Renderer<Handle = image::Handle>,
+{
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.content.as_widget().size_hint()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let pressed = tree.state.downcast_ref::<State>().pressed;
+        let slice = match (&self.pressed, &self.hovered) {
+            (Some(slice), _) if pressed => slice,
+            (_, Some(slice)) if cursor.is_over(bounds) => slice,
+            _ => &self.normal,
+        };
+        slice.draw(renderer, bounds);
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        // The press is only observed: the content, e.g. a button, still handles it.
+        let state = tree.state.downcast_mut::<State>();
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+            | Event::Touch(iced_core::touch::Event::FingerPressed { .. }) => {
+                state.pressed = cursor.is_over(layout.bounds());
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+            | Event::Touch(iced_core::touch::Event::FingerLifted { .. })
+            | Event::Touch(iced_core::touch::Event::FingerLost { .. }) => {
+                state.pressed = false;
+            }
+            _ => {}
+        }
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer, translation)
+    }
+}
+
+impl<'a, Message: 'a, Theme: 'a, Renderer> From<NineSliceBox<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Renderer: image::Renderer<Handle = image::Handle> + 'a,
+{
+    fn from(nine_slice: NineSliceBox<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(nine_slice)
+    }
+}
+
+/// The [`NineSlice`]s of the panels and buttons of a game, used in place of the flat
+/// backgrounds of the theme.
+///
+/// Fill it once the images are loaded, and build panels and buttons with it. Missing
+/// slices fall back to the styles of the theme.
+///
+/// ```ignore
+/// let atlas = images.get(&ui_atlas).unwrap();
+/// let slice = |index: usize| NineSlice::new(atlas, layout.textures[index], SliceBorders::all(6));
+/// commands.insert_resource(IcedNineSlices {
+///     panel: slice(0),
+///     button: slice(1),
+///     button_hovered: slice(2),
+///     button_pressed: slice(3),
+/// });
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+pub struct IcedNineSlices {
+    /// The background of panels.
+    pub panel: Option<NineSlice>,
+    /// The background of buttons.
+    pub button: Option<NineSlice>,
+    /// The background of buttons under the cursor, instead of [`button`](Self::button).
+    pub button_hovered: Option<NineSlice>,
+    /// The background of pressed buttons, instead of [`button`](Self::button).
+    pub button_pressed: Option<NineSlice>,
+}
+
+impl IcedNineSlices {
+    /// Creates a panel around `content`, with the [`panel`](Self::panel) slice as its
+    /// background.
+    pub fn panel<'a, Message: 'a>(
+        &self,
+        content: impl Into<crate::ui_types::Element<'a, Message>>,
+    ) -> crate::ui_types::Element<'a, Message> {
+        match &self.panel {
+            Some(slice) => nine_slice(slice, content).into(),
+            None => widgets::container(content)
+                .style(theme::Container::Box)
+                .into(),
+        }
+    }
+
+    /// Creates a button around `content`, sending `on_press` when pressed, with the button
+    /// slices as its background.
+    pub fn button<'a, Message: Clone + 'a>(
+        &self,
+        content: impl Into<crate::ui_types::Element<'a, Message>>,
+        on_press: Message,
+    ) -> crate::ui_types::Element<'a, Message> {
+        let button = widgets::button(content).on_press(on_press);
+        let Some(slice) = &self.button else {
+            return button.into();
+        };
+        let mut nine_slice = nine_slice(slice, button.style(theme::Button::Text));
+        if let Some(hovered) = &self.button_hovered {
+            nine_slice = nine_slice.hovered(hovered);
+        }
+        if let Some(pressed) = &self.button_pressed {
+            nine_slice = nine_slice.pressed(pressed);
+        }
+        nine_slice.into()
+    }
+}
@@ -0,0 +1,295 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+
+use bevy_utils::HashMap;
+use iced_core::widget::operation::scrollable::AbsoluteOffset;
+use iced_core::widget::operation::{Focusable, Scrollable};
+use iced_core::widget::tree::{self, Tree};
+use iced_core::widget::{Id, Operation, Widget};
+use iced_core::{
+    event, layout, mouse, overlay, renderer, Clipboard, Element, Event, Layout, Length, Rectangle,
+    Shell, Size, Vector,
+};
+use serde::{Deserialize, Serialize};
+
+/// A widget whose scroll positions and text input focus are saved with
+/// [`IcedContext::save_state`](crate::IcedContext::save_state), and restored with
+/// [`IcedContext::load_state`](crate::IcedContext::load_state).
+///
+/// The scrollables and text inputs of the content are told apart by their order, so the
+/// content should keep the same structure across restarts. Keys must be unique across all
+/// interfaces: wrapping every panel of an editor in its own `persistent` keeps the state of
+/// a panel restorable even when another one changes.
+///
+/// **Only the scroll offsets and the focused text input are saved, not the text values.**
+/// Iced widgets don't own the value of a text input, collapsed sections or other state
+/// passed to them: it belongs to the application, which saves it alongside the bytes of
+/// `save_state`, e.g. by serializing the resource its interface is built from.
+pub struct Persistent<'a, Message, Theme, Renderer> {
+    key: String,
+    content: Element<'a, Message, Theme, Renderer>,
+}
+
+/// Creates a [`Persistent`] widget saving the state of `content` under `key`.
+pub fn persistent<'a, Message, Theme, Renderer>(
+    key: impl Into<String>,
+    content: impl Into<Element<'a, Message, Theme, Renderer>>,
+) -> Persistent<'a, Message, Theme, Renderer> {
+    Persistent {
+        key: key.into(),
+        content: content.into(),
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Persistent<'a, Message, Theme, Renderer>
+where
+    Renderer: iced_core::Renderer,
+{
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.content.as_widget().size_hint()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content.as_widget().layout(tree, renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content
+            .as_widget()
+            .draw(tree, renderer, theme, style, layout, cursor, viewport);
+    }
+
+    fn tag(&self) -> tree::Tag {
+        self.content.as_widget().tag()
+    }
+
+    fn state(&self) -> tree::State {
+        self.content.as_widget().state()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.content.as_widget().children()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        self.content.as_widget().diff(tree);
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        operation.custom(&mut Scope::Enter(self.key.clone()), None);
+        self.content
+            .as_widget()
+            .operate(tree, layout, renderer, operation);
+        operation.custom(&mut Scope::Leave, None);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        self.content.as_widget_mut().on_event(
+            tree, event, layout, cursor, renderer, clipboard, shell, viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content
+            .as_widget()
+            .mouse_interaction(tree, layout, cursor, viewport, renderer)
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(tree, layout, renderer, translation)
+    }
+}
+
+impl<'a, Message: 'a, Theme: 'a, Renderer> From<Persistent<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Renderer: iced_core::Renderer + 'a,
+{
+    fn from(persistent: Persistent<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(persistent)
+    }
+}
+
+// Reported to operations around the content of a `Persistent` widget.
+enum Scope {
+    Enter(String),
+    Leave,
+}
+
+// The state of the widgets of a `Persistent` widget.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct PersistedWidgets {
+    // The offsets of the scrollables, in operation order.
+    scroll_offsets: Vec<[f32; 2]>,
+    // The index of the focused text input, in operation order.
+    focused_input: Option<usize>,
+}
+
+// The state of the `Persistent` widgets displayed so far, and the state loaded for the
+// ones that weren't displayed since.
+#[derive(Default)]
+pub(crate) struct PersistedState {
+    saved: HashMap<String, PersistedWidgets>,
+    loaded: HashMap<String, PersistedWidgets>,
+}
+
+impl PersistedState {
+    // Restore the loaded state of the `Persistent` widgets of an interface, and save their
+    // current state, with the operation given to `operate`.
+    pub fn update<M>(&mut self, operate: impl FnOnce(&mut dyn Operation<M>)) {
+        operate(&mut PersistOperation {
+            state: self,
+            scopes: Vec::new(),
+        });
+    }
+
+    pub fn save(&self) -> Vec<u8> {
+        // Sorted, so that saving the same state gives the same bytes.
+        let state: BTreeMap<_, _> = self.saved.iter().chain(&self.loaded).collect();
+        serde_json::to_vec(&state).unwrap_or_default()
+    }
+
+    pub fn load(&mut self, bytes: &[u8]) -> serde_json::Result<()> {
+        self.loaded = serde_json::from_slice(bytes)?;
+        Ok(())
+    }
+}
+
+// The `Persistent` widget being operated on, with the state loaded for it.
+struct OpenScope {
+    key: String,
+    widgets: PersistedWidgets,
+    loaded: Option<PersistedWidgets>,
+    inputs: usize,
+}
+
+struct PersistOperation<'a> {
+    state: &'a mut PersistedState,
+    scopes: Vec<OpenScope>,
+}
+
+impl<T> Operation<T> for PersistOperation<'_> {
+    fn container(
+        &mut self,
+        _id: Option<&Id>,
+        _bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+        operate_on_children(self);
+    }
+
+    fn scrollable(
+        &mut self,
+        state: &mut dyn Scrollable,
+        _id: Option<&Id>,
+        _bounds: Rectangle,
+        translation: Vector,
+    ) {
+        let Some(scope) = self.scopes.last_mut() else {
+            return;
+        };
+        let index = scope.widgets.scroll_offsets.len();
+        let loaded = scope
+            .loaded
+            .as_ref()
+            .and_then(|loaded| loaded.scroll_offsets.get(index));
+        let offset = match loaded {
+            Some(&[x, y]) => {
+                state.scroll_to(AbsoluteOffset { x, y });
+                [x, y]
+            }
+            None => [translation.x, translation.y],
+        };
+        scope.widgets.scroll_offsets.push(offset);
+    }
+
+    // Text inputs are the only focusable widgets.
+    fn focusable(&mut self, state: &mut dyn Focusable, _id: Option<&Id>) {
+        let Some(scope) = self.scopes.last_mut() else {
+            return;
+        };
+        let index = scope.inputs;
+        scope.inputs += 1;
+        let focused = match &scope.loaded {
+            Some(loaded) if loaded.focused_input == Some(index) => {
+                state.focus();
+                true
+            }
+            Some(_) => {
+                state.unfocus();
+                false
+            }
+            None => state.is_focused(),
+        };
+        if focused {
+            scope.widgets.focused_input = Some(index);
+        }
+    }
+
+    fn custom(&mut self, state: &mut dyn Any, _id: Option<&Id>) {
+        match state.downcast_ref::<Scope>() {
+            Some(Scope::Enter(key)) => self.scopes.push(OpenScope {
+                key: key.clone(),
+                widgets: PersistedWidgets::default(),
+                loaded: self.state.loaded.remove(key),
+                inputs: 0,
+            }),
+            Some(Scope::Leave) => {
+                if let Some(scope) = self.scopes.pop() {
+                    self.state.saved.insert(scope.key, scope.widgets);
+                }
+            }
+            None => {}
+        }
+    }
+}
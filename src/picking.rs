@@ -0,0 +1,53 @@
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::{Entity, EventWriter, IntoSystemConfigs, Query, Res, With};
+use bevy_mod_picking::backend::prelude::{
+    HitData, PickSet, PointerHits, PointerId, PointerLocation,
+};
+use bevy_render::camera::NormalizedRenderTarget;
+use bevy_window::PrimaryWindow;
+
+use crate::IcedPointerOverUi;
+
+// Above the hits of every other backend.
+const ORDER: f32 = 1_000_000.0;
+
+/// A `bevy_mod_picking` backend keeping pointers over interfaces from picking entities
+/// behind them.
+///
+/// While [`IcedPointerOverUi`] is set, pointers over the primary window hit the window
+/// entity itself, which blocks the hits of the other backends.
+pub struct IcedPickingPlugin;
+
+impl Plugin for IcedPickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, block_picking.in_set(PickSet::Backend));
+    }
+}
+
+fn block_picking(
+    over_ui: Res<IcedPointerOverUi>,
+    windows: Query<Entity, With<PrimaryWindow>>,
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    mut hits: EventWriter<PointerHits>,
+) {
+    if !**over_ui {
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    for (pointer, location) in &pointers {
+        let Some(location) = location.location() else {
+            continue;
+        };
+        if !matches!(location.target, NormalizedRenderTarget::Window(target) if target.entity() == window)
+        {
+            continue;
+        }
+        hits.send(PointerHits::new(
+            *pointer,
+            vec![(window, HitData::new(window, 0.0, None, None))],
+            ORDER,
+        ));
+    }
+}
@@ -0,0 +1,71 @@
+use std::sync::{Mutex, PoisonError};
+
+use bevy_ecs::system::Resource;
+use iced_wgpu::wgpu;
+use iced_wgpu::wgpu::TextureFormat;
+use iced_widget::graphics::Viewport;
+
+use crate::Primitive;
+
+/// The interfaces of a frame, handed to an [`IcedPresentHook`] to present them to the
+/// primary window.
+pub struct IcedFrame<'a> {
+    /// The primitives of the interfaces, in logical pixels of the window, in draw order.
+    pub primitives: &'a [Primitive],
+    /// The runs of [`primitives`](Self::primitives) faded with the same opacity (see
+    /// [`IcedContext::display_with_opacity`](crate::IcedContext::display_with_opacity)):
+    /// the end of each run, and its opacity, to combine with [`opacity`](Self::opacity).
+    pub runs: &'a [(usize, f32)],
+    /// The viewport the interfaces were laid out in, with the physical size of the window.
+    pub viewport: &'a Viewport,
+    /// The lines of the debug overlay, drawn over the interfaces by the built-in backend.
+    pub overlay: &'a [String],
+    /// The color the window must be cleared to first, or `None` to draw over its contents.
+    pub clear_color: Option<iced_core::Color>,
+    /// The opacity of the interfaces, see
+    /// [`IcedSettings::opacity`](crate::IcedSettings::opacity).
+    pub opacity: f32,
+    /// The format of the surface of the window.
+    pub format: TextureFormat,
+    /// The surface of the window.
+    pub target: &'a wgpu::TextureView,
+    /// The device of the render world.
+    pub device: &'a wgpu::Device,
+    /// The queue of the render world.
+    pub queue: &'a wgpu::Queue,
+    /// The encoder of the commands of the [`IcedPass`](crate::IcedPass) node.
+    pub encoder: &'a mut wgpu::CommandEncoder,
+}
+
+/// Presents the interfaces of the primary window in place of the built-in Iced backend.
+///
+/// Insert it into the render app to draw the primitives of interfaces with a renderer of
+/// your own, e.g. a pipeline with bindless textures:
+///
+/// ```ignore
+/// app.sub_app_mut(RenderApp)
+///     .insert_resource(IcedPresentHook::new(move |frame: IcedFrame| {
+///         my_renderer.draw(frame.encoder, frame.target, frame.primitives, frame.viewport);
+///     }));
+/// ```
+///
+/// The hook is called by the [`IcedPass`](crate::IcedPass) node, on the frames where there
+/// is something to present.
+/// [`IcedSettings::fixed_scale`](crate::IcedSettings::fixed_scale) is not applied, and
+/// interfaces presented elsewhere (tool windows, cameras, `bevy_ui` nodes...) still go
+/// through the built-in backend.
+#[derive(Resource)]
+pub struct IcedPresentHook(Mutex<PresentFn>);
+
+type PresentFn = Box<dyn FnMut(IcedFrame) + Send + Sync>;
+
+impl IcedPresentHook {
+    /// A hook presenting frames with `present`.
+    pub fn new(present: impl FnMut(IcedFrame) + Send + Sync + 'static) -> Self {
+        Self(Mutex::new(Box::new(present)))
+    }
+
+    pub(crate) fn present(&self, frame: IcedFrame) {
+        (self.0.lock().unwrap_or_else(PoisonError::into_inner))(frame);
+    }
+}
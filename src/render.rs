@@ -1,132 +1,1027 @@
 use bevy_derive::{Deref, DerefMut};
-use bevy_ecs::prelude::Query;
+use bevy_ecs::change_detection::DetectChangesMut;
+use bevy_ecs::prelude::{Entity, Query, With};
+use bevy_ecs::query::QueryItem;
 use bevy_ecs::{
-    system::{Commands, Res, Resource},
+    system::{Commands, Local, Res, ResMut, Resource},
     world::World,
 };
+use bevy_math::UVec2;
+use bevy_render::camera::{Camera, ExtractedCamera};
 use bevy_render::render_graph::RenderLabel;
 use bevy_render::renderer::{RenderDevice, RenderQueue};
+use bevy_render::{render_graph::ViewNode, view::ViewTarget};
 use bevy_render::{
     render_graph::{Node, NodeRunError, RenderGraphContext},
     renderer::RenderContext,
     view::ExtractedWindows,
-    Extract,
+    Extract, MainWorld,
 };
-use bevy_window::Window;
-use iced_core::Size;
-use iced_wgpu::wgpu::util::StagingBelt;
+use bevy_utils::tracing::{info_span, warn};
+use bevy_utils::{HashMap, HashSet};
+use bevy_window::{PrimaryWindow, Window};
+use iced_core::time::Instant;
+use iced_core::{Point, Rectangle, Size, Transformation};
 use iced_wgpu::wgpu::TextureFormat;
 use iced_widget::graphics::Viewport;
-use std::sync::Mutex;
+use std::any::TypeId;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
 
-use crate::{DidDraw, IcedProps, IcedResource, IcedSettings};
+use crate::backdrop::{BackdropRegion, BlurPass};
+use crate::compat::{self, Primitive};
+#[cfg(feature = "monitor_scale")]
+use crate::monitor_scale::IcedCurrentMonitor;
+use crate::present_hook::{IcedFrame, IcedPresentHook};
+use crate::scaled::{self, ScaledTarget};
+use crate::systems::{IcedLifecycle, IcedNeedsUpdate};
+use crate::{iced, Compositing, IcedCamera, IcedResource, IcedSettings};
 
+/// The label of the node presenting Iced interfaces to the primary window.
+///
+/// The [`IcedPlugin`](crate::IcedPlugin) adds this node to the main [`RenderGraph`],
+/// running after [`CameraDriverLabel`], so interfaces are drawn over everything the cameras
+/// rendered. Custom pipelines can add edges from or to this label to order their own nodes
+/// around it, or replace the node with one created by [`IcedNode::new`].
+///
+/// [`RenderGraph`]: bevy_render::render_graph::RenderGraph
+/// [`CameraDriverLabel`]: bevy_render::graph::CameraDriverLabel
 #[derive(Clone, Hash, Debug, Eq, PartialEq, RenderLabel)]
 pub struct IcedPass;
 
+// The format the backend of the main world is created with. It only lays out and draws
+// interfaces: the backends presenting them are created with the actual target formats.
 #[cfg(target_arch = "wasm32")]
 pub const TEXTURE_FMT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
 #[cfg(not(target_arch = "wasm32"))]
 pub const TEXTURE_FMT: TextureFormat = TextureFormat::Bgra8UnormSrgb;
 
+/// The viewport that interfaces are laid out in and presented with.
+///
+/// It is updated from the primary window in [`PreUpdate`](bevy_app::PreUpdate), then
+/// extracted to the render world every frame, following the window if it was resized since.
+/// Pipelines rendering at a custom resolution can replace it in the render world after
+/// extraction: the [`IcedPass`] node always presents with the [`ViewportResource`] of the
+/// render world.
 #[derive(Resource, Deref, DerefMut, Clone)]
 pub struct ViewportResource(pub Viewport);
 
+// The primitives produced by one `IcedContext::display` call.
+#[derive(Clone)]
+pub struct DrawnLayer {
+    // The message type of the interface, which replaces its layers of the last frame.
+    pub source: TypeId,
+    pub below_ui: bool,
+    // The layer of `IcedContext::display_with_layer`, drawn after the lower ones.
+    pub layer: i32,
+    // The opacity of `IcedContext::display_with_opacity`.
+    pub opacity: f32,
+    // Whether the layer counts for `IcedContext::is_hovered`.
+    pub hit_test: bool,
+    // Where the layer is presented, instead of the primary window.
+    pub target: Option<LayerTarget>,
+    // The regions of the scene blurred behind the layer.
+    pub backdrops: Vec<BackdropRegion>,
+    pub primitives: Vec<Primitive>,
+}
+
+// The visible area of a layer, in logical pixels of the window.
+pub struct HitRegion {
+    pub source: TypeId,
+    // Whether the layer is above `bevy_ui`, and its layer, which layers are drawn in.
+    pub order: (bool, i32),
+    pub bounds: Rectangle,
+}
+
+// A tool window or a `bevy_ui` node an interface was displayed in, with its own viewport.
+#[derive(Clone)]
+pub struct LayerTarget {
+    pub entity: Entity,
+    pub viewport: Viewport,
+    pub background: iced_core::Color,
+}
+
+// The primitives of the last extracted frame of a target.
+pub struct TargetFrame {
+    pub viewport: Viewport,
+    pub background: iced_core::Color,
+    pub primitives: Vec<Primitive>,
+}
+
+// The primitives of the last extracted frame, split around `bevy_ui`.
+//
+// It only lives in the render world, which owns every backend presenting interfaces.
+#[derive(Resource, Default)]
+pub struct PresentedFrame {
+    pub settings: iced::Settings,
+    pub below_ui: Vec<Primitive>,
+    pub above_ui: Vec<Primitive>,
+    // The runs of `below_ui` and `above_ui` displayed with the same opacity: the end of each
+    // run, and its opacity.
+    pub below_ui_runs: Vec<(usize, f32)>,
+    pub above_ui_runs: Vec<(usize, f32)>,
+    // The lines of the debug overlay, drawn over `above_ui`.
+    pub overlay: Vec<String>,
+    pub below_ui_backdrops: Vec<BackdropRegion>,
+    pub above_ui_backdrops: Vec<BackdropRegion>,
+    pub targets: HashMap<Entity, TargetFrame>,
+    pub compositing: Compositing,
+    pub fixed_scale: Option<f64>,
+    pub opacity: f32,
+    pub stats: IcedRenderStats,
+    // The CPU time spent presenting the frame so far, in nanoseconds, added to by the
+    // render graph nodes.
+    pub present_time: AtomicU64,
+    // How many times the application was resumed, after which backends must be rebuilt.
+    pub resumed: u32,
+    // Whether the primary window is presented with the software renderer.
+    #[cfg(feature = "tiny_skia")]
+    pub software: bool,
+}
+
+impl PresentedFrame {
+    pub fn new(settings: iced::Settings) -> Self {
+        Self {
+            settings,
+            ..Default::default()
+        }
+    }
+
+    // Account for the time spent presenting since `start`.
+    fn add_present_time(&self, start: Instant) {
+        let elapsed = start.elapsed().as_nanos().try_into().unwrap_or(u64::MAX);
+        self.present_time.fetch_add(elapsed, Ordering::Relaxed);
+    }
+}
+
+/// Statistics about the interfaces rendered in the last frame, for performance budgeting.
+///
+/// They are gathered by the render graph and reported when the next frame is extracted, so
+/// they lag one frame behind.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct IcedRenderStats {
+    /// The number of interfaces and [`IcedContext::draw`](crate::IcedContext::draw) calls.
+    pub layers: usize,
+    /// The number of quads (backgrounds, borders...).
+    pub quads: usize,
+    /// The number of text sections.
+    pub text_sections: usize,
+    /// The number of other primitives (images, meshes...).
+    pub other_primitives: usize,
+    /// The CPU time spent encoding the commands presenting the interfaces.
+    pub present_time: Duration,
+}
+
+/// The message types of the interfaces presented in the last frame.
+///
+/// Interfaces skipped on frames that don't need an update (see
+/// [`iced_needs_update`](crate::iced_needs_update)) stay on screen, and active, until a frame
+/// needing an update passes without them being displayed. This is updated when frames are
+/// extracted, so it lags one frame behind displays.
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq)]
+pub struct IcedActiveInterfaces(HashSet<TypeId>);
+
+impl IcedActiveInterfaces {
+    /// Whether an interface of message type `M` was presented.
+    pub fn is_active<M: 'static>(&self) -> bool {
+        self.0.contains(&TypeId::of::<M>())
+    }
+
+    /// Whether no interface was presented.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+// The logical area of the viewport that interfaces are laid out in.
+#[derive(Resource, Deref, DerefMut, Clone)]
+pub struct ViewportBounds {
+    #[deref]
+    pub rect: Rectangle,
+    // Whether the viewport currently has no area (e.g. the window is minimized), in which
+    // case interfaces are neither built nor presented, and `rect` is the last valid one.
+    pub hidden: bool,
+    // The scale factor chosen by the settings in place of the one of the window, resolved
+    // once so that interfaces are presented with the scale factor they were laid out with.
+    pub scale_factor: Option<f64>,
+}
+
+impl ViewportBounds {
+    pub fn new(rect: Rectangle) -> Self {
+        Self {
+            rect,
+            hidden: false,
+            scale_factor: None,
+        }
+    }
+}
+
 pub fn update_viewport(
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<&Camera, With<IcedCamera>>,
     iced_settings: Res<IcedSettings>,
+    lifecycle: Res<IcedLifecycle>,
+    last_bounds: Res<ViewportBounds>,
+    #[cfg(feature = "monitor_scale")] monitor: Option<Res<IcedCurrentMonitor>>,
     mut commands: Commands,
 ) {
-    let window = windows.single();
-    let scale_factor = iced_settings
-        .scale_factor
-        .unwrap_or_else(|| window.scale_factor().into());
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let scale_factor_override = iced_settings.scale_factor;
+    #[cfg(feature = "monitor_scale")]
+    let scale_factor_override = monitor
+        .as_deref()
+        .and_then(|monitor| monitor.0.as_deref())
+        .and_then(|name| iced_settings.monitor_scale_factors.get(name).copied())
+        .or(scale_factor_override);
+    let scale_factor = scale_factor_override.unwrap_or_else(|| window.scale_factor().into());
+    let mut hide = || {
+        if !last_bounds.hidden {
+            commands.insert_resource(ViewportBounds {
+                hidden: true,
+                ..last_bounds.clone()
+            });
+        }
+    };
+    // Minimized windows report a size of zero, which would lead to NaNs during layout.
+    if lifecycle.suspended
+        || window.physical_width() == 0
+        || window.physical_height() == 0
+        || !scale_factor.is_finite()
+        || scale_factor <= 0.0
+    {
+        hide();
+        return;
+    }
     let viewport = Viewport::with_physical_size(
         Size::new(window.physical_width(), window.physical_height()),
         scale_factor,
     );
+    let bounds = iced_settings
+        .bounds
+        .map(|rect| {
+            Rectangle::new(
+                Point::new(rect.min.x, rect.min.y),
+                Size::new(rect.width(), rect.height()),
+            )
+        })
+        .or_else(|| {
+            cameras
+                .iter()
+                .find_map(Camera::physical_viewport_rect)
+                .map(|rect| {
+                    let scale_factor = scale_factor as f32;
+                    Rectangle::new(
+                        Point::new(
+                            rect.min.x as f32 / scale_factor,
+                            rect.min.y as f32 / scale_factor,
+                        ),
+                        Size::new(
+                            rect.width() as f32 / scale_factor,
+                            rect.height() as f32 / scale_factor,
+                        ),
+                    )
+                })
+        })
+        .unwrap_or_else(|| Rectangle::with_size(viewport.logical_size()));
+    if !(bounds.width > 0.0 && bounds.height > 0.0) {
+        hide();
+        return;
+    }
     commands.insert_resource(ViewportResource(viewport));
+    commands.insert_resource(ViewportBounds {
+        scale_factor: scale_factor_override,
+        ..ViewportBounds::new(bounds)
+    });
 }
 
-// Same as DidDraw, but as a regular bool instead of an atomic.
+// Whether there are layers to present this frame.
 #[derive(Resource, Deref, DerefMut)]
 struct DidDrawBasic(bool);
 
+#[allow(clippy::too_many_arguments)]
 pub fn extract_iced_data(
     mut commands: Commands,
     viewport: Extract<Res<ViewportResource>>,
-    did_draw: Extract<Res<DidDraw>>,
+    bounds: Extract<Res<ViewportBounds>>,
+    cameras: Extract<Query<Entity, With<IcedCamera>>>,
+    settings: Extract<Res<IcedSettings>>,
+    lifecycle: Extract<Res<IcedLifecycle>>,
+    windows: Extract<Query<&Window, With<PrimaryWindow>>>,
+    mut presented: ResMut<PresentedFrame>,
 ) {
-    commands.insert_resource(viewport.clone());
-    commands.insert_resource(DidDrawBasic(
-        did_draw.swap(false, std::sync::atomic::Ordering::Relaxed),
+    commands.insert_resource(current_viewport(
+        &viewport,
+        windows.get_single().ok(),
+        bounds.scale_factor,
     ));
+    for entity in &cameras {
+        commands.get_or_spawn(entity).insert(IcedCamera);
+    }
+
+    presented.compositing = settings.compositing;
+    presented.fixed_scale = settings.fixed_scale;
+    presented.opacity = settings.opacity.clamp(0.0, 1.0);
+    presented.resumed = lifecycle.resumed;
+}
+
+// Warn that `Compositing::Transparent` is ignored when presenting into a camera, where it
+// would discard the scene the interfaces are drawn over.
+pub fn warn_camera_compositing(settings: Res<IcedSettings>, mut warned: Local<bool>) {
+    if settings.compositing == Compositing::Transparent && !*warned {
+        warn!(
+            "Iced: `Compositing::Transparent` only applies when presenting to \
+             `PresentTarget::Window`, interfaces are drawn over the scene of the camera"
+        );
+        *warned = true;
+    }
+}
+
+// The viewport to present with, sized like the window as it is extracted.
+//
+// The window can be resized or rescaled after `update_viewport` ran, e.g. by a system
+// changing its resolution, in which case its surface is reconfigured for this frame
+// already: presenting with the size of the layout would stretch the interfaces over it.
+fn current_viewport(
+    viewport: &ViewportResource,
+    window: Option<&Window>,
+    scale_factor_override: Option<f64>,
+) -> ViewportResource {
+    let Some(window) = window else {
+        return viewport.clone();
+    };
+    let size = Size::new(window.physical_width(), window.physical_height());
+    let scale_factor = scale_factor_override.unwrap_or_else(|| window.scale_factor().into());
+    let unchanged = size == viewport.physical_size() && scale_factor == viewport.scale_factor();
+    // A minimized window has no surface to present to anyway.
+    if unchanged
+        || size.width == 0
+        || size.height == 0
+        || !scale_factor.is_finite()
+        || scale_factor <= 0.0
+    {
+        return viewport.clone();
+    }
+    ViewportResource(Viewport::with_physical_size(size, scale_factor))
+}
+
+// Move the layers drawn during the frame out of the main world, into the `PresentedFrame`.
+//
+// The main world is borrowed mutably rather than through `Extract`, so that the layers can
+// be taken without copying them, and without any lock shared by the two worlds.
+pub fn extract_iced_layers(
+    mut commands: Commands,
+    mut main_world: ResMut<MainWorld>,
+    mut presented: ResMut<PresentedFrame>,
+) {
+    // Report the statistics of the frame presented since the last extraction.
+    let stats = IcedRenderStats {
+        present_time: Duration::from_nanos(presented.present_time.swap(0, Ordering::Relaxed)),
+        ..presented.stats.clone()
+    };
+    main_world
+        .resource_mut::<IcedRenderStats>()
+        .set_if_neq(stats);
+
+    let needs_update = main_world.resource::<IcedNeedsUpdate>().0;
+    let hidden = main_world.resource::<ViewportBounds>().hidden;
+    let mut props = main_world.resource_mut::<IcedResource>();
+    // The poison is cleared and reported by the next `IcedContext` using the props.
+    let props = props.0.get_mut().unwrap_or_else(PoisonError::into_inner);
+    presented.overlay = props.debug.overlay();
+    // When interfaces were skipped because nothing changed, present the last ones again.
+    if props.layers.is_empty() && !needs_update {
+        commands.insert_resource(DidDrawBasic(!hidden && !props.presented_layers.is_empty()));
+        return;
+    }
+    for layer in &mut props.layers {
+        // Shared with the copy kept for the next frames, rather than copied.
+        layer.primitives = vec![compat::share(std::mem::take(&mut layer.primitives))];
+    }
+    // The interfaces that weren't drawn are kept on the frames they could skip, and
+    // removed on the others: they aren't displayed anymore.
+    let mut layers = std::mem::take(&mut props.presented_layers);
+    if needs_update {
+        layers.clear();
+    } else {
+        layers.retain(|kept| props.layers.iter().all(|layer| layer.source != kept.source));
+    }
+    layers.append(&mut props.layers);
+    // The sort is stable, so layers with the same priority keep their call order.
+    layers.sort_by_key(|layer| layer.layer);
+    commands.insert_resource(DidDrawBasic(!hidden && !layers.is_empty()));
+    let active = layers.iter().map(|layer| layer.source).collect();
+    props.presented_layers.clone_from(&layers);
+
+    // Extraction happens between two frames, so the layers are complete at this point.
+    let presented = &mut *presented;
+    presented.below_ui.clear();
+    presented.above_ui.clear();
+    presented.below_ui_runs.clear();
+    presented.above_ui_runs.clear();
+    presented.below_ui_backdrops.clear();
+    presented.above_ui_backdrops.clear();
+    presented.targets.clear();
+    presented.stats = IcedRenderStats {
+        layers: layers.len(),
+        ..Default::default()
+    };
+    props.hit_regions.clear();
+    for layer in layers {
+        compat::count_primitives(&layer.primitives, &mut presented.stats);
+        if let Some(target) = layer.target {
+            let frame = presented
+                .targets
+                .entry(target.entity)
+                .or_insert_with(|| TargetFrame {
+                    viewport: target.viewport.clone(),
+                    background: target.background,
+                    primitives: Vec::new(),
+                });
+            frame.primitives.extend(layer.primitives);
+            continue;
+        }
+        if layer.hit_test {
+            let mut regions = Vec::new();
+            compat::visible_regions(
+                &layer.primitives,
+                Transformation::IDENTITY,
+                None,
+                &mut regions,
+            );
+            props
+                .hit_regions
+                .extend(regions.into_iter().map(|bounds| HitRegion {
+                    source: layer.source,
+                    order: (!layer.below_ui, layer.layer),
+                    bounds,
+                }));
+        }
+        // Invisible layers still count for hovering, like they still process input.
+        if layer.opacity <= 0.0 {
+            continue;
+        }
+        if layer.below_ui {
+            presented.below_ui.extend(layer.primitives);
+            presented.below_ui_backdrops.extend(layer.backdrops);
+            let end = presented.below_ui.len();
+            push_run(&mut presented.below_ui_runs, end, layer.opacity);
+        } else {
+            presented.above_ui.extend(layer.primitives);
+            presented.above_ui_backdrops.extend(layer.backdrops);
+            let end = presented.above_ui.len();
+            push_run(&mut presented.above_ui_runs, end, layer.opacity);
+        }
+    }
+    main_world
+        .resource_mut::<IcedActiveInterfaces>()
+        .set_if_neq(IcedActiveInterfaces(active));
+}
+
+// Extend the last run of primitives to `end` if it has the same opacity, or start another.
+fn push_run(runs: &mut Vec<(usize, f32)>, end: usize, opacity: f32) {
+    match runs.last_mut() {
+        Some((last_end, last_opacity)) if *last_opacity == opacity => *last_end = end,
+        _ => runs.push((end, opacity)),
+    }
+}
+
+// A backend presenting a run of primitives, tagged with its format and the number of resumes
+// it was built after, with the offscreen texture the run is faded from.
+struct RunBackend {
+    format: TextureFormat,
+    resumed: u32,
+    backend: iced_wgpu::Backend,
+    scaled: Option<ScaledTarget>,
+}
+
+// Present the `runs` of `primitives` to `view`, each faded by its opacity and the one of
+// the settings, with a backend of `backends` each: a backend can only present once per
+// frame. Runs are rasterized at `fixed_scale` if set, and offscreen when faded.
+#[allow(clippy::too_many_arguments)]
+fn present_runs(
+    backends: &mut Vec<RunBackend>,
+    presented: &PresentedFrame,
+    device: &iced_wgpu::wgpu::Device,
+    queue: &iced_wgpu::wgpu::Queue,
+    encoder: &mut iced_wgpu::wgpu::CommandEncoder,
+    clear_color: Option<iced_core::Color>,
+    format: TextureFormat,
+    view: &iced_wgpu::wgpu::TextureView,
+    primitives: &[Primitive],
+    runs: &[(usize, f32)],
+    fixed_scale: Option<f64>,
+    viewport: &Viewport,
+    overlay: &[String],
+) {
+    let mut start = 0;
+    let mut runs: Vec<_> = runs
+        .iter()
+        .map(|&(end, opacity)| {
+            let run = &primitives[start..end];
+            start = end;
+            (run, opacity)
+        })
+        .collect();
+    // The target is still cleared, and the overlay drawn, without primitives.
+    if runs.is_empty() {
+        runs.push((&[], 1.0));
+    }
+    backends.truncate(runs.len());
+    let last = runs.len() - 1;
+    for (i, (primitives, opacity)) in runs.into_iter().enumerate() {
+        // Resuming recreates the surface, which the state of the backend refers to.
+        if backends
+            .get(i)
+            .is_some_and(|run| (run.format, run.resumed) != (format, presented.resumed))
+        {
+            backends.truncate(i);
+        }
+        if backends.len() == i {
+            backends.push(RunBackend {
+                format,
+                resumed: presented.resumed,
+                backend: compat::new_backend(device, queue, presented.settings, format),
+                scaled: None,
+            });
+        }
+        let RunBackend {
+            backend,
+            scaled: target,
+            ..
+        } = &mut backends[i];
+        let clear_color = clear_color.filter(|_| i == 0);
+        let overlay = if i == last { overlay } else { &[] };
+        let opacity = opacity * presented.opacity;
+        // Fading requires an offscreen texture, at the scale factor unless one is fixed.
+        let scale = match (fixed_scale, opacity < 1.0) {
+            (Some(scale), _) => Some(scale),
+            (None, true) => Some(viewport.scale_factor()),
+            (None, false) => None,
+        };
+        match scale {
+            None => compat::present(
+                backend,
+                device,
+                queue,
+                encoder,
+                clear_color,
+                format,
+                view,
+                primitives,
+                viewport,
+                overlay,
+            ),
+            Some(scale) => {
+                let max_dimension = device.limits().max_texture_dimension_2d;
+                let scaled_viewport = scaled::viewport(viewport, scale, max_dimension);
+                let scaled =
+                    ScaledTarget::prepare(target, device, format, scaled_viewport.physical_size());
+                compat::present(
+                    backend,
+                    device,
+                    queue,
+                    encoder,
+                    Some(iced_core::Color::TRANSPARENT),
+                    format,
+                    scaled.view(),
+                    primitives,
+                    &scaled_viewport,
+                    overlay,
+                );
+                scaled.blit(queue, encoder, view, clear_color.is_some(), opacity);
+            }
+        }
+    }
+}
+
+// The format of the images interfaces are rendered into. Being sRGB, materials and
+// `bevy_ui` nodes sampling them get the colors the interfaces would have in the window.
+#[cfg(any(feature = "ui_node", feature = "world_panel"))]
+pub const IMAGE_TARGET_FMT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+
+// An image of `size` that interfaces can be rendered into.
+#[cfg(any(feature = "ui_node", feature = "world_panel"))]
+pub fn target_image(size: bevy_render::render_resource::Extent3d) -> bevy_render::texture::Image {
+    use bevy_render::render_resource::{TextureDescriptor, TextureDimension, TextureUsages};
+
+    let mut image = bevy_render::texture::Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("bevy_iced image target"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: IMAGE_TARGET_FMT,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        ..Default::default()
+    };
+    image.resize(size);
+    image
+}
+
+// Render the interfaces of targets backed by images (`bevy_ui` nodes, world panels) into
+// them, before the render graph samples them.
+#[cfg(any(feature = "ui_node", feature = "world_panel"))]
+pub fn present_to_images(
+    targets: &[(Entity, bevy_asset::AssetId<bevy_render::texture::Image>)],
+    presented: &PresentedFrame,
+    images: &bevy_render::render_asset::RenderAssets<bevy_render::texture::Image>,
+    render_device: &RenderDevice,
+    render_queue: &RenderQueue,
+    // A backend can only present once per frame, so each target has its own.
+    backends: &mut HashMap<Entity, iced_wgpu::Backend>,
+) {
+    backends.retain(|entity, _| targets.iter().any(|(target, _)| target == entity));
+    if targets.is_empty() {
+        return;
+    }
+    let device = render_device.wgpu_device();
+    let mut encoder = device.create_command_encoder(&iced_wgpu::wgpu::CommandEncoderDescriptor {
+        label: Some("bevy_iced image targets"),
+    });
+    for (entity, image) in targets {
+        let (Some(frame), Some(image)) = (presented.targets.get(entity), images.get(*image)) else {
+            continue;
+        };
+        let backend = backends.entry(*entity).or_insert_with(|| {
+            compat::new_backend(device, render_queue, presented.settings, IMAGE_TARGET_FMT)
+        });
+        compat::present(
+            backend,
+            device,
+            render_queue,
+            &mut encoder,
+            Some(frame.background),
+            IMAGE_TARGET_FMT,
+            &image.texture_view,
+            &frame.primitives,
+            &frame.viewport,
+            &[],
+        );
+    }
+    render_queue.submit([encoder.finish()]);
 }
 
+/// The render graph node presenting Iced interfaces to the primary window.
+///
+/// It draws over the current contents of the window surface, and does nothing on frames
+/// where no interface was displayed. See [`IcedPass`] for how it is added to the graph.
+///
+/// It also presents the interfaces of [`IcedWindow`](crate::IcedWindow)s, which replace the
+/// contents of their windows.
+#[derive(Default)]
 pub struct IcedNode {
-    staging_belt: Mutex<StagingBelt>,
+    // The backends of the runs of primitives with the same opacity.
+    runs: Mutex<Vec<RunBackend>>,
+    // The backends of the tool windows, tagged like the ones of `IcedViewNode`.
+    tool_windows: Mutex<HashMap<Entity, (TextureFormat, u32, iced_wgpu::Backend)>>,
+    #[cfg(feature = "tiny_skia")]
+    software: Mutex<crate::software::SoftwareRenderer>,
 }
 
 impl IcedNode {
+    /// Create an [`IcedNode`], for custom render graphs.
     pub fn new() -> Self {
-        Self {
-            staging_belt: Mutex::new(StagingBelt::new(5 * 1024)),
-        }
+        Self::default()
     }
 }
 
-impl Node for IcedNode {
-    fn update(&mut self, _world: &mut World) {
-        self.staging_belt.lock().unwrap().recall();
+impl IcedNode {
+    // Present the interfaces of the tool windows, each with its own backend.
+    fn present_tool_windows(
+        &self,
+        render_context: &mut RenderContext,
+        windows: &ExtractedWindows,
+        presented: &PresentedFrame,
+        render_device: &RenderDevice,
+        render_queue: &RenderQueue,
+    ) {
+        let mut backends = self
+            .tool_windows
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        // Closed windows don't come back, and neither do their backends.
+        backends.retain(|entity, _| presented.targets.contains_key(entity));
+        let resumed = presented.resumed;
+        // The other targets are `bevy_ui` nodes, presented by the `ui_node` module.
+        for (entity, frame) in &presented.targets {
+            let Some(window) = windows.windows.get(entity) else {
+                continue;
+            };
+            let (Some(view), Some(format)) = (
+                window.swap_chain_texture_view.as_ref(),
+                window.swap_chain_texture_format,
+            ) else {
+                continue;
+            };
+            if backends
+                .get(entity)
+                .is_some_and(|(f, r, _)| (*f, *r) != (format, resumed))
+            {
+                backends.remove(entity);
+            }
+            let (_, _, backend) = backends.entry(*entity).or_insert_with(|| {
+                (
+                    format,
+                    resumed,
+                    compat::new_backend(
+                        render_device.wgpu_device(),
+                        render_queue,
+                        presented.settings,
+                        format,
+                    ),
+                )
+            });
+            compat::present(
+                backend,
+                render_device.wgpu_device(),
+                render_queue,
+                render_context.command_encoder(),
+                Some(frame.background),
+                format,
+                view,
+                &frame.primitives,
+                &frame.viewport,
+                &[],
+            );
+        }
     }
+}
 
+impl Node for IcedNode {
     fn run(
         &self,
         _graph: &mut RenderGraphContext,
         render_context: &mut RenderContext,
         world: &World,
     ) -> Result<(), NodeRunError> {
-        let Some(extracted_window) = world
-            .get_resource::<ExtractedWindows>()
-            .unwrap()
-            .windows
-            .values()
-            .next()
-        else {
+        let Some(windows) = world.get_resource::<ExtractedWindows>() else {
             return Ok(());
         };
 
-        let IcedProps {
-            renderer, debug, ..
-        } = &mut *world.resource::<IcedResource>().lock().unwrap();
-        let crate::Renderer::Wgpu(renderer) = renderer else {
+        let presented = world.resource::<PresentedFrame>();
+        self.present_tool_windows(
+            render_context,
+            windows,
+            presented,
+            world.resource::<RenderDevice>(),
+            world.resource::<RenderQueue>(),
+        );
+        let Some(extracted_window) = windows
+            .primary
+            .and_then(|entity| windows.windows.get(&entity))
+        else {
             return Ok(());
         };
         let render_device = world.resource::<RenderDevice>().wgpu_device();
         let render_queue = world.resource::<RenderQueue>();
         let viewport = world.resource::<ViewportResource>();
 
-        if !world.get_resource::<DidDrawBasic>().is_some_and(|x| x.0) {
+        // A transparent window must be cleared even when there is nothing to draw.
+        let clear_color = match presented.compositing {
+            Compositing::Over => None,
+            Compositing::Transparent => Some(iced_core::Color::TRANSPARENT),
+        };
+        if clear_color.is_none()
+            && (!world.get_resource::<DidDrawBasic>().is_some_and(|x| x.0)
+                || presented.above_ui.is_empty()
+                || presented.opacity <= 0.0)
+        {
+            return Ok(());
+        }
+        // The surface can be missing for a frame, e.g. during fullscreen transitions.
+        let Some(view) = extracted_window.swap_chain_texture_view.as_ref() else {
+            return Ok(());
+        };
+        let format = extracted_window
+            .swap_chain_texture_format
+            .unwrap_or(TEXTURE_FMT);
+
+        let _span = info_span!("iced::render").entered();
+        let start = Instant::now();
+        if let Some(hook) = world.get_resource::<IcedPresentHook>() {
+            hook.present(IcedFrame {
+                primitives: &presented.above_ui,
+                runs: &presented.above_ui_runs,
+                viewport,
+                overlay: &presented.overlay,
+                clear_color,
+                opacity: presented.opacity,
+                format,
+                target: view,
+                device: render_device,
+                queue: render_queue,
+                encoder: render_context.command_encoder(),
+            });
+            presented.add_present_time(start);
+            return Ok(());
+        }
+        #[cfg(feature = "tiny_skia")]
+        if presented.software {
+            let viewport = match presented.fixed_scale {
+                Some(scale) => scaled::viewport(
+                    viewport,
+                    scale,
+                    render_device.limits().max_texture_dimension_2d,
+                ),
+                None => (**viewport).clone(),
+            };
+            self.software
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .present(
+                    render_device,
+                    render_queue,
+                    render_context.command_encoder(),
+                    clear_color.is_some(),
+                    format,
+                    view,
+                    &presented.above_ui,
+                    &viewport,
+                    &presented.overlay,
+                    presented.opacity,
+                );
+            presented.add_present_time(start);
             return Ok(());
         }
-        let view = extracted_window.swap_chain_texture_view.as_ref().unwrap();
-        let staging_belt = &mut *self.staging_belt.lock().unwrap();
+        present_runs(
+            &mut self.runs.lock().unwrap_or_else(PoisonError::into_inner),
+            presented,
+            render_device,
+            render_queue,
+            render_context.command_encoder(),
+            clear_color,
+            format,
+            view,
+            &presented.above_ui,
+            &presented.above_ui_runs,
+            presented.fixed_scale,
+            viewport,
+            &presented.overlay,
+        );
+        presented.add_present_time(start);
+
+        Ok(())
+    }
+}
 
-        renderer.with_primitives(|backend, primitives| {
-            backend.present(
+/// The label of the node presenting Iced interfaces into the main texture of the
+/// [`IcedCamera`], when [`IcedPlugin::target`](crate::IcedPlugin::target) is
+/// [`PresentTarget::Camera`](crate::PresentTarget::Camera).
+///
+/// The node is added to the 2D and 3D camera graphs, after tonemapping and before the end of
+/// post-processing. Order it relative to other post-processing nodes with extra edges: for
+/// example, an edge from this label to FXAA antialiases interfaces, while an edge from FXAA
+/// to this label leaves them untouched.
+#[derive(Clone, Hash, Debug, Eq, PartialEq, RenderLabel)]
+pub struct IcedViewPass;
+
+/// The label of the node presenting Iced interfaces into the output texture of the
+/// [`IcedCamera`], after upscaling, when [`IcedPlugin::target`](crate::IcedPlugin::target) is
+/// [`PresentTarget::Camera`](crate::PresentTarget::Camera).
+///
+/// The node only does something when the camera renders at a different resolution than its
+/// target, in which case it replaces the node labeled [`IcedViewPass`]: scaling interfaces
+/// along with the scene would blur their text.
+#[derive(Clone, Hash, Debug, Eq, PartialEq, RenderLabel)]
+pub struct IcedUpscaledPass;
+
+#[cfg(feature = "bevy_ui")]
+#[derive(Clone, Hash, Debug, Eq, PartialEq, RenderLabel)]
+pub struct IcedBelowUiPass;
+
+// Where in the graph of a camera an `IcedViewNode` runs.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ViewStage {
+    // Before `bevy_ui`, for the layers displayed below it.
+    #[cfg_attr(not(feature = "bevy_ui"), allow(dead_code))]
+    BelowUi,
+    // During post-processing, at the resolution of the camera.
+    PostProcessing,
+    // After upscaling, at the resolution of the camera target.
+    Upscaled,
+}
+
+// Presents layers into the textures of each `IcedCamera`.
+pub struct IcedViewNode {
+    stage: ViewStage,
+    // Each node has its own backends, since a backend can only present once per frame.
+    // The backends are tagged with their format and the number of resumes they were built
+    // after.
+    runs: Mutex<Vec<RunBackend>>,
+    blur: Mutex<Option<BlurPass>>,
+}
+
+impl IcedViewNode {
+    pub fn new(stage: ViewStage) -> Self {
+        Self {
+            stage,
+            runs: Mutex::default(),
+            blur: Mutex::new(None),
+        }
+    }
+}
+
+impl ViewNode for IcedViewNode {
+    type ViewQuery = (
+        &'static ViewTarget,
+        &'static ExtractedCamera,
+        &'static IcedCamera,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (target, camera, _): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let main_size = target.main_texture().size();
+        let upscaled = camera
+            .physical_target_size
+            .is_some_and(|size| size != UVec2::new(main_size.width, main_size.height));
+        match self.stage {
+            ViewStage::PostProcessing if upscaled => return Ok(()),
+            ViewStage::Upscaled if !upscaled => return Ok(()),
+            _ => {}
+        }
+
+        let presented = world.resource::<PresentedFrame>();
+        let (primitives, runs, backdrops, overlay) = match self.stage {
+            ViewStage::BelowUi => (
+                &presented.below_ui,
+                &presented.below_ui_runs,
+                &presented.below_ui_backdrops,
+                &[][..],
+            ),
+            _ => (
+                &presented.above_ui,
+                &presented.above_ui_runs,
+                &presented.above_ui_backdrops,
+                &presented.overlay[..],
+            ),
+        };
+        // Nothing was drawn this frame, or the viewport is hidden.
+        if primitives.is_empty()
+            || presented.opacity <= 0.0
+            || !world.get_resource::<DidDrawBasic>().is_some_and(|x| x.0)
+        {
+            return Ok(());
+        }
+        let render_device = world.resource::<RenderDevice>().wgpu_device();
+        let render_queue = world.resource::<RenderQueue>();
+        let viewport = world.resource::<ViewportResource>();
+
+        // The upscaled texture is the output of the camera, which can't be sampled.
+        if !backdrops.is_empty() && self.stage != ViewStage::Upscaled {
+            let mut blur = self.blur.lock().unwrap_or_else(PoisonError::into_inner);
+            let blur = BlurPass::prepare(
+                &mut blur,
+                render_device,
+                target.main_texture_format(),
+                target.main_texture().size(),
+            );
+            let post_process = target.post_process_write();
+            blur.run(
                 render_device,
-                render_queue,
                 render_context.command_encoder(),
-                None,
-                TEXTURE_FMT,
-                view,
-                primitives,
-                viewport,
-                &debug.overlay(),
+                post_process.source,
+                post_process.destination,
+                backdrops,
+                viewport.scale_factor() as f32,
             );
-        });
+        }
+        let (view, format) = match self.stage {
+            ViewStage::Upscaled => (target.out_texture(), target.out_texture_format()),
+            _ => (target.main_texture_view(), target.main_texture_format()),
+        };
 
-        staging_belt.finish();
+        let _span = info_span!("iced::render").entered();
+        let start = Instant::now();
+        // Camera targets usually have a different format than the window surface.
+        present_runs(
+            &mut self.runs.lock().unwrap_or_else(PoisonError::into_inner),
+            presented,
+            render_device,
+            render_queue,
+            render_context.command_encoder(),
+            None,
+            format,
+            view,
+            primitives,
+            runs,
+            presented.fixed_scale,
+            viewport,
+            overlay,
+        );
+        presented.add_present_time(start);
 
         Ok(())
     }
@@ -0,0 +1,515 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::{IntoSystemConfigs, ResMut, Resource};
+use bevy_ecs::system::Local;
+use iced_core::keyboard::key::Named;
+use iced_core::keyboard::{self, Key, Location, Modifiers};
+use iced_core::mouse::{self, ScrollDelta};
+use iced_core::time::Instant;
+use iced_core::touch::{self, Finger};
+use iced_core::{window, Event as IcedEvent, Point};
+use serde::{Deserialize, Serialize};
+
+use crate::systems::{self, IcedInteraction};
+use crate::IcedEventQueue;
+
+/// Records the input of interfaces, and replays recordings in place of the real input.
+///
+/// This reproduces the widget state bugs that depend on the exact input of a session (a
+/// slider jumping when dragged fast, a text input losing characters...): have users record
+/// the session showing the bug, then replay it frame by frame, as many times as needed.
+///
+/// ```ignore
+/// fn toggle_recording(keys: Res<ButtonInput<KeyCode>>, mut replay: ResMut<IcedReplay>) {
+///     if keys.just_pressed(KeyCode::F9) {
+///         match replay.stop() {
+///             Some(recording) => recording.save("session.jsonl").unwrap(),
+///             None => replay.start_recording(),
+///         }
+///     }
+/// }
+/// ```
+///
+/// The input of the interfaces displayed in the primary window is recorded, along with the
+/// time of every frame, so that animations replay with the recorded timing. Keys without a
+/// name or character, and the input of tool windows, aren't recorded. While a recording is
+/// replayed, the real input is ignored, and the cursor of interfaces is the recorded one,
+/// in place of the window cursor and of any
+/// [`IcedCursorSource`](crate::cursor_source::IcedCursorSource) or virtual cursor.
+///
+/// Requires the [`IcedReplayPlugin`].
+#[derive(Resource, Default)]
+pub struct IcedReplay {
+    state: ReplayState,
+}
+
+#[derive(Default)]
+enum ReplayState {
+    #[default]
+    Idle,
+    Recording {
+        // The time of the first recorded frame.
+        origin: Option<Instant>,
+        frames: Vec<RecordedFrame>,
+    },
+    Replaying {
+        // The time the replay started at, which the recorded times are offset from.
+        origin: Option<Instant>,
+        frames: VecDeque<RecordedFrame>,
+        // The cursor of the last replayed frame.
+        cursor: mouse::Cursor,
+    },
+}
+
+impl IcedReplay {
+    /// Start recording, from the next frame on. A replay in progress is stopped.
+    pub fn start_recording(&mut self) {
+        self.state = ReplayState::Recording {
+            origin: None,
+            frames: Vec::new(),
+        };
+    }
+
+    /// Replay `recording`, from the next frame on, one recorded frame per frame. A
+    /// recording in progress is discarded.
+    pub fn replay(&mut self, recording: IcedRecording) {
+        self.state = ReplayState::Replaying {
+            origin: None,
+            frames: recording.frames.into(),
+            cursor: mouse::Cursor::Unavailable,
+        };
+    }
+
+    /// Stop recording or replaying, returning what was recorded.
+    pub fn stop(&mut self) -> Option<IcedRecording> {
+        match std::mem::take(&mut self.state) {
+            ReplayState::Recording { frames, .. } => Some(IcedRecording { frames }),
+            _ => None,
+        }
+    }
+
+    /// Whether the input is being recorded.
+    pub fn is_recording(&self) -> bool {
+        matches!(self.state, ReplayState::Recording { .. })
+    }
+
+    /// Whether a recording is being replayed. This stops once all its frames were.
+    pub fn is_replaying(&self) -> bool {
+        matches!(self.state, ReplayState::Replaying { .. })
+    }
+}
+
+/// The input of interfaces during a session, recorded by [`IcedReplay`].
+///
+/// Recordings are saved as JSON lines, one per frame.
+#[derive(Clone, Debug, Default)]
+pub struct IcedRecording {
+    frames: Vec<RecordedFrame>,
+}
+
+impl IcedRecording {
+    /// Load a recording saved with [`save`](Self::save).
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let frames = BufReader::new(File::open(path)?)
+            .lines()
+            .filter(|line| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+            .map(|line| {
+                serde_json::from_str(&line?)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(Self { frames })
+    }
+
+    /// Save the recording to `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        for frame in &self.frames {
+            serde_json::to_writer(&mut file, frame)?;
+            writeln!(file)?;
+        }
+        file.flush()
+    }
+
+    /// The number of recorded frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether no frame was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// Records and replays the input of interfaces with the [`IcedReplay`] resource.
+pub struct IcedReplayPlugin;
+
+impl Plugin for IcedReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<IcedReplay>().add_systems(
+            PreUpdate,
+            record_or_replay.in_set(systems::InputSet::Replay),
+        );
+    }
+}
+
+fn record_or_replay(
+    mut replay: ResMut<IcedReplay>,
+    mut event_queue: ResMut<IcedEventQueue>,
+    mut interaction: ResMut<IcedInteraction>,
+    mut replayed: Local<bool>,
+) {
+    // The redraw tick is always first, and marks the frame.
+    let Some(&IcedEvent::Window(id, window::Event::RedrawRequested(now))) = event_queue.first()
+    else {
+        return;
+    };
+    let finished = match &mut replay.state {
+        ReplayState::Idle => false,
+        ReplayState::Recording { origin, frames } => {
+            let origin = *origin.get_or_insert(now);
+            frames.push(RecordedFrame {
+                time: (now - origin).as_secs_f64(),
+                events: event_queue[1..].iter().filter_map(record).collect(),
+            });
+            false
+        }
+        ReplayState::Replaying {
+            origin,
+            frames,
+            cursor,
+        } => match frames.pop_front() {
+            Some(frame) => {
+                let origin = *origin.get_or_insert(now);
+                let tick = origin + Duration::from_secs_f64(frame.time);
+                event_queue.clear();
+                event_queue.push(IcedEvent::Window(id, window::Event::RedrawRequested(tick)));
+                event_queue.extend(frame.events.iter().filter_map(RecordedEvent::to_event));
+                // Widgets are given the cursor along with the events, which must agree.
+                for event in &event_queue[1..] {
+                    match event {
+                        IcedEvent::Mouse(mouse::Event::CursorMoved { position }) => {
+                            *cursor = mouse::Cursor::Available(*position);
+                        }
+                        IcedEvent::Mouse(mouse::Event::CursorLeft) => {
+                            *cursor = mouse::Cursor::Unavailable;
+                        }
+                        _ => {}
+                    }
+                }
+                interaction.virtual_cursor = None;
+                interaction.cursor_source = Some(*cursor);
+                false
+            }
+            None => true,
+        },
+    };
+    if finished {
+        replay.state = ReplayState::Idle;
+    }
+    // Once the replay stops, the cursor sources set their cursor again on the next frame.
+    if std::mem::replace(&mut *replayed, replay.is_replaying()) && !replay.is_replaying() {
+        interaction.cursor_source = None;
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct RecordedFrame {
+    // The time of the frame, in seconds since the first one.
+    time: f64,
+    events: Vec<RecordedEvent>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+enum RecordedEvent {
+    CursorEntered,
+    CursorLeft,
+    CursorMoved {
+        x: f32,
+        y: f32,
+    },
+    ButtonPressed(RecordedButton),
+    ButtonReleased(RecordedButton),
+    WheelLines {
+        x: f32,
+        y: f32,
+    },
+    WheelPixels {
+        x: f32,
+        y: f32,
+    },
+    KeyPressed {
+        key: RecordedKey,
+        location: RecordedLocation,
+        modifiers: u32,
+        text: Option<String>,
+    },
+    KeyReleased {
+        key: RecordedKey,
+        location: RecordedLocation,
+        modifiers: u32,
+    },
+    ModifiersChanged(u32),
+    FingerPressed {
+        id: u64,
+        x: f32,
+        y: f32,
+    },
+    FingerMoved {
+        id: u64,
+        x: f32,
+        y: f32,
+    },
+    FingerLifted {
+        id: u64,
+        x: f32,
+        y: f32,
+    },
+    FingerLost {
+        id: u64,
+        x: f32,
+        y: f32,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+enum RecordedButton {
+    Left,
+    Right,
+    Middle,
+    Back,
+    Forward,
+    Other(u16),
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+enum RecordedLocation {
+    Standard,
+    Left,
+    Right,
+    Numpad,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+enum RecordedKey {
+    Character(String),
+    Named(String),
+}
+
+// The events that aren't input, and keys that can't be replayed, aren't recorded.
+fn record(event: &IcedEvent) -> Option<RecordedEvent> {
+    Some(match event {
+        IcedEvent::Mouse(event) => match event {
+            mouse::Event::CursorEntered => RecordedEvent::CursorEntered,
+            mouse::Event::CursorLeft => RecordedEvent::CursorLeft,
+            mouse::Event::CursorMoved { position } => RecordedEvent::CursorMoved {
+                x: position.x,
+                y: position.y,
+            },
+            mouse::Event::ButtonPressed(button) => RecordedEvent::ButtonPressed((*button).into()),
+            mouse::Event::ButtonReleased(button) => RecordedEvent::ButtonReleased((*button).into()),
+            mouse::Event::WheelScrolled { delta } => match *delta {
+                ScrollDelta::Lines { x, y } => RecordedEvent::WheelLines { x, y },
+                ScrollDelta::Pixels { x, y } => RecordedEvent::WheelPixels { x, y },
+            },
+        },
+        IcedEvent::Keyboard(keyboard::Event::KeyPressed {
+            key,
+            location,
+            modifiers,
+            text,
+        }) => RecordedEvent::KeyPressed {
+            key: RecordedKey::from_key(key)?,
+            location: (*location).into(),
+            modifiers: modifiers.bits(),
+            text: text.as_ref().map(ToString::to_string),
+        },
+        IcedEvent::Keyboard(keyboard::Event::KeyReleased {
+            key,
+            location,
+            modifiers,
+        }) => RecordedEvent::KeyReleased {
+            key: RecordedKey::from_key(key)?,
+            location: (*location).into(),
+            modifiers: modifiers.bits(),
+        },
+        IcedEvent::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+            RecordedEvent::ModifiersChanged(modifiers.bits())
+        }
+        IcedEvent::Touch(event) => {
+            let (touch::Event::FingerPressed { id, position }
+            | touch::Event::FingerMoved { id, position }
+            | touch::Event::FingerLifted { id, position }
+            | touch::Event::FingerLost { id, position }) = event;
+            let (id, x, y) = (id.0, position.x, position.y);
+            match event {
+                touch::Event::FingerPressed { .. } => RecordedEvent::FingerPressed { id, x, y },
+                touch::Event::FingerMoved { .. } => RecordedEvent::FingerMoved { id, x, y },
+                touch::Event::FingerLifted { .. } => RecordedEvent::FingerLifted { id, x, y },
+                touch::Event::FingerLost { .. } => RecordedEvent::FingerLost { id, x, y },
+            }
+        }
+        IcedEvent::Window(..) => return None,
+    })
+}
+
+impl RecordedEvent {
+    // The recorded event, unless it names a key unknown to this version.
+    fn to_event(&self) -> Option<IcedEvent> {
+        let finger = |id: u64, x: f32, y: f32| (Finger(id), Point::new(x, y));
+        Some(match self.clone() {
+            Self::CursorEntered => IcedEvent::Mouse(mouse::Event::CursorEntered),
+            Self::CursorLeft => IcedEvent::Mouse(mouse::Event::CursorLeft),
+            Self::CursorMoved { x, y } => IcedEvent::Mouse(mouse::Event::CursorMoved {
+                position: Point::new(x, y),
+            }),
+            Self::ButtonPressed(button) => {
+                IcedEvent::Mouse(mouse::Event::ButtonPressed(button.into()))
+            }
+            Self::ButtonReleased(button) => {
+                IcedEvent::Mouse(mouse::Event::ButtonReleased(button.into()))
+            }
+            Self::WheelLines { x, y } => IcedEvent::Mouse(mouse::Event::WheelScrolled {
+                delta: ScrollDelta::Lines { x, y },
+            }),
+            Self::WheelPixels { x, y } => IcedEvent::Mouse(mouse::Event::WheelScrolled {
+                delta: ScrollDelta::Pixels { x, y },
+            }),
+            Self::KeyPressed {
+                key,
+                location,
+                modifiers,
+                text,
+            } => IcedEvent::Keyboard(keyboard::Event::KeyPressed {
+                key: key.to_key()?,
+                location: location.into(),
+                modifiers: Modifiers::from_bits_truncate(modifiers),
+                text: text.map(Into::into),
+            }),
+            Self::KeyReleased {
+                key,
+                location,
+                modifiers,
+            } => IcedEvent::Keyboard(keyboard::Event::KeyReleased {
+                key: key.to_key()?,
+                location: location.into(),
+                modifiers: Modifiers::from_bits_truncate(modifiers),
+            }),
+            Self::ModifiersChanged(modifiers) => IcedEvent::Keyboard(
+                keyboard::Event::ModifiersChanged(Modifiers::from_bits_truncate(modifiers)),
+            ),
+            Self::FingerPressed { id, x, y } => {
+                let (id, position) = finger(id, x, y);
+                IcedEvent::Touch(touch::Event::FingerPressed { id, position })
+            }
+            Self::FingerMoved { id, x, y } => {
+                let (id, position) = finger(id, x, y);
+                IcedEvent::Touch(touch::Event::FingerMoved { id, position })
+            }
+            Self::FingerLifted { id, x, y } => {
+                let (id, position) = finger(id, x, y);
+                IcedEvent::Touch(touch::Event::FingerLifted { id, position })
+            }
+            Self::FingerLost { id, x, y } => {
+                let (id, position) = finger(id, x, y);
+                IcedEvent::Touch(touch::Event::FingerLost { id, position })
+            }
+        })
+    }
+}
+
+impl From<mouse::Button> for RecordedButton {
+    fn from(button: mouse::Button) -> Self {
+        match button {
+            mouse::Button::Left => Self::Left,
+            mouse::Button::Right => Self::Right,
+            mouse::Button::Middle => Self::Middle,
+            mouse::Button::Back => Self::Back,
+            mouse::Button::Forward => Self::Forward,
+            mouse::Button::Other(other) => Self::Other(other),
+        }
+    }
+}
+
+impl From<RecordedButton> for mouse::Button {
+    fn from(button: RecordedButton) -> Self {
+        match button {
+            RecordedButton::Left => Self::Left,
+            RecordedButton::Right => Self::Right,
+            RecordedButton::Middle => Self::Middle,
+            RecordedButton::Back => Self::Back,
+            RecordedButton::Forward => Self::Forward,
+            RecordedButton::Other(other) => Self::Other(other),
+        }
+    }
+}
+
+impl From<Location> for RecordedLocation {
+    fn from(location: Location) -> Self {
+        match location {
+            Location::Standard => Self::Standard,
+            Location::Left => Self::Left,
+            Location::Right => Self::Right,
+            Location::Numpad => Self::Numpad,
+        }
+    }
+}
+
+impl From<RecordedLocation> for Location {
+    fn from(location: RecordedLocation) -> Self {
+        match location {
+            RecordedLocation::Standard => Self::Standard,
+            RecordedLocation::Left => Self::Left,
+            RecordedLocation::Right => Self::Right,
+            RecordedLocation::Numpad => Self::Numpad,
+        }
+    }
+}
+
+impl RecordedKey {
+    fn from_key(key: &Key) -> Option<Self> {
+        match key {
+            Key::Character(c) => Some(Self::Character(c.to_string())),
+            Key::Named(named) => named_key_name(*named).map(|name| Self::Named(name.into())),
+            Key::Unidentified => None,
+        }
+    }
+
+    fn to_key(&self) -> Option<Key> {
+        match self {
+            Self::Character(c) => Some(Key::Character(c.as_str().into())),
+            Self::Named(name) => named_key(name).map(Key::Named),
+        }
+    }
+}
+
+// The named keys that are recorded, by the name they are saved with.
+macro_rules! named_keys {
+    ($($name:ident),* $(,)?) => {
+        fn named_key_name(named: Named) -> Option<&'static str> {
+            match named {
+                $(Named::$name => Some(stringify!($name)),)*
+                _ => None,
+            }
+        }
+
+        fn named_key(name: &str) -> Option<Named> {
+            match name {
+                $(stringify!($name) => Some(Named::$name),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+named_keys!(
+    Alt, AltGraph, CapsLock, Control, Shift, Super, Enter, Tab, Space, ArrowDown, ArrowLeft,
+    ArrowRight, ArrowUp, End, Home, PageDown, PageUp, Backspace, Delete, Insert, Escape, Copy, Cut,
+    Paste, Undo, Redo, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+);
@@ -0,0 +1,168 @@
+use iced_core::font::Weight;
+use iced_core::text::Shaping;
+use iced_core::{Alignment, Color, Element, Font, Pixels};
+use iced_widget::{Column, Row, Text};
+
+use crate::{Renderer, Theme};
+
+/// A run of text sharing the same style.
+#[derive(Clone, Debug, Default)]
+pub struct Span {
+    content: String,
+    color: Option<Color>,
+    size: Option<Pixels>,
+    font: Option<Font>,
+    weight: Option<Weight>,
+}
+
+impl Span {
+    /// Create a span with the given content and the default style of its [`RichText`].
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Create a span showing a single glyph of an icon font.
+    pub fn icon(glyph: char, font: Font) -> Self {
+        Self::new(glyph).font(font)
+    }
+
+    /// Set the color of the span.
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set the text size of the span.
+    pub fn size(mut self, size: impl Into<Pixels>) -> Self {
+        self.size = Some(size.into());
+        self
+    }
+
+    /// Set the font of the span.
+    pub fn font(mut self, font: Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// Set the weight of the span's font.
+    pub fn weight(mut self, weight: Weight) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    /// Make the span bold.
+    pub fn bold(self) -> Self {
+        self.weight(Weight::Bold)
+    }
+}
+
+impl From<&str> for Span {
+    fn from(content: &str) -> Self {
+        Self::new(content)
+    }
+}
+
+impl From<String> for Span {
+    fn from(content: String) -> Self {
+        Self::new(content)
+    }
+}
+
+/// A paragraph made of [`Span`]s with their own styles, such as a chat line or quest text.
+///
+/// Line breaks (`\n`) inside spans start new lines. Spans are laid out next to each other
+/// and are not wrapped, so long paragraphs should be split into several lines.
+///
+/// ```ignore
+/// let line = RichText::new()
+///     .push(Span::new("Alice").color(Color::from_rgb(1.0, 0.5, 0.0)).bold())
+///     .push(": have you seen the ")
+///     .push(Span::icon('\u{e800}', ICON_FONT))
+///     .push(" key?");
+/// ctx.display(line);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RichText {
+    spans: Vec<Span>,
+    size: Option<Pixels>,
+    font: Option<Font>,
+    line_spacing: f32,
+}
+
+impl RichText {
+    /// Create an empty paragraph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a span to the paragraph.
+    pub fn push(mut self, span: impl Into<Span>) -> Self {
+        self.spans.push(span.into());
+        self
+    }
+
+    /// Set the default text size of the spans.
+    pub fn size(mut self, size: impl Into<Pixels>) -> Self {
+        self.size = Some(size.into());
+        self
+    }
+
+    /// Set the default font of the spans.
+    pub fn font(mut self, font: Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// Set the vertical space between lines.
+    pub fn line_spacing(mut self, spacing: f32) -> Self {
+        self.line_spacing = spacing;
+        self
+    }
+
+    fn text<'a>(&self, span: &Span, content: &str) -> Text<'a, Theme, Renderer> {
+        let mut text = Text::new(content.to_owned()).shaping(Shaping::Advanced);
+        if let Some(size) = span.size.or(self.size) {
+            text = text.size(size);
+        }
+        let font = span.font.or(self.font);
+        if let Some(weight) = span.weight {
+            text = text.font(Font {
+                weight,
+                ..font.unwrap_or_default()
+            });
+        } else if let Some(font) = font {
+            text = text.font(font);
+        }
+        if let Some(color) = span.color {
+            text = text.style(color);
+        }
+        text
+    }
+}
+
+impl<'a, Message: 'a> From<RichText> for Element<'a, Message, Theme, Renderer> {
+    fn from(rich_text: RichText) -> Self {
+        let mut lines = vec![Row::new().align_items(Alignment::End)];
+        for span in &rich_text.spans {
+            for (i, content) in span.content.split('\n').enumerate() {
+                if i > 0 {
+                    lines.push(Row::new().align_items(Alignment::End));
+                }
+                if !content.is_empty() {
+                    let line = lines.pop().unwrap();
+                    lines.push(line.push(rich_text.text(span, content)));
+                }
+            }
+        }
+        lines
+            .into_iter()
+            .fold(
+                Column::new().spacing(rich_text.line_spacing),
+                |column, line| column.push(line),
+            )
+            .into()
+    }
+}
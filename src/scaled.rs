@@ -0,0 +1,313 @@
+// Rendering interfaces at a fixed scale to an offscreen texture, which is then stretched over
+// the window. See `IcedSettings::fixed_scale` and `IcedSettings::opacity`.
+
+use std::borrow::Cow;
+
+use iced_core::Size;
+use iced_wgpu::wgpu;
+use iced_wgpu::wgpu::TextureFormat;
+use iced_widget::graphics::Viewport;
+
+// The viewport interfaces are rasterized with: the same logical size as `viewport`, at `scale`.
+//
+// The scale is lowered for the texture to fit in `max_dimension` (the limit of the device,
+// only 2048 pixels on WebGL2), which only costs sharpness since it is stretched anyway.
+pub fn viewport(viewport: &Viewport, scale: f64, max_dimension: u32) -> Viewport {
+    let logical = viewport.logical_size();
+    let largest = f64::from(logical.width.max(logical.height));
+    let scale = if largest * scale > f64::from(max_dimension) {
+        f64::from(max_dimension) / largest
+    } else {
+        scale
+    };
+    Viewport::with_physical_size(
+        Size::new(
+            ((f64::from(logical.width) * scale).ceil() as u32).min(max_dimension),
+            ((f64::from(logical.height) * scale).ceil() as u32).min(max_dimension),
+        ),
+        scale,
+    )
+}
+
+pub struct ScaledTarget {
+    format: TextureFormat,
+    // The format of the texture, the one of the target unless pixels are uploaded to it.
+    source: TextureFormat,
+    size: Size<u32>,
+    pipeline: wgpu::RenderPipeline,
+    layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    // The opacity the texture is blitted with, padded to 16 bytes.
+    opacity: wgpu::Buffer,
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    bind_group: wgpu::BindGroup,
+}
+
+impl ScaledTarget {
+    // Get the target in `slot`, (re)creating what is needed for `format` and `size`.
+    pub fn prepare<'a>(
+        slot: &'a mut Option<Self>,
+        device: &wgpu::Device,
+        format: TextureFormat,
+        size: Size<u32>,
+    ) -> &'a Self {
+        Self::prepare_with_source(slot, device, format, format, size)
+    }
+
+    // Get the target in `slot` for RGBA8 pixels uploaded with `upload`, like `prepare`.
+    #[cfg(feature = "tiny_skia")]
+    pub fn prepare_upload<'a>(
+        slot: &'a mut Option<Self>,
+        device: &wgpu::Device,
+        format: TextureFormat,
+        size: Size<u32>,
+    ) -> &'a Self {
+        // The pixels hold sRGB values, which sRGB targets expect to be decoded first.
+        let source = if format.is_srgb() {
+            TextureFormat::Rgba8UnormSrgb
+        } else {
+            TextureFormat::Rgba8Unorm
+        };
+        Self::prepare_with_source(slot, device, format, source, size)
+    }
+
+    fn prepare_with_source<'a>(
+        slot: &'a mut Option<Self>,
+        device: &wgpu::Device,
+        format: TextureFormat,
+        source: TextureFormat,
+        size: Size<u32>,
+    ) -> &'a Self {
+        if slot
+            .as_ref()
+            .is_some_and(|target| (target.format, target.source) != (format, source))
+        {
+            *slot = None;
+        }
+        let target = slot.get_or_insert_with(|| Self::new(device, format, source, size));
+        if target.size != size {
+            target.resize(device, size);
+        }
+        target
+    }
+
+    fn new(
+        device: &wgpu::Device,
+        format: TextureFormat,
+        source: TextureFormat,
+        size: Size<u32>,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("bevy_iced scaled shader"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("scaled.wgsl"))),
+        });
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("bevy_iced scaled bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("bevy_iced scaled pipeline layout"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("bevy_iced scaled pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    // Iced blends over the transparent texture, which leaves it premultiplied.
+                    blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("bevy_iced scaled sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let opacity = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("bevy_iced scaled opacity"),
+            size: 16,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let (texture, view, bind_group) =
+            Self::texture(device, &layout, &sampler, &opacity, source, size);
+
+        Self {
+            format,
+            source,
+            size,
+            pipeline,
+            layout,
+            sampler,
+            opacity,
+            texture,
+            view,
+            bind_group,
+        }
+    }
+
+    fn resize(&mut self, device: &wgpu::Device, size: Size<u32>) {
+        (self.texture, self.view, self.bind_group) = Self::texture(
+            device,
+            &self.layout,
+            &self.sampler,
+            &self.opacity,
+            self.source,
+            size,
+        );
+        self.size = size;
+    }
+
+    fn texture(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        sampler: &wgpu::Sampler,
+        opacity: &wgpu::Buffer,
+        format: TextureFormat,
+        size: Size<u32>,
+    ) -> (wgpu::Texture, wgpu::TextureView, wgpu::BindGroup) {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("bevy_iced scaled texture"),
+            size: wgpu::Extent3d {
+                width: size.width.max(1),
+                height: size.height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("bevy_iced scaled bind group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: opacity.as_entire_binding(),
+                },
+            ],
+        });
+        (texture, view, bind_group)
+    }
+
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    // Replace the contents of the texture with premultiplied RGBA8 `pixels`, row by row.
+    #[cfg(feature = "tiny_skia")]
+    pub fn upload(&self, queue: &wgpu::Queue, pixels: &[u8]) {
+        let size = wgpu::Extent3d {
+            width: self.size.width.max(1),
+            height: self.size.height.max(1),
+            depth_or_array_layers: 1,
+        };
+        queue.write_texture(
+            self.texture.as_image_copy(),
+            pixels,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * size.width),
+                rows_per_image: None,
+            },
+            size,
+        );
+    }
+
+    // Stretch the texture over `target` with `opacity`, clearing it to transparent first if
+    // `clear` is set.
+    pub fn blit(
+        &self,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+        clear: bool,
+        opacity: f32,
+    ) {
+        let opacity: Vec<u8> = [opacity, 0.0, 0.0, 0.0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        queue.write_buffer(&self.opacity, 0, &opacity);
+        let load = if clear {
+            wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT)
+        } else {
+            wgpu::LoadOp::Load
+        };
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("bevy_iced scaled pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &self.bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+}
@@ -0,0 +1,235 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_asset::Assets;
+use bevy_ecs::prelude::{Event, EventReader, EventWriter, IntoSystemConfigs, Local};
+use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_render::render_asset::RenderAssetUsages;
+use bevy_render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy_render::renderer::{render_system, RenderDevice, RenderQueue};
+use bevy_render::texture::Image;
+use bevy_render::{Render, RenderApp, RenderSet};
+use iced_wgpu::wgpu;
+
+pub use bevy_asset::Handle;
+
+use crate::compat::{self, Primitive};
+use crate::render::PresentedFrame;
+use crate::ViewportResource;
+
+const FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+
+/// Send this event to capture the interfaces of the next rendered frame, without the rest
+/// of the scene.
+///
+/// The capture is rendered separately on a transparent background, and delivered a few
+/// frames later as an [`IcedScreenshotReady`] event. Requires the [`IcedScreenshotPlugin`].
+#[derive(Event, Clone, Copy, Debug, Default)]
+pub struct IcedScreenshotRequest;
+
+/// A capture of the interfaces, added to [`Assets<Image>`] in response to an
+/// [`IcedScreenshotRequest`].
+///
+/// The image has the physical size of the window, and its colors are premultiplied by
+/// their alpha.
+#[derive(Event, Clone, Debug)]
+pub struct IcedScreenshotReady(pub Handle<Image>);
+
+// A capture read back from the GPU, with the number of requests it answers.
+struct Capture {
+    requests: usize,
+    size: Extent3d,
+    data: Vec<u8>,
+}
+
+// Shared between the main and render worlds.
+#[derive(Resource, Clone)]
+struct ScreenshotChannel {
+    requests: Arc<AtomicUsize>,
+    in_flight: Arc<AtomicUsize>,
+    sender: Sender<Capture>,
+    receiver: Arc<Mutex<Receiver<Capture>>>,
+}
+
+/// Adds support for [`IcedScreenshotRequest`].
+pub struct IcedScreenshotPlugin;
+
+impl Plugin for IcedScreenshotPlugin {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = mpsc::channel();
+        app.insert_resource(ScreenshotChannel {
+            requests: Arc::default(),
+            in_flight: Arc::default(),
+            sender,
+            receiver: Arc::new(Mutex::new(receiver)),
+        })
+        .add_event::<IcedScreenshotRequest>()
+        .add_event::<IcedScreenshotReady>()
+        .add_systems(PreUpdate, (receive_screenshots, request_screenshots));
+    }
+
+    fn finish(&self, app: &mut App) {
+        let channel = app.world.resource::<ScreenshotChannel>().clone();
+        app.sub_app_mut(RenderApp)
+            .insert_resource(channel)
+            .add_systems(
+                Render,
+                capture_screenshots
+                    .in_set(RenderSet::Render)
+                    .after(render_system),
+            );
+    }
+}
+
+fn request_screenshots(
+    mut requests: EventReader<IcedScreenshotRequest>,
+    channel: Res<ScreenshotChannel>,
+) {
+    let count = requests.read().count();
+    if count > 0 {
+        channel.requests.fetch_add(count, Ordering::Relaxed);
+    }
+}
+
+fn receive_screenshots(
+    channel: Res<ScreenshotChannel>,
+    mut images: ResMut<Assets<Image>>,
+    mut ready: EventWriter<IcedScreenshotReady>,
+) {
+    let receiver = channel
+        .receiver
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    for capture in receiver.try_iter() {
+        let handle = images.add(Image::new(
+            capture.size,
+            TextureDimension::D2,
+            capture.data,
+            FORMAT,
+            RenderAssetUsages::default(),
+        ));
+        for _ in 0..capture.requests {
+            ready.send(IcedScreenshotReady(handle.clone()));
+        }
+    }
+}
+
+// Present the interfaces of the frame to a texture of their own and read it back.
+fn capture_screenshots(
+    channel: Res<ScreenshotChannel>,
+    presented: Res<PresentedFrame>,
+    viewport: Res<ViewportResource>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    // A backend can only present once per frame, so captures have their own.
+    mut backend: Local<Option<iced_wgpu::Backend>>,
+) {
+    let device = render_device.wgpu_device();
+    // Mapping the buffers of previous captures requires polling the device.
+    if channel.in_flight.load(Ordering::Relaxed) > 0 {
+        device.poll(wgpu::Maintain::Poll);
+    }
+    let requests = channel.requests.swap(0, Ordering::Relaxed);
+    if requests == 0 {
+        return;
+    }
+    let physical_size = viewport.physical_size();
+    if physical_size.width == 0 || physical_size.height == 0 {
+        // Try again once the window has a size.
+        channel.requests.fetch_add(requests, Ordering::Relaxed);
+        return;
+    }
+    let size = Extent3d {
+        width: physical_size.width,
+        height: physical_size.height,
+        depth_or_array_layers: 1,
+    };
+
+    let backend = backend.get_or_insert_with(|| {
+        compat::new_backend(device, render_queue.as_ref(), presented.settings, FORMAT)
+    });
+    let primitives: Vec<Primitive> = presented
+        .below_ui
+        .iter()
+        .chain(&presented.above_ui)
+        .cloned()
+        .collect();
+
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("iced_screenshot"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: TextureDimension::D2,
+        format: FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("iced_screenshot"),
+    });
+    compat::present(
+        backend,
+        device,
+        render_queue.as_ref(),
+        &mut encoder,
+        Some(iced_core::Color::TRANSPARENT),
+        FORMAT,
+        &view,
+        &primitives,
+        &viewport,
+        &[],
+    );
+
+    // Rows of a copy to a buffer must be aligned.
+    let row_size = size.width as usize * 4;
+    let padded_row_size =
+        wgpu::util::align_to(row_size, wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize);
+    let buffer = Arc::new(device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("iced_screenshot"),
+        size: (padded_row_size * size.height as usize) as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    }));
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row_size as u32),
+                rows_per_image: None,
+            },
+        },
+        size,
+    );
+    render_queue.submit([encoder.finish()]);
+
+    channel.in_flight.fetch_add(1, Ordering::Relaxed);
+    let mapped = buffer.clone();
+    let channel = channel.clone();
+    buffer
+        .slice(..)
+        .map_async(wgpu::MapMode::Read, move |result| {
+            channel.in_flight.fetch_sub(1, Ordering::Relaxed);
+            if result.is_err() {
+                return;
+            }
+            let data = mapped
+                .slice(..)
+                .get_mapped_range()
+                .chunks(padded_row_size)
+                .flat_map(|row| &row[..row_size])
+                .copied()
+                .collect();
+            mapped.unmap();
+            let _ = channel.sender.send(Capture {
+                requests,
+                size,
+                data,
+            });
+        });
+}
@@ -0,0 +1,106 @@
+use bevy_render::renderer::RenderAdapterInfo;
+use iced_core::Size;
+use iced_wgpu::wgpu;
+use iced_wgpu::wgpu::TextureFormat;
+use iced_widget::graphics::Viewport;
+
+use crate::compat::{self, Primitive};
+use crate::scaled::ScaledTarget;
+
+/// When interfaces are rasterized on the CPU with `tiny-skia`, see
+/// [`IcedPlugin::software`](crate::IcedPlugin::software).
+///
+/// The software renderer draws interfaces into a buffer, uploaded to a texture stretched
+/// over the window every frame. It doesn't draw meshes and custom shader primitives (e.g.
+/// the geometry of canvases and `gpu_image` textures), and it is only used for the primary
+/// window: tool windows and other targets are always rendered on the GPU.
+///
+/// This can't help when Bevy itself fails to create a rendering device.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SoftwareRendering {
+    /// Always render interfaces on the GPU.
+    Never,
+    /// Render interfaces in software when the adapter is emulated on the CPU (e.g.
+    /// llvmpipe or WARP), where the GPU renderer is slower and its text can be corrupted.
+    #[default]
+    WhenEmulated,
+    /// Always render interfaces in software.
+    Always,
+}
+
+impl SoftwareRendering {
+    pub(crate) fn is_enabled(self, adapter: Option<&RenderAdapterInfo>) -> bool {
+        match self {
+            Self::Never => false,
+            Self::WhenEmulated => {
+                adapter.is_some_and(|adapter| adapter.device_type == wgpu::DeviceType::Cpu)
+            }
+            Self::Always => true,
+        }
+    }
+}
+
+// Rasterizes interfaces with `tiny-skia`, and presents the result with a `ScaledTarget`.
+pub(crate) struct SoftwareRenderer {
+    backend: iced_tiny_skia::Backend,
+    pixels: Option<(tiny_skia::Pixmap, tiny_skia::Mask)>,
+    target: Option<ScaledTarget>,
+}
+
+impl Default for SoftwareRenderer {
+    fn default() -> Self {
+        Self {
+            backend: compat::new_software_backend(),
+            pixels: None,
+            target: None,
+        }
+    }
+}
+
+impl SoftwareRenderer {
+    // Like `compat::present`, with the opacity of `IcedSettings::opacity`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn present(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        clear: bool,
+        format: TextureFormat,
+        view: &wgpu::TextureView,
+        primitives: &[Primitive],
+        viewport: &Viewport,
+        overlay: &[String],
+        opacity: f32,
+    ) {
+        let Size { width, height } = viewport.physical_size();
+        if self
+            .pixels
+            .as_ref()
+            .is_none_or(|(pixmap, _)| (pixmap.width(), pixmap.height()) != (width, height))
+        {
+            self.pixels =
+                tiny_skia::Pixmap::new(width, height).zip(tiny_skia::Mask::new(width, height));
+        }
+        let Some((pixmap, mask)) = &mut self.pixels else {
+            return;
+        };
+        let primitives: Vec<_> = primitives.iter().filter_map(compat::to_software).collect();
+        compat::draw_software(
+            &mut self.backend,
+            &mut pixmap.as_mut(),
+            mask,
+            &primitives,
+            viewport,
+            overlay,
+        );
+        let target = ScaledTarget::prepare_upload(
+            &mut self.target,
+            device,
+            format,
+            viewport.physical_size(),
+        );
+        target.upload(queue, pixmap.data());
+        target.blit(queue, encoder, view, clear, opacity);
+    }
+}
@@ -0,0 +1,58 @@
+use bevy_ecs::system::Resource;
+
+/// The fonts installed on the system.
+///
+/// Use [`families`](Self::families) to list the available font families, and pass the
+/// result of [`font_data`](Self::font_data) to
+/// [`IcedContext::load_font`](crate::IcedContext::load_font) to use one of them.
+/// Enumerating the fonts can take a while, so this is best done once and kept around as a
+/// resource.
+#[derive(Resource)]
+pub struct SystemFonts {
+    db: fontdb::Database,
+}
+
+impl SystemFonts {
+    /// Enumerate the fonts installed on the system.
+    pub fn load() -> Self {
+        let mut db = fontdb::Database::new();
+        db.load_system_fonts();
+        Self { db }
+    }
+
+    /// The names of the available font families, sorted alphabetically.
+    pub fn families(&self) -> Vec<String> {
+        let mut families: Vec<String> = self
+            .db
+            .faces()
+            .flat_map(|face| face.families.iter().map(|(name, _)| name.clone()))
+            .collect();
+        families.sort();
+        families.dedup();
+        families
+    }
+
+    /// The contents of the regular face of the given family, if it is installed.
+    pub fn font_data(&self, family: &str) -> Option<Vec<u8>> {
+        let id = self.db.query(&fontdb::Query {
+            families: &[fontdb::Family::Name(family)],
+            ..Default::default()
+        })?;
+        self.db.with_face_data(id, |data, _| data.to_vec())
+    }
+
+    /// The contents of the default sans-serif font of the system.
+    pub fn default_font_data(&self) -> Option<Vec<u8>> {
+        let id = self.db.query(&fontdb::Query {
+            families: &[fontdb::Family::SansSerif],
+            ..Default::default()
+        })?;
+        self.db.with_face_data(id, |data, _| data.to_vec())
+    }
+}
+
+impl Default for SystemFonts {
+    fn default() -> Self {
+        Self::load()
+    }
+}
@@ -1,8 +1,13 @@
-use crate::conversions;
+use crate::middleware::IcedEventFilters;
+use crate::render::{ViewportBounds, ViewportResource};
+use crate::{convert, utils, IcedCacheRef, IcedSettings, IcedTextInputFocused, IcedWindow};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
-    prelude::EventReader,
-    system::{Res, ResMut, Resource, SystemParam},
+    change_detection::{DetectChanges, DetectChangesMut},
+    event::{Events, ManualEventReader},
+    prelude::{Entity, EventReader, EventWriter, Query, With},
+    schedule::SystemSet,
+    system::{Local, Res, ResMut, Resource, SystemParam},
 };
 use bevy_input::keyboard::KeyCode;
 use bevy_input::touch::TouchInput;
@@ -11,23 +16,941 @@ use bevy_input::{
     mouse::{MouseButtonInput, MouseWheel},
     ButtonInput, ButtonState,
 };
-use bevy_window::{CursorEntered, CursorLeft, CursorMoved, ReceivedCharacter};
+use bevy_math::Vec2;
+use bevy_time::Time;
+use bevy_utils::HashMap;
+use bevy_window::{
+    ApplicationLifetime, CursorEntered, CursorIcon, CursorLeft, CursorMoved, FileDragAndDrop,
+    PrimaryWindow, ReceivedCharacter, RequestRedraw, Window, WindowFocused, WindowMoved,
+    WindowResized,
+};
+use iced_core::time::Instant;
 use iced_core::SmolStr;
-use iced_core::{keyboard, mouse, Event as IcedEvent, Point};
+use iced_core::{
+    keyboard, mouse, touch, window, Event as IcedEvent, Point, Rectangle, Size, Vector,
+};
+use iced_runtime::user_interface;
+use std::collections::VecDeque;
+use std::time::Duration;
 
+// The systems changing the input after `process_input`, before the event filters and the
+// systems reading it.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum InputSet {
+    // The systems adding input of their own, like cursor sources.
+    Sources,
+    // The recording or replay of the input (see `IcedReplayPlugin`), which replaces the
+    // input of the sources too.
+    Replay,
+}
+
+/// The Iced events collected from Bevy's input this frame.
+///
+/// The positions of mouse and touch events are in logical pixels of the viewport, like
+/// the ones of [`IcedCursorSource`](crate::cursor_source::IcedCursorSource)s.
+///
+/// Interfaces displayed this frame process these events in call order, and the events
+/// captured by one of them don't reach the next ones. Events pushed here after
+/// input processing (or with [`IcedContext::inject_event`](crate::IcedContext::inject_event))
+/// go through the same path as real input, which is useful for tests and automation.
+///
+/// The input of [`IcedWindow`](crate::IcedWindow)s is kept apart, for the interfaces they
+/// display.
 #[derive(Resource, Deref, DerefMut, Default)]
-pub struct IcedEventQueue(Vec<iced_core::Event>);
+pub struct IcedEventQueue {
+    #[deref]
+    events: Vec<iced_core::Event>,
+    windows: HashMap<Entity, Vec<iced_core::Event>>,
+    // The events captured by interfaces this frame, in every queue.
+    captured: Vec<iced_core::Event>,
+}
+
+impl IcedEventQueue {
+    // The queue of the input sent to `window`: its own for a tool window, the main one
+    // otherwise.
+    fn window_mut(
+        &mut self,
+        window: Entity,
+        tool_windows: &Query<Entity, With<IcedWindow>>,
+    ) -> &mut Vec<iced_core::Event> {
+        if tool_windows.contains(window) {
+            self.windows.entry(window).or_default()
+        } else {
+            &mut self.events
+        }
+    }
+
+    // The input of a tool window, which only the interfaces it displays process.
+    pub(crate) fn window(&mut self, window: Entity) -> &mut Vec<iced_core::Event> {
+        self.windows.entry(window).or_default()
+    }
+
+    // The main queue, then the ones of the tool windows and panels.
+    pub(crate) fn queues_mut(&mut self) -> impl Iterator<Item = &mut Vec<iced_core::Event>> {
+        std::iter::once(&mut self.events).chain(self.windows.values_mut())
+    }
+
+    // Remove the events captured by an interface displayed in a tool window.
+    pub(crate) fn consume_window(&mut self, window: Entity, statuses: &[iced_core::event::Status]) {
+        let mut statuses = statuses.iter();
+        let queue = self.windows.entry(window).or_default();
+        let captured = &mut self.captured;
+        queue.retain(|event| {
+            let ignored = statuses.next() == Some(&iced_core::event::Status::Ignored);
+            if !ignored {
+                captured.push(event.clone());
+            }
+            ignored
+        });
+    }
+
+    // Remove the events captured by an interface, so that they don't reach the interfaces
+    // displayed after it, and leave the others in `ignored`, but the redraw tick.
+    pub(crate) fn consume(
+        &mut self,
+        statuses: &[iced_core::event::Status],
+        ignored: &mut IcedIgnoredEvents,
+    ) {
+        let mut statuses = statuses.iter();
+        let captured = &mut self.captured;
+        self.events.retain(|event| {
+            let ignored = statuses.next() == Some(&iced_core::event::Status::Ignored);
+            if !ignored {
+                captured.push(event.clone());
+            }
+            ignored
+        });
+        ignored.clear();
+        ignored.extend(
+            self.events
+                .iter()
+                .filter(|event| !is_redraw_tick(event))
+                .cloned(),
+        );
+    }
+
+    // The events captured by interfaces so far this frame.
+    pub(crate) fn captured(&self) -> &[iced_core::Event] {
+        &self.captured
+    }
+}
+
+/// How the input of a frame is trimmed before interfaces process it, see
+/// [`IcedSettings::event_queue`].
+///
+/// After a hitch, the input of many frames can be delivered at once, and replaying all of
+/// it in a single update makes sliders and scrollables jump.
+///
+/// Releases of buttons, keys and fingers, changes of modifiers and focus, and the cursor
+/// entering or leaving the window are never dropped, so that no drag or key is left stuck.
+/// By default, every event is kept.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct EventQueuePolicy {
+    /// Whether consecutive cursor moves are merged into the last one.
+    pub coalesce_cursor_moves: bool,
+    /// The maximum number of events processed in a frame, beyond which the oldest ones are
+    /// dropped. Setting this to `None` keeps every event.
+    pub max_len: Option<usize>,
+    /// The number of frames after which the input left to a later update (see
+    /// [`IcedSettings::update_frequency`]) is dropped. Setting this to `None` keeps it
+    /// until the update.
+    pub max_age: Option<u32>,
+}
+
+impl IcedSettings {
+    // Trim the input of a frame, before the event filters.
+    pub(crate) fn trim_input(&self, queue: &mut Vec<IcedEvent>) {
+        self.input.apply(queue);
+        self.event_queue.apply(queue);
+    }
+}
+
+impl EventQueuePolicy {
+    fn apply(&self, queue: &mut Vec<IcedEvent>) {
+        let is_cursor_move =
+            |event: &IcedEvent| matches!(event, IcedEvent::Mouse(mouse::Event::CursorMoved { .. }));
+        if self.coalesce_cursor_moves {
+            let mut events = std::mem::take(queue).into_iter().peekable();
+            while let Some(event) = events.next() {
+                if !(is_cursor_move(&event) && events.peek().is_some_and(is_cursor_move)) {
+                    queue.push(event);
+                }
+            }
+        }
+        if let Some(max_len) = self.max_len {
+            let mut excess = queue.len().saturating_sub(max_len);
+            queue.retain(|event| {
+                let drop = excess > 0 && !is_essential(event);
+                excess -= usize::from(drop);
+                !drop
+            });
+        }
+    }
+
+    // Drop the input left to a later update that is older than `max_age` frames.
+    fn drop_stale(&self, deferred: &mut Vec<(u64, IcedEvent)>, frame: u64) {
+        if let Some(max_age) = self.max_age {
+            deferred.retain(|(sent, event)| {
+                frame.saturating_sub(*sent) <= u64::from(max_age) || is_essential(event)
+            });
+        }
+    }
+}
+
+// Whether dropping `event` would leave widgets in a state that no later event fixes, like a
+// button still pressed, or the redraw tick that animations need.
+fn is_essential(event: &IcedEvent) -> bool {
+    matches!(
+        event,
+        IcedEvent::Mouse(
+            mouse::Event::ButtonReleased(_)
+                | mouse::Event::CursorEntered
+                | mouse::Event::CursorLeft
+        ) | IcedEvent::Keyboard(
+            keyboard::Event::KeyReleased { .. } | keyboard::Event::ModifiersChanged(_)
+        ) | IcedEvent::Touch(touch::Event::FingerLifted { .. } | touch::Event::FingerLost { .. })
+            | IcedEvent::Window(
+                _,
+                window::Event::Focused
+                    | window::Event::Unfocused
+                    | window::Event::RedrawRequested(_)
+            )
+    )
+}
+
+/// The categories of input forwarded to interfaces, see [`IcedSettings::input`].
+///
+/// Display-only interfaces, like a HUD, can turn everything off: they then never process
+/// input nor capture it, and the cursor doesn't hover them. Events injected with
+/// [`IcedContext::inject_event`](crate::IcedContext::inject_event) are always forwarded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct InputForwarding {
+    /// Cursor moves and mouse buttons. When this is off, interfaces don't see the cursor.
+    pub mouse: bool,
+    /// Mouse wheel scrolling.
+    pub wheel: bool,
+    /// Key presses and text.
+    pub keyboard: bool,
+    /// Touches. When this is off, touches don't act as a cursor.
+    pub touch: bool,
+}
+
+impl Default for InputForwarding {
+    fn default() -> Self {
+        Self {
+            mouse: true,
+            wheel: true,
+            keyboard: true,
+            touch: true,
+        }
+    }
+}
+
+impl InputForwarding {
+    /// Forward no input at all, for display-only interfaces.
+    pub fn none() -> Self {
+        Self {
+            mouse: false,
+            wheel: false,
+            keyboard: false,
+            touch: false,
+        }
+    }
+
+    fn apply(&self, queue: &mut Vec<IcedEvent>) {
+        if *self == Self::default() {
+            return;
+        }
+        queue.retain(|event| match event {
+            IcedEvent::Mouse(mouse::Event::WheelScrolled { .. }) => self.wheel,
+            IcedEvent::Mouse(_) => self.mouse,
+            IcedEvent::Keyboard(_) => self.keyboard,
+            IcedEvent::Touch(_) => self.touch,
+            IcedEvent::Window(..) => true,
+        });
+    }
+}
+
+/// How often interfaces are updated, see [`IcedSettings::update_frequency`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub enum IcedUpdateFrequency {
+    /// Interfaces are updated on every frame that needs it.
+    #[default]
+    EveryFrame,
+    /// Interfaces are updated at most once every given number of frames.
+    EveryNFrames(u32),
+    /// Interfaces are updated at most once per period of Bevy's clock, e.g. 33 ms for 30 Hz.
+    Fixed(Duration),
+}
+
+// The state of `IcedUpdateFrequency` across frames.
+#[derive(Default)]
+pub struct UpdateThrottle {
+    frames: u32,
+    last_update: Option<Instant>,
+    // Whether an update was needed on a skipped frame.
+    pending: bool,
+    // The number of frames seen, to age the deferred input.
+    frame: u64,
+    // The input of the skipped frames, left to the next update, with the frame it was sent on.
+    deferred: Vec<(u64, IcedEvent)>,
+}
+
+impl IcedUpdateFrequency {
+    fn is_due(self, throttle: &mut UpdateThrottle, now: Option<Instant>) -> bool {
+        match self {
+            Self::EveryFrame => true,
+            Self::EveryNFrames(frames) => {
+                throttle.frames += 1;
+                let due = throttle.frames >= frames.max(1);
+                if due {
+                    throttle.frames = 0;
+                }
+                due
+            }
+            Self::Fixed(period) => match (now, throttle.last_update) {
+                (Some(now), Some(last)) if now < last + period => false,
+                _ => {
+                    throttle.last_update = now;
+                    true
+                }
+            },
+        }
+    }
+}
+
+/// How held keys are repeated to interfaces, see [`IcedSettings::key_repeat`].
+///
+/// The default matches the usual settings of desktop platforms. Bevy doesn't expose the
+/// ones of the OS.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct KeyRepeat {
+    /// The time a key is held before it starts repeating.
+    pub delay: Duration,
+    /// The time between two repeats.
+    pub interval: Duration,
+}
+
+impl Default for KeyRepeat {
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_millis(500),
+            interval: Duration::from_millis(33),
+        }
+    }
+}
+
+// The key held down in a window (`None` for the main queue), repeated by `process_input`.
+struct HeldKey {
+    window: Option<Entity>,
+    key: keyboard::Key,
+    modifiers: keyboard::Modifiers,
+    next_repeat: Instant,
+}
+
+// The state of `IcedSettings::key_repeat` across frames.
+#[derive(Default)]
+pub struct KeyRepeater {
+    held: Option<HeldKey>,
+    // Set when the platform repeats keys by itself, which stops the synthesis.
+    native: bool,
+}
+
+impl KeyRepeater {
+    // Follow the key presses and releases of the queue of `target`.
+    fn track(
+        &mut self,
+        target: Option<Entity>,
+        queue: &[IcedEvent],
+        now: Instant,
+        delay: Duration,
+    ) {
+        for event in queue {
+            match event {
+                // Characters are also sent as text, which isn't a key press.
+                IcedEvent::Keyboard(keyboard::Event::KeyPressed {
+                    key,
+                    modifiers,
+                    text: None,
+                    ..
+                }) => {
+                    let held = self.held.replace(HeldKey {
+                        window: target,
+                        key: key.clone(),
+                        modifiers: *modifiers,
+                        next_repeat: now + delay,
+                    });
+                    self.native |=
+                        held.is_some_and(|held| held.window == target && held.key == *key);
+                }
+                IcedEvent::Keyboard(keyboard::Event::KeyReleased { key, .. })
+                    if self.held.as_ref().is_some_and(|held| held.key == *key) =>
+                {
+                    self.held = None;
+                }
+                IcedEvent::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                    if let Some(held) = self.held.as_mut().filter(|held| held.window == target) {
+                        held.modifiers = *modifiers;
+                    }
+                }
+                // The release of the key goes to the window focused next.
+                IcedEvent::Window(_, window::Event::Unfocused)
+                    if self.held.as_ref().is_some_and(|held| held.window == target) =>
+                {
+                    self.held = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // The repeats of the held key due by `now`, as they would be sent by the platform.
+    fn repeat(
+        &mut self,
+        now: Instant,
+        interval: Duration,
+    ) -> Option<(Option<Entity>, Vec<IcedEvent>)> {
+        if self.native {
+            return None;
+        }
+        let held = self.held.as_mut()?;
+        // Characters insert their text, unless they are part of a shortcut.
+        let text = match &held.key {
+            keyboard::Key::Character(text)
+                if !(held.modifiers.control() || held.modifiers.logo()) =>
+            {
+                Some(text.clone())
+            }
+            _ => None,
+        };
+        let mut repeats = Vec::new();
+        while held.next_repeat <= now {
+            repeats.push(IcedEvent::Keyboard(keyboard::Event::KeyPressed {
+                key: held.key.clone(),
+                modifiers: held.modifiers,
+                location: keyboard::Location::Standard,
+                text: text.clone(),
+            }));
+            held.next_repeat += interval.max(Duration::from_millis(1));
+        }
+        Some((held.window, repeats))
+    }
+}
+
+/// How touch drags keep scrolling after the finger is lifted, see
+/// [`IcedSettings::kinetic_scroll`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct KineticScroll {
+    /// How fast scrolling slows down: its velocity is divided by `e` every `1 / decay`
+    /// seconds.
+    pub decay: f32,
+    /// The velocity below which scrolling stops, in logical pixels per second. Drags
+    /// released slower than this don't scroll any further.
+    pub min_velocity: f32,
+    /// The highest velocity scrolling starts at, in logical pixels per second.
+    pub max_velocity: f32,
+}
+
+impl Default for KineticScroll {
+    fn default() -> Self {
+        Self {
+            decay: 3.0,
+            min_velocity: 60.0,
+            max_velocity: 6000.0,
+        }
+    }
+}
+
+// The time over which the velocity of a drag is measured when the finger is lifted.
+const FLING_WINDOW: Duration = Duration::from_millis(100);
+
+// Scrolling going on after a touch drag, where the finger was lifted.
+struct Fling {
+    position: Point,
+    velocity: Vector,
+    last_frame: Instant,
+}
+
+// The state of `IcedSettings::kinetic_scroll` across frames.
+#[derive(Default)]
+pub struct KineticScroller {
+    // The finger dragging over the main window, with its recent positions.
+    drag: Option<(touch::Finger, VecDeque<(Instant, Point)>)>,
+    fling: Option<Fling>,
+}
+
+impl KineticScroller {
+    // Follow the touches of the main queue, starting a fling when a drag is released fast
+    // enough and stopping it on any other press or scroll.
+    fn track(&mut self, queue: &[IcedEvent], now: Instant, settings: &KineticScroll) {
+        for event in queue {
+            match event {
+                IcedEvent::Touch(touch::Event::FingerPressed { id, position }) => {
+                    self.fling = None;
+                    // Pinches and other gestures with several fingers don't fling.
+                    self.drag = match self.drag {
+                        None => Some((*id, VecDeque::from([(now, *position)]))),
+                        Some(_) => None,
+                    };
+                }
+                IcedEvent::Touch(touch::Event::FingerMoved { id, position }) => {
+                    if let Some((_, samples)) =
+                        self.drag.as_mut().filter(|(finger, _)| finger == id)
+                    {
+                        while samples
+                            .front()
+                            .is_some_and(|(at, _)| *at + FLING_WINDOW < now)
+                        {
+                            samples.pop_front();
+                        }
+                        samples.push_back((now, *position));
+                    }
+                }
+                IcedEvent::Touch(touch::Event::FingerLifted { id, position }) => {
+                    let Some((_, samples)) = self.drag.take().filter(|(finger, _)| finger == id)
+                    else {
+                        continue;
+                    };
+                    let Some((at, start)) = samples
+                        .into_iter()
+                        .find(|(at, _)| *at + FLING_WINDOW >= now)
+                        .filter(|(at, _)| *at < now)
+                    else {
+                        continue;
+                    };
+                    let velocity = (*position - start) * (1.0 / (now - at).as_secs_f32());
+                    let speed = velocity.x.hypot(velocity.y);
+                    if speed >= settings.min_velocity {
+                        self.fling = Some(Fling {
+                            position: *position,
+                            velocity: velocity * (settings.max_velocity.min(speed) / speed),
+                            last_frame: now,
+                        });
+                    }
+                }
+                IcedEvent::Touch(touch::Event::FingerLost { .. }) => self.drag = None,
+                IcedEvent::Mouse(
+                    mouse::Event::ButtonPressed(_) | mouse::Event::WheelScrolled { .. },
+                ) => self.fling = None,
+                _ => {}
+            }
+        }
+    }
+
+    // The scrolling of the fling since the last frame, as a wheel event.
+    fn scroll(&mut self, now: Instant, settings: &KineticScroll) -> Option<IcedEvent> {
+        let fling = self.fling.as_mut()?;
+        let elapsed = now
+            .saturating_duration_since(fling.last_frame)
+            .as_secs_f32();
+        fling.last_frame = now;
+        fling.velocity = fling.velocity * (-settings.decay * elapsed).exp();
+        if fling.velocity.x.hypot(fling.velocity.y) < settings.min_velocity {
+            self.fling = None;
+            return None;
+        }
+        // Wheel deltas move the content like the finger does.
+        let Vector { x, y } = fling.velocity * elapsed;
+        Some(IcedEvent::Mouse(mouse::Event::WheelScrolled {
+            delta: mouse::ScrollDelta::Pixels { x, y },
+        }))
+    }
+
+    // Where the finger was lifted, which stays the cursor while the fling scrolls.
+    fn cursor(&self) -> Option<Point> {
+        self.fling.as_ref().map(|fling| fling.position)
+    }
+}
+
+/// The Iced events of this frame that no interface captured.
+///
+/// This lets games forward input to the world only when it wasn't meant for the UI, for
+/// example clicks that should reach world picking. It is reset at the beginning of every
+/// frame and filled as interfaces process the input. The redraw tick sent to interfaces
+/// every frame to drive animations isn't input, and is left out.
+#[derive(Resource, Deref, DerefMut, Default)]
+pub struct IcedIgnoredEvents(Vec<iced_core::Event>);
+
+/// Whether interfaces need to be updated this frame, because of new input or a change of
+/// the window or [`IcedSettings`].
+///
+/// Static interfaces (menus, pause screens...) can skip rebuilding most frames by running
+/// their system with the [`iced_needs_update`] condition: on frames where this is `false`,
+/// the interfaces of message types that aren't displayed are presented as they were last
+/// drawn, alongside the ones that are. On frames where it is `true`, interfaces that aren't
+/// displayed are removed from the screen. Interfaces whose widgets are animating (like the
+/// cursor of a text input), or were invalidated (like the contents of a
+/// [`responsive`](crate::widgets::responsive) widget), are updated again when they ask for
+/// it.
+///
+/// Set this to `true`, in a system running before the interfaces, to force an update when
+/// the state displayed by an interface changes.
+///
+/// With an [`IcedSettings::update_frequency`] other than every frame, this is only `true`
+/// on the frames where interfaces are due for an update.
+#[derive(Resource, Deref, DerefMut, Default, PartialEq, Eq)]
+pub struct IcedNeedsUpdate(pub bool);
+
+// Whether the application is suspended (e.g. in the background on Android), and how many
+// times it was resumed, which recreates the window surface.
+#[derive(Resource, Default)]
+pub struct IcedLifecycle {
+    pub suspended: bool,
+    pub resumed: u32,
+}
+
+pub fn track_lifecycle(
+    mut events: EventReader<ApplicationLifetime>,
+    mut lifecycle: ResMut<IcedLifecycle>,
+) {
+    for event in events.read() {
+        match event {
+            ApplicationLifetime::Suspended => lifecycle.suspended = true,
+            ApplicationLifetime::Resumed => {
+                lifecycle.suspended = false;
+                lifecycle.resumed = lifecycle.resumed.wrapping_add(1);
+            }
+            ApplicationLifetime::Started => {}
+        }
+    }
+}
+
+/// A run condition for systems displaying interfaces, true when [`IcedNeedsUpdate`] is.
+pub fn iced_needs_update(needs_update: Res<IcedNeedsUpdate>) -> bool {
+    **needs_update
+}
+
+/// Whether the cursor was over an interactive part of an interface (a button, a text input,
+/// a slider...) during the last frame.
+///
+/// It is computed from the layout of the interfaces, so it can be used to keep the input of
+/// the world from reacting under them. With the `picking` feature, the
+/// `IcedPickingPlugin` uses it to block `bevy_mod_picking`.
+#[derive(Resource, Deref, Default, PartialEq, Eq)]
+pub struct IcedPointerOverUi(pub bool);
+
+pub fn update_pointer_over_ui(
+    interaction: Res<IcedInteraction>,
+    mut over_ui: ResMut<IcedPointerOverUi>,
+) {
+    over_ui.set_if_neq(IcedPointerOverUi(
+        interaction.current != mouse::Interaction::Idle,
+    ));
+}
+
+// The mouse interaction requested by the interfaces drawn this frame.
+#[derive(Resource, Default)]
+pub struct IcedInteraction {
+    pub current: mouse::Interaction,
+    // The icon last applied to the window cursor, and the one of the application it replaced.
+    applied: Option<(CursorIcon, CursorIcon)>,
+    // The position of the virtual cursor, which replaces the window cursor when it is active.
+    pub virtual_cursor: Option<Point>,
+    // The cursor of an `IcedCursorSource`, which replaces the window cursor when it exists.
+    pub cursor_source: Option<mouse::Cursor>,
+    // Whether an interface asked to be updated again without new input, to rebuild the
+    // widgets it invalidated or to animate them, on the next frame or at a given time.
+    pub update_next_frame: bool,
+    pub update_at: Option<Instant>,
+    // The time of the redraw tick of this frame.
+    pub now: Option<Instant>,
+    // Where the finger of a kinetic scroll was lifted, in place of the touch cursor.
+    pub fling_cursor: Option<Point>,
+}
+
+impl IcedInteraction {
+    // Schedule the update requested by an interface after processing the input. Interfaces
+    // that sent messages are updated on the next frame, to show the state they changed.
+    pub fn schedule_update(&mut self, state: &user_interface::State, sent_messages: bool) {
+        self.update_next_frame |= sent_messages;
+        match state {
+            user_interface::State::Outdated
+            | user_interface::State::Updated {
+                redraw_request: Some(window::RedrawRequest::NextFrame),
+            } => self.update_next_frame = true,
+            // The time is the one of the redraw tick, driven by Bevy's clock.
+            user_interface::State::Updated {
+                redraw_request: Some(window::RedrawRequest::At(at)),
+            } => {
+                self.update_at = Some(self.update_at.map_or(*at, |current| current.min(*at)));
+            }
+            user_interface::State::Updated {
+                redraw_request: None,
+            } => {}
+        }
+    }
+}
+
+// The readers of Bevy's input events, shared by `process_input` and the interfaces reading
+// the input sent after it with `IcedSettings::direct_input`, so that each event is read once.
+#[derive(Resource, Default)]
+pub struct InputReaders {
+    cursor_entered: ManualEventReader<CursorEntered>,
+    cursor_left: ManualEventReader<CursorLeft>,
+    window_focused: ManualEventReader<WindowFocused>,
+    window_resized: ManualEventReader<WindowResized>,
+    window_moved: ManualEventReader<WindowMoved>,
+    file_drag_and_drop: ManualEventReader<FileDragAndDrop>,
+    cursor: ManualEventReader<CursorMoved>,
+    mouse_button: ManualEventReader<MouseButtonInput>,
+    mouse_wheel: ManualEventReader<MouseWheel>,
+    received_character: ManualEventReader<ReceivedCharacter>,
+    keyboard_input: ManualEventReader<KeyboardInput>,
+    touch_input: ManualEventReader<TouchInput>,
+}
 
 #[derive(SystemParam)]
 pub struct InputEvents<'w, 's> {
-    cursor_entered: EventReader<'w, 's, CursorEntered>,
-    cursor_left: EventReader<'w, 's, CursorLeft>,
-    cursor: EventReader<'w, 's, CursorMoved>,
-    mouse_button: EventReader<'w, 's, MouseButtonInput>,
-    mouse_wheel: EventReader<'w, 's, MouseWheel>,
-    received_character: EventReader<'w, 's, ReceivedCharacter>,
-    keyboard_input: EventReader<'w, 's, KeyboardInput>,
-    touch_input: EventReader<'w, 's, TouchInput>,
+    readers: ResMut<'w, InputReaders>,
+    cursor_entered: Res<'w, Events<CursorEntered>>,
+    cursor_left: Res<'w, Events<CursorLeft>>,
+    window_focused: Res<'w, Events<WindowFocused>>,
+    window_resized: Res<'w, Events<WindowResized>>,
+    window_moved: Res<'w, Events<WindowMoved>>,
+    file_drag_and_drop: Res<'w, Events<FileDragAndDrop>>,
+    cursor: Res<'w, Events<CursorMoved>>,
+    mouse_button: Res<'w, Events<MouseButtonInput>>,
+    mouse_wheel: Res<'w, Events<MouseWheel>>,
+    received_character: Res<'w, Events<ReceivedCharacter>>,
+    keyboard_input: Res<'w, Events<KeyboardInput>>,
+    touch_input: Res<'w, Events<TouchInput>>,
+    input_map: Res<'w, ButtonInput<KeyCode>>,
+    tool_windows: Query<'w, 's, Entity, With<IcedWindow>>,
+    primary: PrimaryViewport<'w, 's>,
+}
+
+// Maps the positions of the primary window to the logical coordinates of the viewport,
+// which differ with `IcedSettings::fixed_scale`, as the cursor of interfaces does.
+#[derive(SystemParam)]
+pub struct PrimaryViewport<'w, 's> {
+    viewport: Res<'w, ViewportResource>,
+    windows: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+}
+
+impl<'w, 's> PrimaryViewport<'w, 's> {
+    fn map(&self, window: Entity, position: Point) -> Point {
+        match self.windows.get(window) {
+            Ok(window) => utils::process_cursor_position(
+                Vec2::new(position.x, position.y),
+                self.viewport.logical_size(),
+                window,
+            ),
+            Err(_) => position,
+        }
+    }
+}
+
+impl<'w, 's> InputEvents<'w, 's> {
+    // Convert the input events not read yet, pushing them to the queue of their window.
+    fn read(&mut self, event_queue: &mut IcedEventQueue, scroll_sensitivity: f32) {
+        let Self {
+            readers,
+            cursor_entered,
+            cursor_left,
+            window_focused,
+            window_resized,
+            window_moved,
+            file_drag_and_drop,
+            cursor,
+            mouse_button,
+            mouse_wheel,
+            received_character,
+            keyboard_input,
+            touch_input,
+            input_map,
+            tool_windows,
+            primary,
+        } = self;
+
+        for ev in readers.cursor.read(cursor) {
+            let queue = event_queue.window_mut(ev.window, tool_windows);
+            queue.push(IcedEvent::Mouse(mouse::Event::CursorMoved {
+                position: primary.map(ev.window, Point::new(ev.position.x, ev.position.y)),
+            }));
+        }
+
+        for ev in readers.mouse_button.read(mouse_button) {
+            let button = convert::mouse_button(ev.button);
+            let queue = event_queue.window_mut(ev.window, tool_windows);
+            queue.push(IcedEvent::Mouse(match ev.state {
+                ButtonState::Pressed => iced_core::mouse::Event::ButtonPressed(button),
+                ButtonState::Released => iced_core::mouse::Event::ButtonReleased(button),
+            }));
+        }
+
+        for ev in readers.cursor_entered.read(cursor_entered) {
+            let queue = event_queue.window_mut(ev.window, tool_windows);
+            queue.push(IcedEvent::Mouse(iced_core::mouse::Event::CursorEntered));
+        }
+
+        for ev in readers.cursor_left.read(cursor_left) {
+            let queue = event_queue.window_mut(ev.window, tool_windows);
+            queue.push(IcedEvent::Mouse(iced_core::mouse::Event::CursorLeft));
+        }
+
+        for ev in readers.window_focused.read(window_focused) {
+            let queue = event_queue.window_mut(ev.window, tool_windows);
+            if ev.focused {
+                queue.push(IcedEvent::Window(window::Id::MAIN, window::Event::Focused));
+            } else {
+                queue.push(IcedEvent::Window(
+                    window::Id::MAIN,
+                    window::Event::Unfocused,
+                ));
+                // The cursor isn't tracked anymore, so hover states would get stuck otherwise.
+                queue.push(IcedEvent::Mouse(iced_core::mouse::Event::CursorLeft));
+            }
+        }
+
+        for ev in readers.window_resized.read(window_resized) {
+            let queue = event_queue.window_mut(ev.window, tool_windows);
+            queue.push(IcedEvent::Window(
+                window::Id::MAIN,
+                window::Event::Resized {
+                    width: ev.width as u32,
+                    height: ev.height as u32,
+                },
+            ));
+        }
+
+        for ev in readers.window_moved.read(window_moved) {
+            event_queue.push(IcedEvent::Window(
+                window::Id::MAIN,
+                window::Event::Moved {
+                    x: ev.position.x,
+                    y: ev.position.y,
+                },
+            ));
+        }
+
+        for ev in readers.file_drag_and_drop.read(file_drag_and_drop) {
+            let (target, event) = match ev {
+                FileDragAndDrop::HoveredFile { window, path_buf } => {
+                    (*window, window::Event::FileHovered(path_buf.clone()))
+                }
+                FileDragAndDrop::DroppedFile { window, path_buf } => {
+                    (*window, window::Event::FileDropped(path_buf.clone()))
+                }
+                FileDragAndDrop::HoveredFileCanceled { window } => {
+                    (*window, window::Event::FilesHoveredLeft)
+                }
+            };
+            let queue = event_queue.window_mut(target, tool_windows);
+            queue.push(IcedEvent::Window(window::Id::MAIN, event));
+        }
+
+        for ev in readers.mouse_wheel.read(mouse_wheel) {
+            let queue = event_queue.window_mut(ev.window, tool_windows);
+            queue.push(IcedEvent::Mouse(iced_core::mouse::Event::WheelScrolled {
+                delta: convert::scroll_delta(ev, scroll_sensitivity),
+            }));
+        }
+
+        let modifiers = compute_modifiers(input_map);
+
+        for ev in readers.received_character.read(received_character) {
+            for char in ev.char.chars() {
+                let smol_str = SmolStr::new(char.to_string());
+                let event = keyboard::Event::KeyPressed {
+                    key: keyboard::Key::Character(smol_str.clone()),
+                    modifiers,
+                    // NOTE: This is a winit thing we don't get from bevy events
+                    location: keyboard::Location::Standard,
+                    text: Some(smol_str),
+                };
+                let queue = event_queue.window_mut(ev.window, tool_windows);
+                queue.push(IcedEvent::Keyboard(event));
+            }
+        }
+
+        for ev in readers.keyboard_input.read(keyboard_input) {
+            use keyboard::Event::*;
+            let event = match ev.key_code {
+                KeyCode::ControlLeft
+                | KeyCode::ControlRight
+                | KeyCode::ShiftLeft
+                | KeyCode::ShiftRight
+                | KeyCode::AltLeft
+                | KeyCode::AltRight
+                | KeyCode::SuperLeft
+                | KeyCode::SuperRight => ModifiersChanged(modifiers),
+                _ => {
+                    let key = convert::key_code(&ev.logical_key);
+                    let location = convert::key_location(ev.key_code);
+                    if ev.state.is_pressed() {
+                        KeyPressed {
+                            key,
+                            modifiers,
+                            location,
+                            text: None,
+                        }
+                    } else {
+                        KeyReleased {
+                            key,
+                            modifiers,
+                            location,
+                        }
+                    }
+                }
+            };
+
+            let queue = event_queue.window_mut(ev.window, tool_windows);
+            queue.push(IcedEvent::Keyboard(event));
+        }
+
+        for ev in readers.touch_input.read(touch_input) {
+            let queue = event_queue.window_mut(ev.window, tool_windows);
+            let event = IcedEvent::Touch(convert::touch_event(ev));
+            queue.push(utils::map_event_position(&event, |position| {
+                primary.map(ev.window, position)
+            }));
+        }
+    }
+}
+
+// The input queue of an `IcedContext`, which reads the input sent since `process_input`
+// before each interface with `IcedSettings::direct_input`.
+#[derive(SystemParam)]
+pub struct DirectInput<'w, 's> {
+    queue: ResMut<'w, IcedEventQueue>,
+    input: InputEvents<'w, 's>,
+    filters: ResMut<'w, IcedEventFilters>,
+}
+
+impl<'w, 's> DirectInput<'w, 's> {
+    // Push the input sent since it was last read to the queues, trimmed and filtered like
+    // the input read by `process_input`.
+    pub fn read_late_input(&mut self, settings: &IcedSettings) {
+        let mut late = IcedEventQueue::default();
+        self.input.read(&mut late, settings.scroll_sensitivity);
+        for queue in late.queues_mut() {
+            settings.trim_input(queue);
+            self.filters.apply(queue);
+        }
+        self.queue.append(&mut late.events);
+        for (window, mut events) in late.windows {
+            self.queue.window(window).append(&mut events);
+        }
+    }
+}
+
+impl<'w, 's> std::ops::Deref for DirectInput<'w, 's> {
+    type Target = IcedEventQueue;
+
+    fn deref(&self) -> &IcedEventQueue {
+        &self.queue
+    }
+}
+
+impl<'w, 's> std::ops::DerefMut for DirectInput<'w, 's> {
+    fn deref_mut(&mut self) -> &mut IcedEventQueue {
+        &mut self.queue
+    }
 }
 
 fn compute_modifiers(input_map: &ButtonInput<KeyCode>) -> keyboard::Modifiers {
@@ -47,93 +970,471 @@ fn compute_modifiers(input_map: &ButtonInput<KeyCode>) -> keyboard::Modifiers {
     modifiers
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn process_input(
     mut events: InputEvents,
     mut event_queue: ResMut<IcedEventQueue>,
-    input_map: Res<ButtonInput<KeyCode>>,
+    mut ignored_events: ResMut<IcedIgnoredEvents>,
+    mut interaction: ResMut<IcedInteraction>,
+    settings: Res<IcedSettings>,
+    time: Res<Time>,
+    cache: IcedCacheRef,
+    mut redraws: EventWriter<RequestRedraw>,
+    mut time_origin: Local<Option<Instant>>,
+    mut repeater: Local<KeyRepeater>,
+    mut scroller: Local<KineticScroller>,
 ) {
     event_queue.clear();
+    event_queue.windows.clear();
+    event_queue.captured.clear();
+    ignored_events.clear();
+    interaction.current = mouse::Interaction::Idle;
 
-    for ev in events.cursor.read() {
-        event_queue.push(IcedEvent::Mouse(mouse::Event::CursorMoved {
-            position: Point::new(ev.position.x, ev.position.y),
-        }));
+    // Drive animated widgets from Bevy's clock, so they follow its pausing and scaling.
+    let origin = *time_origin.get_or_insert_with(Instant::now);
+    let tick = IcedEvent::Window(
+        window::Id::MAIN,
+        window::Event::RedrawRequested(origin + time.elapsed()),
+    );
+    event_queue.push(tick.clone());
+    for tool_window in &events.tool_windows {
+        event_queue.windows.insert(tool_window, vec![tick.clone()]);
     }
 
-    for ev in events.mouse_button.read() {
-        let button = conversions::mouse_button(ev.button);
-        event_queue.push(IcedEvent::Mouse(match ev.state {
-            ButtonState::Pressed => iced_core::mouse::Event::ButtonPressed(button),
-            ButtonState::Released => iced_core::mouse::Event::ButtonReleased(button),
-        }));
+    events.read(&mut event_queue, settings.scroll_sensitivity);
+    for queue in event_queue.queues_mut() {
+        settings.trim_input(queue);
     }
+    let now = origin + time.elapsed();
 
-    for _ev in events.cursor_entered.read() {
-        event_queue.push(IcedEvent::Mouse(iced_core::mouse::Event::CursorEntered));
+    if let Some(kinetic_scroll) = &settings.kinetic_scroll {
+        scroller.track(&event_queue, now, kinetic_scroll);
+        if let Some(scroll) = scroller.scroll(now, kinetic_scroll) {
+            event_queue.push(scroll);
+            redraws.send(RequestRedraw);
+        }
+    } else {
+        *scroller = KineticScroller::default();
     }
+    interaction.fling_cursor = scroller.cursor();
 
-    for _ev in events.cursor_left.read() {
-        event_queue.push(IcedEvent::Mouse(iced_core::mouse::Event::CursorLeft));
+    let Some(key_repeat) = settings.key_repeat else {
+        return;
+    };
+    repeater.track(None, &event_queue, now, key_repeat.delay);
+    for (window, queue) in &event_queue.windows {
+        repeater.track(Some(*window), queue, now, key_repeat.delay);
+    }
+    // Only text inputs are meant to see the repeats, not the hotkeys of the game.
+    if !cache.has_focused_input() {
+        repeater.held = None;
+        return;
+    }
+    if let Some((window, mut repeats)) = repeater.repeat(now, key_repeat.interval) {
+        match window {
+            Some(window) => event_queue.window(window).append(&mut repeats),
+            None => event_queue.append(&mut repeats),
+        }
+        // Wake reactive applications up for the next repeat.
+        if let Some(held) = &repeater.held {
+            let at = held.next_repeat;
+            interaction.update_at =
+                Some(interaction.update_at.map_or(at, |current| current.min(at)));
+        }
     }
+}
+
+// The physical size, scale factor, bounds and visibility the interfaces were last laid out
+// with.
+type LastLayout = (Size<u32>, f64, Rectangle, bool);
 
-    for ev in events.mouse_wheel.read() {
-        event_queue.push(IcedEvent::Mouse(iced_core::mouse::Event::WheelScrolled {
-            delta: mouse::ScrollDelta::Pixels { x: ev.x, y: ev.y },
-        }));
+#[allow(clippy::too_many_arguments)]
+pub fn detect_updates(
+    mut event_queue: ResMut<IcedEventQueue>,
+    viewport: Res<ViewportResource>,
+    bounds: Res<ViewportBounds>,
+    settings: Res<IcedSettings>,
+    mut interaction: ResMut<IcedInteraction>,
+    mut needs_update: ResMut<IcedNeedsUpdate>,
+    mut last_layout: Local<Option<LastLayout>>,
+    mut throttle: Local<UpdateThrottle>,
+) {
+    let layout = (
+        viewport.physical_size(),
+        viewport.scale_factor(),
+        **bounds,
+        bounds.hidden,
+    );
+    let layout_changed = last_layout.replace(layout) != Some(layout);
+    // The redraw tick is sent every frame, and only matters to animations.
+    let has_input = event_queue
+        .iter()
+        .chain(event_queue.windows.values().flatten())
+        .any(|event| {
+            !matches!(
+                event,
+                IcedEvent::Window(_, window::Event::RedrawRequested(_))
+            )
+        });
+    let now = event_queue.iter().find_map(|event| match event {
+        IcedEvent::Window(_, window::Event::RedrawRequested(now)) => Some(*now),
+        _ => None,
+    });
+    interaction.now = now;
+    let due = interaction
+        .update_at
+        .is_some_and(|at| now.is_some_and(|now| at <= now));
+    if due {
+        interaction.update_at = None;
     }
+    let scheduled = std::mem::take(&mut interaction.update_next_frame) || due;
+    let wanted = has_input || layout_changed || scheduled || settings.is_changed();
+    throttle.frame += 1;
 
-    let modifiers = compute_modifiers(&input_map);
+    if !settings.update_frequency.is_due(&mut throttle, now) {
+        throttle.pending |= wanted;
+        let frame = throttle.frame;
+        // The redraw tick stays for animations.
+        let (ticks, skipped): (Vec<_>, Vec<_>) = std::mem::take(&mut **event_queue)
+            .into_iter()
+            .partition(is_redraw_tick);
+        **event_queue = ticks;
+        throttle
+            .deferred
+            .extend(skipped.into_iter().map(|event| (frame, event)));
+        needs_update.set_if_neq(IcedNeedsUpdate(false));
+        return;
+    }
+    if !throttle.deferred.is_empty() {
+        let mut deferred = std::mem::take(&mut throttle.deferred);
+        settings
+            .event_queue
+            .drop_stale(&mut deferred, throttle.frame);
+        let current = std::mem::take(&mut **event_queue);
+        // The deferred input happened before this frame's, but after its redraw tick.
+        let (ticks, current): (Vec<_>, Vec<_>) = current.into_iter().partition(is_redraw_tick);
+        **event_queue = ticks
+            .into_iter()
+            .chain(deferred.into_iter().map(|(_, event)| event))
+            .chain(current)
+            .collect();
+        settings.event_queue.apply(&mut event_queue);
+    }
+    let pending = std::mem::take(&mut throttle.pending);
+    needs_update.set_if_neq(IcedNeedsUpdate(!bounds.hidden && (wanted || pending)));
+}
 
-    for ev in events.received_character.read() {
-        for char in ev.char.chars() {
-            let smol_str = SmolStr::new(char.to_string());
-            let event = keyboard::Event::KeyPressed {
-                key: keyboard::Key::Character(smol_str.clone()),
-                modifiers,
-                // NOTE: This is a winit thing we don't get from bevy events
-                location: keyboard::Location::Standard,
-                text: Some(smol_str),
+fn is_redraw_tick(event: &IcedEvent) -> bool {
+    matches!(
+        event,
+        IcedEvent::Window(_, window::Event::RedrawRequested(_))
+    )
+}
+
+// Wake reactive applications (see `WinitSettings`) up for the updates interfaces asked for
+// on the next frame.
+pub fn request_redraw(interaction: Res<IcedInteraction>, mut redraws: EventWriter<RequestRedraw>) {
+    if interaction.update_next_frame {
+        redraws.send(RequestRedraw);
+    }
+}
+
+pub fn apply_virtual_keyboard(
+    mut focus_events: EventReader<IcedTextInputFocused>,
+    settings: Res<IcedSettings>,
+    viewport: Res<ViewportResource>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    // When an interface loses focus while another one gains it, the focus wins.
+    let Some(bounds) = focus_events.read().fold(None, |focused, event| {
+        Some(event.bounds.or(focused.flatten()))
+    }) else {
+        return;
+    };
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+    if !settings.virtual_keyboard {
+        return;
+    }
+    window.ime_enabled = bounds.is_some();
+    if let Some(bounds) = bounds {
+        let scale = (viewport.scale_factor() / f64::from(window.scale_factor())) as f32;
+        window.ime_position = Vec2::new(bounds.x, bounds.y + bounds.height) * scale;
+    }
+}
+
+// Show the cursor icon of the interfaces while the cursor is over an interactive part of
+// them, and give the icon of the application back once it leaves.
+pub fn apply_cursor_icon(
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut interaction: ResMut<IcedInteraction>,
+) {
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+    let current = window.cursor.icon;
+    let icon = (interaction.current != mouse::Interaction::Idle)
+        .then(|| convert::cursor_icon(interaction.current));
+    interaction.applied = match (icon, interaction.applied) {
+        (None, None) => None,
+        (None, Some((applied, app_icon))) => {
+            // Unless the application changed it in the meantime.
+            if current == applied {
+                window.cursor.icon = app_icon;
+            }
+            None
+        }
+        (Some(icon), applied) => {
+            let app_icon = match applied {
+                Some((applied, app_icon)) if current == applied => app_icon,
+                _ => current,
             };
-            event_queue.push(IcedEvent::Keyboard(event));
-        }
-    }
-
-    for ev in events.keyboard_input.read() {
-        use keyboard::Event::*;
-        let event = match ev.key_code {
-            KeyCode::ControlLeft
-            | KeyCode::ControlRight
-            | KeyCode::ShiftLeft
-            | KeyCode::ShiftRight
-            | KeyCode::AltLeft
-            | KeyCode::AltRight
-            | KeyCode::SuperLeft
-            | KeyCode::SuperRight => ModifiersChanged(modifiers),
-            _ => {
-                let key = conversions::key_code(&ev.logical_key);
-                if ev.state.is_pressed() {
-                    KeyPressed {
-                        key,
-                        modifiers,
-                        // NOTE: This is a winit thing we don't get from bevy events
-                        location: keyboard::Location::Standard,
-                        text: None,
-                    }
-                } else {
-                    KeyReleased {
-                        key,
-                        modifiers,
-                        // NOTE: This is a winit thing we don't get from bevy events
-                        location: keyboard::Location::Standard,
-                    }
-                }
+            if current != icon {
+                window.cursor.icon = icon;
             }
+            Some((icon, app_icon))
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cursor_moved(x: f32, y: f32) -> IcedEvent {
+        IcedEvent::Mouse(mouse::Event::CursorMoved {
+            position: Point::new(x, y),
+        })
+    }
+
+    #[test]
+    fn cursor_moves_coalesce() {
+        let policy = EventQueuePolicy {
+            coalesce_cursor_moves: true,
+            ..Default::default()
+        };
+        let pressed = IcedEvent::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left));
+        let mut queue = vec![
+            cursor_moved(1.0, 1.0),
+            cursor_moved(2.0, 2.0),
+            pressed.clone(),
+            cursor_moved(3.0, 3.0),
+        ];
+        policy.apply(&mut queue);
+        assert_eq!(
+            queue,
+            [cursor_moved(2.0, 2.0), pressed, cursor_moved(3.0, 3.0)]
+        );
+    }
+
+    #[test]
+    fn overflow_keeps_releases_past_max_len() {
+        let policy = EventQueuePolicy {
+            max_len: Some(1),
+            ..Default::default()
+        };
+        let released = |button| IcedEvent::Mouse(mouse::Event::ButtonReleased(button));
+        let mut queue = vec![
+            cursor_moved(1.0, 1.0),
+            released(mouse::Button::Left),
+            cursor_moved(2.0, 2.0),
+            released(mouse::Button::Right),
+        ];
+        policy.apply(&mut queue);
+        assert_eq!(
+            queue,
+            [
+                released(mouse::Button::Left),
+                released(mouse::Button::Right)
+            ]
+        );
+    }
+
+    #[test]
+    fn overflow_drops_the_oldest_events() {
+        let policy = EventQueuePolicy {
+            max_len: Some(2),
+            ..Default::default()
+        };
+        let mut queue = (0..5).map(|i| cursor_moved(i as f32, 0.0)).collect();
+        policy.apply(&mut queue);
+        assert_eq!(queue, [cursor_moved(3.0, 0.0), cursor_moved(4.0, 0.0)]);
+    }
+
+    #[test]
+    fn stale_input_keeps_releases() {
+        let policy = EventQueuePolicy {
+            max_age: Some(2),
+            ..Default::default()
         };
+        let released = IcedEvent::Touch(touch::Event::FingerLifted {
+            id: touch::Finger(0),
+            position: Point::ORIGIN,
+        });
+        let mut deferred = vec![
+            (5, cursor_moved(1.0, 1.0)),
+            (5, released.clone()),
+            (8, cursor_moved(2.0, 2.0)),
+            (9, cursor_moved(3.0, 3.0)),
+        ];
+        policy.drop_stale(&mut deferred, 10);
+        assert_eq!(
+            deferred,
+            [
+                (5, released),
+                (8, cursor_moved(2.0, 2.0)),
+                (9, cursor_moved(3.0, 3.0))
+            ]
+        );
+    }
+
+    fn key_pressed(key: keyboard::Key) -> IcedEvent {
+        IcedEvent::Keyboard(keyboard::Event::KeyPressed {
+            key,
+            modifiers: keyboard::Modifiers::empty(),
+            location: keyboard::Location::Standard,
+            text: None,
+        })
+    }
+
+    #[test]
+    fn held_keys_repeat_after_the_delay() {
+        let (delay, interval) = (Duration::from_millis(500), Duration::from_millis(100));
+        let arrow = keyboard::Key::Named(keyboard::key::Named::ArrowLeft);
+        let start = Instant::now();
+        let mut repeater = KeyRepeater::default();
+        repeater.track(None, &[key_pressed(arrow.clone())], start, delay);
+
+        let (_, repeats) = repeater.repeat(start + delay / 2, interval).unwrap();
+        assert!(repeats.is_empty());
+        let (window, repeats) = repeater
+            .repeat(start + delay + interval * 2, interval)
+            .unwrap();
+        assert_eq!(window, None);
+        assert_eq!(repeats, vec![key_pressed(arrow.clone()); 3]);
+
+        let released = IcedEvent::Keyboard(keyboard::Event::KeyReleased {
+            key: arrow,
+            modifiers: keyboard::Modifiers::empty(),
+            location: keyboard::Location::Standard,
+        });
+        repeater.track(None, &[released], start + delay * 2, delay);
+        assert!(repeater.repeat(start + delay * 4, interval).is_none());
+    }
+
+    #[test]
+    fn native_repeats_stop_the_synthesis() {
+        let delay = Duration::from_millis(500);
+        let arrow = keyboard::Key::Named(keyboard::key::Named::ArrowLeft);
+        let start = Instant::now();
+        let mut repeater = KeyRepeater::default();
+        // The platform repeats the held key by itself.
+        repeater.track(None, &[key_pressed(arrow.clone())], start, delay);
+        repeater.track(None, &[key_pressed(arrow.clone())], start + delay, delay);
+        assert!(repeater.repeat(start + delay * 4, delay).is_none());
+
+        // Other keys aren't synthesized either from then on.
+        let home = keyboard::Key::Named(keyboard::key::Named::Home);
+        repeater.track(None, &[key_pressed(home)], start + delay * 5, delay);
+        assert!(repeater.repeat(start + delay * 8, delay).is_none());
+    }
+
+    fn touch(event: fn(touch::Finger, Point) -> touch::Event, y: f32) -> IcedEvent {
+        IcedEvent::Touch(event(touch::Finger(0), Point::new(0.0, y)))
+    }
+
+    fn pressed(id: touch::Finger, position: Point) -> touch::Event {
+        touch::Event::FingerPressed { id, position }
+    }
+
+    fn moved(id: touch::Finger, position: Point) -> touch::Event {
+        touch::Event::FingerMoved { id, position }
+    }
+
+    fn lifted(id: touch::Finger, position: Point) -> touch::Event {
+        touch::Event::FingerLifted { id, position }
+    }
+
+    // Drag the finger up by `distance` over 100 ms, then lift it.
+    fn fling(scroller: &mut KineticScroller, start: Instant, distance: f32) {
+        let settings = KineticScroll::default();
+        let step = Duration::from_millis(50);
+        scroller.track(&[touch(pressed, distance)], start, &settings);
+        scroller.track(&[touch(moved, distance / 2.0)], start + step, &settings);
+        scroller.track(&[touch(lifted, 0.0)], start + step * 2, &settings);
+    }
+
+    fn scrolled(event: Option<IcedEvent>) -> Option<f32> {
+        match event? {
+            IcedEvent::Mouse(mouse::Event::WheelScrolled {
+                delta: mouse::ScrollDelta::Pixels { y, .. },
+            }) => Some(y),
+            event => panic!("not a scroll: {event:?}"),
+        }
+    }
+
+    #[test]
+    fn flings_decay_until_the_cutoff() {
+        let settings = KineticScroll::default();
+        let start = Instant::now();
+        let mut scroller = KineticScroller::default();
+        // Released at 1000 pixels per second.
+        fling(&mut scroller, start, 100.0);
+        let lifted = start + Duration::from_millis(100);
+        assert_eq!(scroller.cursor(), Some(Point::ORIGIN));
+
+        let frame = Duration::from_millis(100);
+        let first = scrolled(scroller.scroll(lifted + frame, &settings)).unwrap();
+        let expected = -1000.0 * (-settings.decay * 0.1).exp() * 0.1;
+        assert!((first - expected).abs() < 0.01, "{first} != {expected}");
+        let second = scrolled(scroller.scroll(lifted + frame * 2, &settings)).unwrap();
+        assert!(second < 0.0 && second > first);
+
+        // The velocity drops below `min_velocity` after ln(1000 / 60) / 3 s, about 940 ms.
+        assert_eq!(
+            scrolled(scroller.scroll(lifted + frame * 10, &settings)),
+            None
+        );
+        assert_eq!(scroller.cursor(), None);
+        assert_eq!(
+            scrolled(scroller.scroll(lifted + frame * 11, &settings)),
+            None
+        );
+    }
+
+    #[test]
+    fn slow_drags_dont_fling() {
+        let start = Instant::now();
+        let mut scroller = KineticScroller::default();
+        // Released at 50 pixels per second, below `min_velocity`.
+        fling(&mut scroller, start, 5.0);
+        assert_eq!(scroller.cursor(), None);
+    }
 
-        event_queue.push(IcedEvent::Keyboard(event));
+    #[test]
+    fn flings_start_at_most_at_max_velocity() {
+        let settings = KineticScroll::default();
+        let start = Instant::now();
+        let mut scroller = KineticScroller::default();
+        // Released at 100000 pixels per second.
+        fling(&mut scroller, start, 10000.0);
+        let lifted = start + Duration::from_millis(100);
+        let frame = Duration::from_millis(10);
+        let first = scrolled(scroller.scroll(lifted + frame, &settings)).unwrap();
+        let expected = -settings.max_velocity * (-settings.decay * 0.01).exp() * 0.01;
+        assert!((first - expected).abs() < 0.01, "{first} != {expected}");
     }
 
-    for ev in events.touch_input.read() {
-        event_queue.push(IcedEvent::Touch(conversions::touch_event(ev)));
+    #[test]
+    fn presses_stop_flings() {
+        let settings = KineticScroll::default();
+        let start = Instant::now();
+        let mut scroller = KineticScroller::default();
+        fling(&mut scroller, start, 100.0);
+        let click = IcedEvent::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left));
+        let now = start + Duration::from_millis(150);
+        scroller.track(&[click], now, &settings);
+        assert_eq!(scrolled(scroller.scroll(now, &settings)), None);
     }
 }
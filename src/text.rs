@@ -0,0 +1,124 @@
+use std::borrow::Cow;
+
+use iced_core::alignment;
+use iced_core::text::{LineHeight, Paragraph as _, Shaping, Text};
+use iced_core::{Font, Pixels, Size};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::Renderer;
+
+/// The string appended to truncated text.
+pub const ELLIPSIS: &str = "…";
+
+type Paragraph = <Renderer as iced_core::text::Renderer>::Paragraph;
+
+fn paragraph(content: &str, font: Font, size: Pixels, bounds: Size) -> Paragraph {
+    Paragraph::with_text(Text {
+        content,
+        bounds,
+        size,
+        line_height: LineHeight::default(),
+        font,
+        horizontal_alignment: alignment::Horizontal::Left,
+        vertical_alignment: alignment::Vertical::Top,
+        shaping: Shaping::Advanced,
+    })
+}
+
+/// Measure the size of `content` when rendered without wrapping.
+///
+/// The text is shaped by the same engine Iced renders with, so the result accounts for
+/// proportional fonts, ligatures and complex scripts.
+pub fn measure(content: &str, font: Font, size: impl Into<Pixels>) -> Size {
+    paragraph(content, font, size.into(), Size::INFINITY).min_bounds()
+}
+
+/// The number of lines `content` takes when wrapped to `max_width`.
+pub fn line_count(content: &str, max_width: f32, font: Font, size: impl Into<Pixels>) -> usize {
+    let size = size.into();
+    let height = paragraph(content, font, size, Size::new(max_width, f32::INFINITY))
+        .min_bounds()
+        .height;
+    (height / LineHeight::default().to_absolute(size).0).round() as usize
+}
+
+/// Truncate `content` so that it fits on a single line of `max_width`, replacing the end
+/// with an [`ELLIPSIS`] if needed.
+///
+/// The text is cut between grapheme clusters, so that accented letters, emoji sequences and
+/// the syllables of complex scripts are kept whole.
+pub fn truncate(
+    content: &str,
+    max_width: f32,
+    font: Font,
+    size: impl Into<Pixels>,
+) -> Cow<'_, str> {
+    let size = size.into();
+    if measure(content, font, size).width <= max_width {
+        return Cow::Borrowed(content);
+    }
+    longest_prefix(content, |text| measure(text, font, size).width <= max_width)
+}
+
+/// Truncate `content` so that it fits in `max_lines` lines wrapped to `max_width`,
+/// replacing the end with an [`ELLIPSIS`] if needed.
+///
+/// Like with [`truncate`], the text is cut between grapheme clusters.
+pub fn clamp_lines(
+    content: &str,
+    max_width: f32,
+    max_lines: usize,
+    font: Font,
+    size: impl Into<Pixels>,
+) -> Cow<'_, str> {
+    let size = size.into();
+    if line_count(content, max_width, font, size) <= max_lines {
+        return Cow::Borrowed(content);
+    }
+    longest_prefix(content, |text| {
+        line_count(text, max_width, font, size) <= max_lines
+    })
+}
+
+// Find the longest prefix of `content`, ending at a grapheme cluster boundary, that still
+// `fits` once the ellipsis is appended.
+fn longest_prefix(content: &str, fits: impl Fn(&str) -> bool) -> Cow<'_, str> {
+    let with_ellipsis = |end: usize| format!("{}{ELLIPSIS}", content[..end].trim_end());
+    let boundaries: Vec<usize> = content.grapheme_indices(true).map(|(i, _)| i).collect();
+    match boundaries.partition_point(|&end| fits(&with_ellipsis(end))) {
+        0 => Cow::Borrowed(""),
+        count => Cow::Owned(with_ellipsis(boundaries[count - 1])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Whether `text` is at most `max` characters long, standing in for a measurement.
+    fn chars(max: usize) -> impl Fn(&str) -> bool {
+        move |text| text.chars().count() <= max
+    }
+
+    #[test]
+    fn prefixes_end_at_grapheme_boundaries() {
+        // The thumbs up with a skin tone is two characters, but a single grapheme.
+        let content = "ab\u{1F44D}\u{1F3FD}cd";
+        assert_eq!(longest_prefix(content, chars(4)), "ab…");
+        assert_eq!(longest_prefix(content, chars(5)), "ab\u{1F44D}\u{1F3FD}…");
+        // So is a letter with a combining accent.
+        let content = "cafe\u{301} noir";
+        assert_eq!(longest_prefix(content, chars(5)), "caf…");
+        assert_eq!(longest_prefix(content, chars(6)), "cafe\u{301}…");
+    }
+
+    #[test]
+    fn prefixes_drop_trailing_whitespace() {
+        assert_eq!(longest_prefix("ab   cd", chars(5)), "ab…");
+    }
+
+    #[test]
+    fn nothing_fits() {
+        assert_eq!(longest_prefix("abc", chars(0)), "");
+    }
+}
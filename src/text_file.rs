@@ -0,0 +1,120 @@
+use std::fmt;
+
+use bevy_asset::{AssetPath, AssetServer};
+use bevy_ecs::system::{Res, SystemParam};
+use bevy_tasks::futures_lite::future;
+use bevy_tasks::futures_lite::{AsyncReadExt, AsyncWriteExt};
+use bevy_tasks::{IoTaskPool, Task};
+
+use crate::widgets::TextEditorContent;
+
+/// Loads and saves the contents of text editors from asset paths, without blocking the
+/// frame.
+///
+/// Paths are resolved like the ones of assets, including their asset source (e.g.
+/// `"scripts://init.lua"`). Saving requires the source to be writable, which the default
+/// file source is on desktop platforms.
+#[derive(SystemParam)]
+pub struct IcedTextFiles<'w> {
+    server: Res<'w, AssetServer>,
+}
+
+impl<'w> IcedTextFiles<'w> {
+    /// Start reading the text file at `path`.
+    pub fn load(&self, path: impl Into<AssetPath<'static>>) -> TextFileTask<String> {
+        let server = self.server.clone();
+        let path = path.into();
+        TextFileTask(IoTaskPool::get().spawn(async move {
+            let source = server
+                .get_source(path.source().clone_owned())
+                .map_err(|err| TextFileError::Source(err.to_string()))?;
+            let mut reader = source
+                .reader()
+                .read(path.path())
+                .await
+                .map_err(|err| TextFileError::Io(err.to_string()))?;
+            let mut bytes = Vec::new();
+            reader
+                .read_to_end(&mut bytes)
+                .await
+                .map_err(|err| TextFileError::Io(err.to_string()))?;
+            String::from_utf8(bytes).map_err(|_| TextFileError::Utf8)
+        }))
+    }
+
+    /// Start writing the text of `content` to the file at `path`, replacing it.
+    pub fn save(
+        &self,
+        path: impl Into<AssetPath<'static>>,
+        content: &TextEditorContent,
+    ) -> TextFileTask<()> {
+        let server = self.server.clone();
+        let path = path.into();
+        let text = content.text();
+        TextFileTask(IoTaskPool::get().spawn(async move {
+            let source = server
+                .get_source(path.source().clone_owned())
+                .map_err(|err| TextFileError::Source(err.to_string()))?;
+            let writer = source
+                .writer()
+                .map_err(|err| TextFileError::Source(err.to_string()))?;
+            let mut file = writer
+                .write(path.path())
+                .await
+                .map_err(|err| TextFileError::Io(err.to_string()))?;
+            file.write_all(text.as_bytes())
+                .await
+                .map_err(|err| TextFileError::Io(err.to_string()))?;
+            file.close()
+                .await
+                .map_err(|err| TextFileError::Io(err.to_string()))
+        }))
+    }
+}
+
+/// A text file being loaded or saved by [`IcedTextFiles`].
+///
+/// Keep it in a component or a [`Local`](bevy_ecs::system::Local) and poll it every frame
+/// until it completes. Dropping it cancels the operation.
+pub struct TextFileTask<T>(Task<Result<T, TextFileError>>);
+
+impl<T> TextFileTask<T> {
+    /// The result of the operation, once it has completed.
+    pub fn poll(&mut self) -> Option<Result<T, TextFileError>> {
+        future::block_on(future::poll_once(&mut self.0))
+    }
+}
+
+impl TextFileTask<String> {
+    /// Replace the text of `content` with the loaded file, once it has been read.
+    pub fn poll_into(
+        &mut self,
+        content: &mut TextEditorContent,
+    ) -> Option<Result<(), TextFileError>> {
+        let text = self.poll()?;
+        Some(text.map(|text| *content = TextEditorContent::with_text(&text)))
+    }
+}
+
+/// An error loading or saving a text file with [`IcedTextFiles`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TextFileError {
+    /// The asset source of the path doesn't exist, or can't be written to.
+    Source(String),
+    /// Reading or writing the file failed.
+    Io(String),
+    /// The file isn't valid UTF-8.
+    Utf8,
+}
+
+impl fmt::Display for TextFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Source(err) => write!(f, "invalid asset source: {err}"),
+            Self::Io(err) => write!(f, "failed to access the file: {err}"),
+            Self::Utf8 => write!(f, "the file isn't valid UTF-8"),
+        }
+    }
+}
+
+impl std::error::Error for TextFileError {}
@@ -0,0 +1,71 @@
+use std::marker::PhantomData;
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::{
+    resource_changed, resource_exists, Condition, IntoSystemConfigs, Res, ResMut, Resource,
+};
+use bevy_render::camera::ClearColor;
+use iced_core::Color;
+use iced_widget::style::theme::{Palette, Theme};
+
+use crate::{systems, IcedSettings};
+
+/// A resource the theme of interfaces is derived from, with an [`IcedThemeSyncPlugin`].
+///
+/// Implement this for a game-wide palette resource to have interfaces follow its changes
+/// (day and night cycles, faction colors...).
+pub trait IcedPaletteSource: Resource {
+    /// The palette of the theme to use.
+    fn palette(&self) -> Palette;
+}
+
+/// The clear color is used as the background, with the text and accent colors of the
+/// light or dark palette of Iced, whichever contrasts with it.
+impl IcedPaletteSource for ClearColor {
+    fn palette(&self) -> Palette {
+        let background = color(self.0);
+        let base = if luminance(background) > 0.5 {
+            Palette::LIGHT
+        } else {
+            Palette::DARK
+        };
+        Palette { background, ..base }
+    }
+}
+
+/// Convert a Bevy color to an Iced one, see [`color::to_iced`](crate::color::to_iced).
+pub fn color(color: bevy_render::color::Color) -> Color {
+    crate::color::to_iced(color)
+}
+
+// The relative luminance of an sRGB color, ignoring gamma.
+fn luminance(color: Color) -> f32 {
+    0.2126 * color.r + 0.7152 * color.g + 0.0722 * color.b
+}
+
+/// Keeps [`IcedSettings::theme`] in sync with the palette of the resource `R`.
+///
+/// The theme is replaced by a custom theme every time the resource changes, and interfaces
+/// are updated in the same frame.
+pub struct IcedThemeSyncPlugin<R: IcedPaletteSource>(PhantomData<R>);
+
+impl<R: IcedPaletteSource> Default for IcedThemeSyncPlugin<R> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<R: IcedPaletteSource> Plugin for IcedThemeSyncPlugin<R> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            sync_theme::<R>
+                .run_if(resource_exists::<R>.and_then(resource_changed::<R>))
+                .before(systems::detect_updates),
+        );
+    }
+}
+
+fn sync_theme<R: IcedPaletteSource>(source: Res<R>, mut settings: ResMut<IcedSettings>) {
+    settings.theme = Theme::custom(String::from("bevy_iced"), source.palette()).into();
+}
@@ -0,0 +1,100 @@
+use std::fmt;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::io::Reader;
+use bevy_asset::{Asset, AssetApp, AssetLoader, AsyncReadExt, LoadContext};
+use bevy_reflect::TypePath;
+use bevy_utils::BoxedFuture;
+
+use crate::dynamic::{IcedDynamicMessage, UiNode};
+use crate::ui_types::Element;
+
+/// An interface description loaded from a `.ui.json` or `.ui.ron` file.
+///
+/// The file contains the root [`UiNode`] of the interface. With Bevy's `file_watcher`
+/// feature, edits to the file are picked up while the game is running, so displaying the
+/// asset every frame is enough to iterate on menus without recompiling.
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct UiAsset {
+    /// The root of the interface.
+    pub root: UiNode,
+}
+
+impl UiAsset {
+    /// Build the interface, turning the messages of its widgets into typed messages with
+    /// `map`.
+    pub fn view<'a, M: 'a>(&'a self, map: impl Fn(IcedDynamicMessage) -> M + 'a) -> Element<'a, M> {
+        self.root.view().map(map)
+    }
+}
+
+/// An error loading a [`UiAsset`].
+#[derive(Debug)]
+pub enum UiAssetError {
+    /// The file couldn't be read.
+    Io(std::io::Error),
+    /// The file is not a valid JSON interface description.
+    Json(serde_json::Error),
+    /// The file is not a valid RON interface description.
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for UiAssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "could not read the interface: {error}"),
+            Self::Json(error) => write!(f, "invalid JSON interface: {error}"),
+            Self::Ron(error) => write!(f, "invalid RON interface: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for UiAssetError {}
+
+#[derive(Default)]
+struct UiAssetLoader;
+
+impl AssetLoader for UiAssetLoader {
+    type Asset = UiAsset;
+    type Settings = ();
+    type Error = UiAssetError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<UiAsset, UiAssetError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader
+                .read_to_end(&mut bytes)
+                .await
+                .map_err(UiAssetError::Io)?;
+            let is_ron = load_context
+                .path()
+                .extension()
+                .is_some_and(|extension| extension == "ron");
+            let root = if is_ron {
+                ron::de::from_bytes(&bytes).map_err(UiAssetError::Ron)?
+            } else {
+                serde_json::from_slice(&bytes).map_err(UiAssetError::Json)?
+            };
+            Ok(UiAsset { root })
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ui.json", "ui.ron"]
+    }
+}
+
+/// Registers [`UiAsset`] and its loader.
+pub struct UiAssetPlugin;
+
+impl Plugin for UiAssetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<UiAsset>()
+            .init_asset_loader::<UiAssetLoader>();
+    }
+}
@@ -0,0 +1,192 @@
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{AssetId, Assets, Handle};
+use bevy_ecs::prelude::{Component, Entity, IntoSystemConfigs, Query, With};
+use bevy_ecs::system::{Commands, Local, Res, ResMut, Resource};
+use bevy_math::UVec2;
+use bevy_render::render_asset::RenderAssets;
+use bevy_render::render_resource::Extent3d;
+use bevy_render::renderer::{render_system, RenderDevice, RenderQueue};
+use bevy_render::texture::Image;
+use bevy_render::{Extract, ExtractSchedule, Render, RenderApp, RenderSet};
+use bevy_transform::components::GlobalTransform;
+use bevy_ui::{Node, UiImage, UiSystem};
+use bevy_utils::HashMap;
+use bevy_window::{PrimaryWindow, Window};
+use iced_core::mouse::Cursor;
+use iced_core::{Color, Size, Vector};
+use iced_widget::graphics::Viewport;
+use iced_widget::style::Theme;
+
+use crate::render::{self, LayerTarget, PresentedFrame};
+use crate::{DisplayStatus, IcedContext, IcedError, Renderer};
+
+/// Marks a `bevy_ui` node that Iced interfaces are displayed in, with
+/// [`IcedContext::display_in_node`].
+///
+/// Add it to an `ImageBundle`: the interfaces are rendered into a texture sized like the
+/// node, which replaces the image of the node. This lets flexbox layouts place rich Iced
+/// widgets among the other nodes.
+///
+/// Requires the [`IcedUiNodePlugin`].
+#[derive(Component, Default)]
+pub struct IcedUiNode {
+    image: Handle<Image>,
+}
+
+impl IcedUiNode {
+    /// The texture the interfaces of the node are rendered into.
+    pub fn image(&self) -> &Handle<Image> {
+        &self.image
+    }
+}
+
+/// The nodes that interfaces can be displayed in, to pass to
+/// [`IcedContext::display_in_node`].
+pub type IcedUiNodes<'w, 's> =
+    Query<'w, 's, (&'static Node, &'static GlobalTransform), With<IcedUiNode>>;
+
+impl<'w, 's, M: bevy_ecs::event::Event> IcedContext<'w, 's, M> {
+    /// Display an [`Element`](iced_core::Element) in the `bevy_ui` node `node`, marked with
+    /// [`IcedUiNode`].
+    ///
+    /// The interface fills the node, as laid out in the last frame. It processes the input
+    /// of the primary window with the cursor mapped into the node, and the events it
+    /// captures don't reach the interfaces displayed after it. Each message type has a
+    /// single widget state per node.
+    pub fn display_in_node<'a>(
+        &'a mut self,
+        nodes: &IcedUiNodes,
+        node: Entity,
+        element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
+    ) -> DisplayStatus {
+        let (Ok(window), Ok((layout, transform))) = (self.windows.get_single(), nodes.get(node))
+        else {
+            self.errors.report(IcedError::NoWindow);
+            return DisplayStatus::default();
+        };
+        let rect = layout.logical_rect(transform);
+        let size = physical_size(layout, window);
+        let scale_factor = self
+            .settings
+            .scale_factor
+            .unwrap_or_else(|| window.scale_factor().into());
+        // The node isn't laid out yet, or is hidden.
+        if rect.width() <= 0.0 || rect.height() <= 0.0 || self.bounds.hidden {
+            return DisplayStatus::default();
+        }
+        let viewport = Viewport::with_physical_size(Size::new(size.x, size.y), scale_factor);
+        let node_scale = viewport.logical_size().width / rect.width();
+        let cursor = match self.cursor(window) {
+            Cursor::Available(position) => {
+                let position = position + Vector::new(self.bounds.x, self.bounds.y);
+                Cursor::Available(iced_core::Point::new(
+                    (position.x - rect.min.x) * node_scale,
+                    (position.y - rect.min.y) * node_scale,
+                ))
+            }
+            Cursor::Unavailable => Cursor::Unavailable,
+        };
+        let target = LayerTarget {
+            entity: node,
+            viewport,
+            background: Color::TRANSPARENT,
+        };
+        self.display_in_target(element.into(), target, cursor, false)
+    }
+}
+
+/// Sizes the textures of the [`IcedUiNode`]s like their nodes, and renders their interfaces
+/// into them.
+pub struct IcedUiNodePlugin;
+
+impl Plugin for IcedUiNodePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, resize_node_images.after(UiSystem::Layout));
+    }
+
+    fn finish(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<ExtractedUiNodes>()
+            .add_systems(ExtractSchedule, extract_ui_nodes)
+            .add_systems(
+                Render,
+                present_ui_nodes
+                    .in_set(RenderSet::Render)
+                    .before(render_system),
+            );
+    }
+}
+
+// The size of the texture of a node, in physical pixels.
+fn physical_size(node: &Node, window: &Window) -> UVec2 {
+    (node.size() * window.scale_factor())
+        .round()
+        .as_uvec2()
+        .max(UVec2::ONE)
+}
+
+fn resize_node_images(
+    mut nodes: Query<(&Node, &mut IcedUiNode, &mut UiImage)>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    for (node, mut iced_node, mut ui_image) in &mut nodes {
+        let size = physical_size(node, window);
+        let size = Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        };
+        // Borrowing the image mutably marks it as changed, and uploads it again.
+        match images
+            .get(&iced_node.image)
+            .map(|image| image.texture_descriptor.size)
+        {
+            Some(current) if current == size => {}
+            Some(_) => {
+                if let Some(image) = images.get_mut(&iced_node.image) {
+                    image.resize(size);
+                }
+            }
+            None => iced_node.image = images.add(render::target_image(size)),
+        }
+        if ui_image.texture != iced_node.image {
+            ui_image.texture = iced_node.image.clone();
+        }
+    }
+}
+
+// The textures of the nodes, in the render world.
+#[derive(Resource, Default)]
+struct ExtractedUiNodes(Vec<(Entity, AssetId<Image>)>);
+
+fn extract_ui_nodes(mut commands: Commands, nodes: Extract<Query<(Entity, &IcedUiNode)>>) {
+    commands.insert_resource(ExtractedUiNodes(
+        nodes
+            .iter()
+            .map(|(entity, node)| (entity, node.image.id()))
+            .collect(),
+    ));
+}
+
+// Render the interfaces of the nodes into their textures, before `bevy_ui` draws them.
+fn present_ui_nodes(
+    nodes: Res<ExtractedUiNodes>,
+    presented: Res<PresentedFrame>,
+    images: Res<RenderAssets<Image>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut backends: Local<HashMap<Entity, iced_wgpu::Backend>>,
+) {
+    render::present_to_images(
+        &nodes.0,
+        &presented,
+        &images,
+        &render_device,
+        &render_queue,
+        &mut backends,
+    );
+}
@@ -0,0 +1,87 @@
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::event::Event;
+use bevy_ecs::prelude::{EventWriter, IntoSystemConfigs, Local, Res};
+use iced_core::keyboard::{self, key::Named, Key};
+use iced_core::{mouse, touch, Event as IcedEvent};
+
+use crate::systems::{self, IcedEventQueue, IcedInteraction, IcedNeedsUpdate};
+use crate::IcedCacheRef;
+
+/// A widget interaction that games usually play a sound for, sent by the
+/// [`IcedUiSoundPlugin`].
+///
+/// ```ignore
+/// fn play_ui_sounds(
+///     mut sounds: EventReader<IcedUiSound>,
+///     assets: Res<UiSounds>,
+///     mut commands: Commands,
+/// ) {
+///     for sound in sounds.read() {
+///         let source = match sound {
+///             IcedUiSound::Hover => assets.hover.clone(),
+///             IcedUiSound::Click => assets.click.clone(),
+///             IcedUiSound::TextInput => assets.key.clone(),
+///         };
+///         commands.spawn(AudioBundle { source, settings: PlaybackSettings::DESPAWN });
+///     }
+/// }
+/// ```
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IcedUiSound {
+    /// The cursor started hovering a clickable widget, like a button.
+    ///
+    /// Moving from a clickable widget straight to another one isn't a new hover.
+    Hover,
+    /// A mouse button or a finger was pressed on an interactive widget.
+    Click,
+    /// Text was typed or erased in a focused text input.
+    TextInput,
+}
+
+/// Sends [`IcedUiSound`]s for the widget interactions of every interface.
+///
+/// They are derived from the input captured by interfaces and from the mouse interaction
+/// they request, so messages don't need to be instrumented to play sounds.
+pub struct IcedUiSoundPlugin;
+
+impl Plugin for IcedUiSoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<IcedUiSound>().add_systems(
+            PostUpdate,
+            send_ui_sounds.before(systems::apply_cursor_icon),
+        );
+    }
+}
+
+fn send_ui_sounds(
+    event_queue: Res<IcedEventQueue>,
+    interaction: Res<IcedInteraction>,
+    needs_update: Res<IcedNeedsUpdate>,
+    cache: IcedCacheRef,
+    mut sounds: EventWriter<IcedUiSound>,
+    mut hovering: Local<bool>,
+) {
+    let typing = cache.has_focused_input();
+    for event in event_queue.captured() {
+        let sound = match event {
+            IcedEvent::Mouse(mouse::Event::ButtonPressed(_))
+            | IcedEvent::Touch(touch::Event::FingerPressed { .. }) => IcedUiSound::Click,
+            // Characters are also sent as key presses without text, which aren't counted.
+            IcedEvent::Keyboard(keyboard::Event::KeyPressed { text: Some(_), .. })
+            | IcedEvent::Keyboard(keyboard::Event::KeyPressed {
+                key: Key::Named(Named::Backspace | Named::Delete),
+                ..
+            }) if typing => IcedUiSound::TextInput,
+            _ => continue,
+        };
+        sounds.send(sound);
+    }
+    // The interaction is only known on the frames where interfaces are updated.
+    if **needs_update {
+        let hovers = interaction.current == mouse::Interaction::Pointer;
+        if hovers && !*hovering {
+            sounds.send(IcedUiSound::Hover);
+        }
+        *hovering = hovers;
+    }
+}
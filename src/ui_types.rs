@@ -0,0 +1,10 @@
+pub use iced_core::{
+    Alignment, Background, Border, Color, Font, Gradient, Length, Padding, Pixels, Point,
+    Rectangle, Shadow, Size, Vector,
+};
+pub use iced_widget::style::Theme;
+
+pub use crate::{Renderer, TextDirection};
+
+/// A generic widget, using the [`Theme`] and [`Renderer`] of `bevy_iced`.
+pub type Element<'a, Message> = iced_core::Element<'a, Message, Theme, Renderer>;
@@ -1,16 +1,22 @@
 use crate::iced;
 use crate::IcedContext;
 use bevy_math::Vec2;
+use bevy_utils::tracing::info_span;
 use bevy_window::Window;
+use iced_core::widget::operation::{Focusable, Operation, TextInput};
+use iced_core::widget::Id;
+use iced_core::{Rectangle, Vector};
+use iced_runtime::Debug;
 
+/// Map a window cursor position to the logical coordinates of the viewport
 pub fn process_cursor_position(
     position: Vec2,
-    bounds: iced_core::Size,
+    viewport_size: iced_core::Size,
     window: &Window,
 ) -> iced_core::Point {
     iced_core::Point {
-        x: position.x * bounds.width / window.width(),
-        y: position.y * bounds.height / window.height(),
+        x: position.x * viewport_size.width / window.width(),
+        y: position.y * viewport_size.height / window.height(),
     }
 }
 
@@ -49,3 +55,125 @@ pub fn process_touch_input<M: bevy_ecs::event::Event>(
                 .copied()
         })
 }
+
+/// Move the positions of mouse and touch events by `-offset`, into the coordinates of an
+/// interface laid out at `offset` in the viewport, like its cursor
+pub fn offset_event(event: &iced::Event, offset: Vector) -> iced::Event {
+    map_event_position(event, |position| position - offset)
+}
+
+/// Map the positions of mouse and touch events with `map`
+pub fn map_event_position(
+    event: &iced::Event,
+    map: impl FnOnce(iced_core::Point) -> iced_core::Point,
+) -> iced::Event {
+    use iced::mouse::Event as Mouse;
+    use iced::touch::Event as Touch;
+
+    match *event {
+        iced::Event::Mouse(Mouse::CursorMoved { position }) => {
+            iced::Event::Mouse(Mouse::CursorMoved {
+                position: map(position),
+            })
+        }
+        iced::Event::Touch(Touch::FingerPressed { id, position }) => {
+            iced::Event::Touch(Touch::FingerPressed {
+                id,
+                position: map(position),
+            })
+        }
+        iced::Event::Touch(Touch::FingerMoved { id, position }) => {
+            iced::Event::Touch(Touch::FingerMoved {
+                id,
+                position: map(position),
+            })
+        }
+        iced::Event::Touch(Touch::FingerLifted { id, position }) => {
+            iced::Event::Touch(Touch::FingerLifted {
+                id,
+                position: map(position),
+            })
+        }
+        iced::Event::Touch(Touch::FingerLost { id, position }) => {
+            iced::Event::Touch(Touch::FingerLost {
+                id,
+                position: map(position),
+            })
+        }
+        ref event => event.clone(),
+    }
+}
+
+/// An operation finding the focused text input of an interface.
+///
+/// Focusable widgets don't report their bounds, so the bounds of the innermost container
+/// holding the input are used instead.
+#[derive(Default)]
+pub struct FindFocusedInput {
+    containers: Vec<Rectangle>,
+    last_focused: bool,
+    pub bounds: Option<Rectangle>,
+}
+
+impl<T> Operation<T> for FindFocusedInput {
+    fn container(
+        &mut self,
+        _id: Option<&Id>,
+        bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+        self.containers.push(bounds);
+        operate_on_children(self);
+        self.containers.pop();
+    }
+
+    fn focusable(&mut self, state: &mut dyn Focusable, _id: Option<&Id>) {
+        self.last_focused = state.is_focused();
+    }
+
+    // Text inputs report their focus state right before this.
+    fn text_input(&mut self, _state: &mut dyn TextInput, _id: Option<&Id>) {
+        if std::mem::take(&mut self.last_focused) {
+            self.bounds = self.containers.last().copied();
+        }
+    }
+}
+
+/// A stage of the work done on an interface, traced in its own span.
+#[derive(Clone, Copy)]
+pub(crate) enum Stage {
+    Layout,
+    Event,
+    Draw,
+}
+
+/// Run a stage of `interface` in a `tracing` span named after it (`iced::layout`,
+/// `iced::event` or `iced::draw`), and time it for the overlay of `debug`.
+pub(crate) fn trace_stage<T>(
+    debug: &mut Debug,
+    stage: Stage,
+    interface: &str,
+    run: impl FnOnce() -> T,
+) -> T {
+    let span = match stage {
+        Stage::Layout => {
+            debug.layout_started();
+            info_span!("iced::layout", interface)
+        }
+        Stage::Event => {
+            debug.event_processing_started();
+            info_span!("iced::event", interface)
+        }
+        Stage::Draw => {
+            debug.draw_started();
+            info_span!("iced::draw", interface)
+        }
+    };
+    let result = span.in_scope(run);
+    match stage {
+        Stage::Layout => debug.layout_finished(),
+        Stage::Event => debug.event_processing_finished(),
+        Stage::Draw => debug.draw_finished(),
+    }
+    result
+}
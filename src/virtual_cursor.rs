@@ -0,0 +1,251 @@
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy_ecs::prelude::{EventReader, IntoSystemConfigs, Res, ResMut, Resource};
+use bevy_input::gamepad::{
+    Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads,
+};
+use bevy_input::{Axis, ButtonInput};
+use bevy_math::Vec2;
+use bevy_time::Time;
+use bevy_window::CursorMoved;
+use iced_core::Renderer as _;
+use iced_core::{mouse, Border, Color, Event as IcedEvent, Point, Rectangle, Shadow};
+use std::any::TypeId;
+use std::sync::PoisonError;
+
+use crate::render::DrawnLayer;
+use crate::systems::{self, IcedInteraction};
+use crate::{compat, IcedEventQueue, IcedResource, Renderer, ViewportResource};
+
+/// A cursor moved with the right stick of a gamepad, for using pointer-centric widgets
+/// without a mouse.
+///
+/// The virtual cursor becomes active when the stick is moved, and replaces the mouse cursor
+/// in every interface until the mouse moves again. Its buttons send the same events as the
+/// ones of a mouse.
+///
+/// Requires the [`IcedVirtualCursorPlugin`].
+#[derive(Resource, Clone, Debug)]
+pub struct IcedVirtualCursor {
+    /// Whether the gamepad can drive the cursor.
+    pub enabled: bool,
+    /// The position of the cursor, in logical pixels of the viewport.
+    pub position: Vec2,
+    /// The speed of the cursor with the stick fully tilted, in logical pixels per second.
+    pub speed: f32,
+    /// The exponent applied to the tilt of the stick. Values above `1.0` make small tilts
+    /// slower, for precise pointing.
+    pub acceleration: f32,
+    /// The tilt below which the stick is considered at rest.
+    pub dead_zone: f32,
+    /// The gamepad button acting as the left mouse button.
+    pub primary_button: GamepadButtonType,
+    /// The gamepad button acting as the right mouse button.
+    pub secondary_button: GamepadButtonType,
+    /// Whether the cursor is drawn over the interfaces while it is active.
+    pub visible: bool,
+    /// The diameter of the drawn cursor, in logical pixels, or the width of its
+    /// [`image`](Self::image).
+    pub size: f32,
+    /// The color of the drawn cursor, when it has no [`image`](Self::image).
+    pub color: Color,
+    /// The image the cursor is drawn with instead of a disc, e.g. a sprite of the game's
+    /// art style created with [`Handle::from_pixels`](iced_core::image::Handle::from_pixels).
+    ///
+    /// It is scaled to [`size`](Self::size) wide, keeping its aspect ratio.
+    #[cfg(feature = "cursor_image")]
+    pub image: Option<iced_core::image::Handle>,
+    /// The point of the [`image`](Self::image) at the position of the cursor, from `(0, 0)`
+    /// for its top left corner (e.g. the tip of an arrow) to `(1, 1)` for its bottom right
+    /// one. It is the center by default.
+    #[cfg(feature = "cursor_image")]
+    pub hotspot: Vec2,
+    active: bool,
+}
+
+impl Default for IcedVirtualCursor {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            position: Vec2::ZERO,
+            speed: 800.0,
+            acceleration: 2.0,
+            dead_zone: 0.15,
+            primary_button: GamepadButtonType::South,
+            secondary_button: GamepadButtonType::East,
+            visible: true,
+            size: 16.0,
+            color: Color::WHITE,
+            #[cfg(feature = "cursor_image")]
+            image: None,
+            #[cfg(feature = "cursor_image")]
+            hotspot: Vec2::splat(0.5),
+            active: false,
+        }
+    }
+}
+
+impl IcedVirtualCursor {
+    /// Whether the virtual cursor currently replaces the mouse cursor.
+    pub fn is_active(&self) -> bool {
+        self.enabled && self.active
+    }
+}
+
+/// Adds the [`IcedVirtualCursor`].
+pub struct IcedVirtualCursorPlugin;
+
+impl Plugin for IcedVirtualCursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<IcedVirtualCursor>()
+            .add_systems(PreUpdate, move_cursor.in_set(systems::InputSet::Sources))
+            .add_systems(PostUpdate, draw_cursor);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn move_cursor(
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    buttons: Res<ButtonInput<GamepadButton>>,
+    mut mouse_moves: EventReader<CursorMoved>,
+    time: Res<Time>,
+    viewport: Res<ViewportResource>,
+    mut cursor: ResMut<IcedVirtualCursor>,
+    mut event_queue: ResMut<IcedEventQueue>,
+    mut interaction: ResMut<IcedInteraction>,
+) {
+    if mouse_moves.read().count() > 0 {
+        cursor.active = false;
+    }
+
+    let stick = |gamepad: Gamepad| {
+        let axis = |axis_type| axes.get(GamepadAxis::new(gamepad, axis_type));
+        Vec2::new(
+            axis(GamepadAxisType::RightStickX).unwrap_or_default(),
+            // The stick points up for positive values, unlike the window coordinates.
+            -axis(GamepadAxisType::RightStickY).unwrap_or_default(),
+        )
+    };
+    let tilt = gamepads
+        .iter()
+        .map(stick)
+        .max_by(|a, b| a.length_squared().total_cmp(&b.length_squared()))
+        .unwrap_or_default();
+    let magnitude = tilt.length().min(1.0);
+    if cursor.enabled && magnitude > cursor.dead_zone {
+        if !cursor.active {
+            // Start from the center of the viewport the first time.
+            if cursor.position == Vec2::ZERO {
+                let size = viewport.logical_size();
+                cursor.position = Vec2::new(size.width, size.height) / 2.0;
+            }
+            cursor.active = true;
+        }
+        let size = viewport.logical_size();
+        let velocity = tilt.normalize() * magnitude.powf(cursor.acceleration) * cursor.speed;
+        cursor.position = (cursor.position + velocity * time.delta_seconds())
+            .clamp(Vec2::ZERO, Vec2::new(size.width, size.height));
+        event_queue.push(IcedEvent::Mouse(mouse::Event::CursorMoved {
+            position: Point::new(cursor.position.x, cursor.position.y),
+        }));
+    }
+
+    if cursor.is_active() {
+        let mapping = [
+            (cursor.primary_button, mouse::Button::Left),
+            (cursor.secondary_button, mouse::Button::Right),
+        ];
+        for gamepad in gamepads.iter() {
+            for (button_type, button) in mapping {
+                let gamepad_button = GamepadButton::new(gamepad, button_type);
+                if buttons.just_pressed(gamepad_button) {
+                    event_queue.push(IcedEvent::Mouse(mouse::Event::ButtonPressed(button)));
+                }
+                if buttons.just_released(gamepad_button) {
+                    event_queue.push(IcedEvent::Mouse(mouse::Event::ButtonReleased(button)));
+                }
+            }
+        }
+    }
+    interaction.virtual_cursor = cursor
+        .is_active()
+        .then(|| Point::new(cursor.position.x, cursor.position.y));
+}
+
+// Draw the cursor over the interfaces drawn this frame. When none was, the last frame is
+// presented again with the cursor where it was, since it didn't move.
+fn draw_cursor(cursor: Res<IcedVirtualCursor>, props: Res<IcedResource>) {
+    if !cursor.is_active() || !cursor.visible {
+        return;
+    }
+    let mut props = props.lock().unwrap_or_else(PoisonError::into_inner);
+    if props.layers.is_empty() {
+        return;
+    }
+    let radius = cursor.size / 2.0;
+    let renderer = &mut props.renderer;
+    renderer.clear();
+    if !draw_image(&cursor, renderer) {
+        renderer.fill_quad(
+            iced_core::renderer::Quad {
+                bounds: Rectangle::new(
+                    Point::new(cursor.position.x - radius, cursor.position.y - radius),
+                    iced_core::Size::new(cursor.size, cursor.size),
+                ),
+                border: Border {
+                    color: Color::BLACK,
+                    width: 1.0,
+                    radius: radius.into(),
+                },
+                shadow: Shadow::default(),
+            },
+            cursor.color,
+        );
+    }
+    let primitives = compat::primitives(renderer);
+    props.layers.push(DrawnLayer {
+        source: TypeId::of::<IcedVirtualCursor>(),
+        below_ui: false,
+        // Over every interface, whatever its layer.
+        layer: i32::MAX,
+        opacity: 1.0,
+        // The cursor would always hover itself.
+        hit_test: false,
+        target: None,
+        backdrops: Vec::new(),
+        primitives,
+    });
+}
+
+// Draw the image of the cursor, returning whether it has one.
+#[cfg(feature = "cursor_image")]
+fn draw_image(cursor: &IcedVirtualCursor, renderer: &mut Renderer) -> bool {
+    use iced_core::image::{FilterMethod, Renderer as _};
+
+    let Some(image) = &cursor.image else {
+        return false;
+    };
+    let dimensions = renderer.dimensions(image);
+    if dimensions.width == 0 {
+        return false;
+    }
+    let size = Vec2::new(
+        cursor.size,
+        cursor.size * dimensions.height as f32 / dimensions.width as f32,
+    );
+    let top_left = cursor.position - cursor.hotspot * size;
+    renderer.draw(
+        image.clone(),
+        FilterMethod::Linear,
+        Rectangle::new(
+            Point::new(top_left.x, top_left.y),
+            iced_core::Size::new(size.x, size.y),
+        ),
+    );
+    true
+}
+
+#[cfg(not(feature = "cursor_image"))]
+fn draw_image(_cursor: &IcedVirtualCursor, _renderer: &mut Renderer) -> bool {
+    false
+}
@@ -0,0 +1,113 @@
+use std::ops::Range;
+
+use iced_core::Length;
+use iced_widget::scrollable::Viewport;
+
+use crate::ui_types::Element;
+use crate::widgets::{self, Column, Scrollable, Space};
+
+/// The scroll position of a [`virtual_list`], and the rows it shows.
+///
+/// Keep it in the state of the application (e.g. a resource), replace it with the one sent
+/// by the list when it scrolls, and build the rows in [`visible_range`](Self::visible_range).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VirtualListState {
+    /// The distance the list is scrolled by, in logical pixels.
+    pub offset: f32,
+    /// The height of the visible part of the list, in logical pixels.
+    ///
+    /// It is only known once the list has been scrolled: set it to the expected height
+    /// beforehand, to build the right amount of rows on the first frames. It defaults to
+    /// the height of a 1080p screen.
+    pub viewport_height: f32,
+    /// The number of rows built above and below the visible ones, so that fast scrolling
+    /// doesn't show empty space for a frame.
+    pub overscan: usize,
+}
+
+impl Default for VirtualListState {
+    fn default() -> Self {
+        Self {
+            offset: 0.0,
+            viewport_height: 1080.0,
+            overscan: 2,
+        }
+    }
+}
+
+impl VirtualListState {
+    /// The rows of a list of `len` rows, `row_height` high, to build at this position.
+    pub fn visible_range(&self, len: usize, row_height: f32) -> Range<usize> {
+        let first = (self.offset / row_height).floor() as usize;
+        let last = ((self.offset + self.viewport_height) / row_height).ceil() as usize;
+        let start = first.saturating_sub(self.overscan).min(len);
+        let end = last.saturating_add(self.overscan).min(len);
+        start..end
+    }
+
+    fn scrolled(self, viewport: Viewport) -> Self {
+        Self {
+            offset: viewport.absolute_offset().y,
+            viewport_height: viewport.bounds().height,
+            ..self
+        }
+    }
+}
+
+/// Creates a vertical list of `len` rows of `row_height`, of which only the `rows` in the
+/// [`visible_range`](VirtualListState::visible_range) of `state` are built.
+///
+/// The rest of the list is filled with empty space, so the scrollbar reflects the whole
+/// list. Whenever the list scrolls, it sends the message built by `on_scroll` with its new
+/// state, from which the application builds the rows of the next frame: only a screenful
+/// of rows is ever built, however long the list is (e.g. an inspector listing the entities
+/// of a query with `query.iter().skip(range.start).take(range.len())`).
+pub fn virtual_list<'a, Message: 'a>(
+    state: VirtualListState,
+    len: usize,
+    row_height: f32,
+    rows: impl IntoIterator<Item = Element<'a, Message>>,
+    on_scroll: impl Fn(VirtualListState) -> Message + 'a,
+) -> Scrollable<'a, Message> {
+    let range = state.visible_range(len, row_height);
+    let rows = rows
+        .into_iter()
+        .take(range.len())
+        .map(|row| widgets::container(row).height(row_height).into());
+    let content = Column::new()
+        .width(Length::Fill)
+        .push(Space::with_height(range.start as f32 * row_height))
+        .push(Column::with_children(rows).width(Length::Fill))
+        .push(Space::with_height((len - range.end) as f32 * row_height));
+    widgets::scrollable(content).on_scroll(move |viewport| on_scroll(state.scrolled(viewport)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(offset: f32) -> VirtualListState {
+        VirtualListState {
+            offset,
+            viewport_height: 100.0,
+            overscan: 2,
+        }
+    }
+
+    #[test]
+    fn ranges_include_the_overscan() {
+        assert_eq!(state(0.0).visible_range(100, 10.0), 0..12);
+        assert_eq!(state(500.0).visible_range(100, 10.0), 48..62);
+        // Partially visible rows are built.
+        assert_eq!(state(505.0).visible_range(100, 10.0), 48..63);
+    }
+
+    #[test]
+    fn ranges_are_clamped_to_the_end_of_the_list() {
+        assert_eq!(state(950.0).visible_range(100, 10.0), 93..100);
+        assert_eq!(state(0.0).visible_range(5, 10.0), 0..5);
+        // The list shrank below the scroll position.
+        assert_eq!(state(5000.0).visible_range(100, 10.0), 100..100);
+        assert_eq!(state(0.0).visible_range(0, 10.0), 0..0);
+    }
+}
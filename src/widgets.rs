@@ -0,0 +1,228 @@
+use std::hash::Hash;
+use std::ops::RangeInclusive;
+
+use iced_core::text::highlighter::PlainText;
+use iced_core::text::Shaping;
+use iced_core::Size;
+use iced_widget::container::{Appearance, StyleSheet};
+use iced_widget::style::theme;
+
+use crate::ui_types::{
+    Alignment, Background, Border, Color, Element, Length, Renderer, Shadow, Theme,
+};
+use crate::TextDirection;
+
+/// A piece of text.
+pub type Text<'a> = iced_widget::Text<'a, Theme, Renderer>;
+/// A clickable button.
+pub type Button<'a, Message> = iced_widget::Button<'a, Message, Theme, Renderer>;
+/// A vertical list of widgets.
+pub type Column<'a, Message> = iced_widget::Column<'a, Message, Theme, Renderer>;
+/// A horizontal list of widgets.
+pub type Row<'a, Message> = iced_widget::Row<'a, Message, Theme, Renderer>;
+/// A box that positions and styles its contents.
+pub type Container<'a, Message> = iced_widget::Container<'a, Message, Theme, Renderer>;
+/// A scrollable area.
+pub type Scrollable<'a, Message> = iced_widget::Scrollable<'a, Message, Theme, Renderer>;
+/// A horizontal bar to select a value in a range.
+pub type Slider<'a, T, Message> = iced_widget::Slider<'a, T, Message, Theme>;
+/// A field that can be filled with text.
+pub type TextInput<'a, Message> = iced_widget::TextInput<'a, Message, Theme, Renderer>;
+/// An empty amount of space.
+pub type Space = iced_widget::Space;
+/// A multi-line text editor.
+pub type TextEditor<'a, Message> = iced_widget::TextEditor<'a, PlainText, Message, Theme, Renderer>;
+/// The text edited by a [`TextEditor`], with its cursor and selection.
+pub type TextEditorContent = iced_widget::text_editor::Content<Renderer>;
+
+/// Creates a new [`Text`] widget with the given content.
+///
+/// The text is shaped with [`Shaping::Advanced`], which handles ligatures, complex scripts
+/// and bidirectional text.
+pub fn text<'a>(content: impl ToString) -> Text<'a> {
+    iced_widget::text(content).shaping(Shaping::Advanced)
+}
+
+/// Creates a new [`Text`] widget aligned to the start of the given direction.
+pub fn text_with_direction<'a>(content: impl ToString, direction: TextDirection) -> Text<'a> {
+    text(content).horizontal_alignment(direction.start())
+}
+
+/// Creates a new [`Button`] with the given content.
+pub fn button<'a, Message: Clone + 'a>(
+    content: impl Into<Element<'a, Message>>,
+) -> Button<'a, Message> {
+    iced_widget::button(content)
+}
+
+/// Creates a new [`Column`] with the given children.
+pub fn column<'a, Message>(children: Vec<Element<'a, Message>>) -> Column<'a, Message> {
+    Column::with_children(children)
+}
+
+/// Creates a new [`Row`] with the given children.
+pub fn row<'a, Message>(children: Vec<Element<'a, Message>>) -> Row<'a, Message> {
+    Row::with_children(children)
+}
+
+/// Creates a new [`Row`] with the given children, listed in reading order: they are laid
+/// out from right to left for [`TextDirection::RightToLeft`].
+///
+/// Interfaces displayed with
+/// [`IcedContext::display_with_direction`](crate::IcedContext::display_with_direction) are
+/// mirrored already, and use [`row`] instead.
+pub fn row_with_direction<'a, Message>(
+    children: Vec<Element<'a, Message>>,
+    direction: TextDirection,
+) -> Row<'a, Message> {
+    Row::with_children(direction.order(children))
+}
+
+/// Creates a new [`Container`] with the given content.
+pub fn container<'a, Message>(content: impl Into<Element<'a, Message>>) -> Container<'a, Message> {
+    iced_widget::container(content)
+}
+
+/// The style of a [`Container`] drawn as a panel, with a background that can be a
+/// [`Gradient`](iced_core::Gradient) and a drop shadow.
+///
+/// ```ignore
+/// container(content).style(PanelStyle {
+///     background: Some(Linear::new(Degrees(90.0)).add_stop(0.0, top).add_stop(1.0, bottom).into()),
+///     border: Border::with_radius(8.0),
+///     shadow: Shadow {
+///         color: Color::from_rgba(0.0, 0.0, 0.0, 0.4),
+///         offset: Vector::new(0.0, 4.0),
+///         blur_radius: 12.0,
+///     },
+///     ..Default::default()
+/// })
+/// ```
+///
+/// The shadow is drawn around the bounds of the container, so leave some space for it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PanelStyle {
+    /// The background of the panel, if any.
+    pub background: Option<Background>,
+    /// The border of the panel, including its corner radius.
+    pub border: Border,
+    /// The shadow cast by the panel.
+    pub shadow: Shadow,
+    /// The color of the text in the panel, instead of the one of the theme.
+    pub text_color: Option<Color>,
+}
+
+impl StyleSheet for PanelStyle {
+    type Style = Theme;
+
+    fn appearance(&self, _theme: &Theme) -> Appearance {
+        Appearance {
+            text_color: self.text_color,
+            background: self.background,
+            border: self.border,
+            shadow: self.shadow,
+        }
+    }
+}
+
+impl From<PanelStyle> for theme::Container {
+    fn from(style: PanelStyle) -> Self {
+        theme::Container::Custom(Box::new(style))
+    }
+}
+
+/// Creates a new vertical [`Scrollable`] with the given content.
+pub fn scrollable<'a, Message>(
+    content: impl Into<Element<'a, Message>>,
+) -> Scrollable<'a, Message> {
+    iced_widget::scrollable(content)
+}
+
+/// Creates a new [`Slider`] over `range`, producing a message with `on_change` whenever
+/// the value is dragged.
+pub fn slider<'a, T, Message>(
+    range: RangeInclusive<T>,
+    value: T,
+    on_change: impl Fn(T) -> Message + 'a,
+) -> Slider<'a, T, Message>
+where
+    T: Copy + From<u8> + PartialOrd,
+    Message: Clone,
+{
+    iced_widget::slider(range, value, on_change)
+}
+
+/// Creates a new [`TextInput`] with the given placeholder and value.
+pub fn text_input<'a, Message: Clone>(placeholder: &str, value: &str) -> TextInput<'a, Message> {
+    iced_widget::text_input(placeholder, value)
+}
+
+/// Creates a new [`TextEditor`] editing `content`.
+///
+/// The editor sends an [`Action`](iced_widget::text_editor::Action) for every edit, through
+/// the message built by [`on_action`](TextEditor::on_action). Apply it to `content` with
+/// [`TextEditorContent::perform`] when handling the message.
+pub fn text_editor<'a, Message: Clone>(content: &'a TextEditorContent) -> TextEditor<'a, Message> {
+    iced_widget::text_editor(content)
+}
+
+/// Creates a new [`Space`] of the given size.
+pub fn space(width: impl Into<Length>, height: impl Into<Length>) -> Space {
+    Space::new(width, height)
+}
+
+/// Creates a subtree that is only rebuilt when `key` changes.
+///
+/// `view` is called the first time the subtree is displayed, and again whenever the hash of
+/// `key` differs from the previous frame; the widgets it built are reused otherwise. The
+/// subtree is still laid out and drawn every frame. Since it is kept across frames, it can't
+/// borrow from the state of the calling system: `key` should capture everything it depends on.
+pub fn memo<'a, Message, K, E>(key: K, view: impl Fn() -> E + 'a) -> Element<'a, Message>
+where
+    Message: 'static,
+    K: Hash + 'a,
+    E: Into<Element<'static, Message>> + 'static,
+{
+    iced_widget::lazy(key, move |_| view()).into()
+}
+
+/// Creates a subtree built from the size available to it, for layouts adapting to the space
+/// they are given.
+///
+/// `view` is called again whenever the size changes, and the interface is updated on the
+/// next frame to lay out the new subtree.
+pub fn responsive<'a, Message: 'a>(
+    view: impl Fn(Size) -> Element<'a, Message> + 'a,
+) -> Element<'a, Message> {
+    iced_widget::responsive(view).into()
+}
+
+/// Horizontal alignment of the children of a [`Column`].
+///
+/// This uses the method name of newer Iced releases, so code written against it keeps
+/// compiling when `bevy_iced` moves to them.
+pub trait AlignX {
+    /// Sets the horizontal alignment of the contents.
+    fn align_x(self, align: Alignment) -> Self;
+}
+
+impl<'a, Message> AlignX for Column<'a, Message> {
+    fn align_x(self, align: Alignment) -> Self {
+        self.align_items(align)
+    }
+}
+
+/// Vertical alignment of the children of a [`Row`].
+///
+/// This uses the method name of newer Iced releases, so code written against it keeps
+/// compiling when `bevy_iced` moves to them.
+pub trait AlignY {
+    /// Sets the vertical alignment of the contents.
+    fn align_y(self, align: Alignment) -> Self;
+}
+
+impl<'a, Message> AlignY for Row<'a, Message> {
+    fn align_y(self, align: Alignment) -> Self {
+        self.align_items(align)
+    }
+}
@@ -0,0 +1,644 @@
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::event::Event;
+use bevy_ecs::prelude::{Entity, EventReader, Query, With};
+use bevy_ecs::system::NonSend;
+use bevy_utils::tracing::warn;
+use bevy_window::PrimaryWindow;
+use bevy_winit::WinitWindows;
+use iced_core::widget::tree::{self, Tree};
+use iced_core::widget::{Operation, Widget};
+use iced_core::{
+    event, layout, mouse, overlay, renderer, touch, Clipboard, Element, Event as IcedEvent, Layout,
+    Length, Point, Rectangle, Shell, Size, Vector,
+};
+use winit::window::{Icon, ResizeDirection, UserAttentionType};
+
+/// A request to the OS shell about the primary window.
+///
+/// Send these from the handlers of UI messages, for example to show the progress of a
+/// long-running operation triggered from the interface in the taskbar and flash its entry
+/// when it completes, or to move a window without decorations from its custom title bar.
+#[derive(Event, Clone, Debug)]
+pub enum WindowShellRequest {
+    /// Set the window icon from RGBA8 pixels, or reset it with `None`.
+    SetIcon(Option<WindowIcon>),
+    /// Request the user's attention, e.g. by flashing the taskbar entry.
+    /// Critical requests keep going until the window is focused.
+    RequestAttention {
+        /// Whether the request is critical.
+        critical: bool,
+    },
+    /// Cancel a previous attention request.
+    ClearAttention,
+    /// Show progress on the taskbar entry of the window, or hide it with
+    /// [`TaskbarProgress::None`].
+    ///
+    /// This is only supported on Windows, and ignored on other platforms.
+    SetProgress(TaskbarProgress),
+    /// Move the window with the cursor, while the left mouse button is held.
+    ///
+    /// Send this from the message of a [`drag_region`], for the custom title bar of a
+    /// window without decorations.
+    StartDrag,
+    /// Resize the window from `edge` with the cursor, while the left mouse button is held.
+    ///
+    /// Send this from the messages of [`resize_edges`], for a window without decorations.
+    StartResize(ResizeEdge),
+}
+
+/// An edge or corner of a window, to resize it from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResizeEdge {
+    /// The top edge.
+    North,
+    /// The bottom edge.
+    South,
+    /// The right edge.
+    East,
+    /// The left edge.
+    West,
+    /// The top right corner.
+    NorthEast,
+    /// The top left corner.
+    NorthWest,
+    /// The bottom right corner.
+    SouthEast,
+    /// The bottom left corner.
+    SouthWest,
+}
+
+impl ResizeEdge {
+    // The edge of `bounds` that `position` is within `thickness` of, corners first.
+    fn at(bounds: Rectangle, position: Point, thickness: f32) -> Option<Self> {
+        if !bounds.contains(position) {
+            return None;
+        }
+        let north = position.y < bounds.y + thickness;
+        let south = position.y >= bounds.y + bounds.height - thickness;
+        let west = position.x < bounds.x + thickness;
+        let east = position.x >= bounds.x + bounds.width - thickness;
+        Some(match (north, south, west, east) {
+            (true, _, true, _) => Self::NorthWest,
+            (true, _, _, true) => Self::NorthEast,
+            (_, true, true, _) => Self::SouthWest,
+            (_, true, _, true) => Self::SouthEast,
+            (true, ..) => Self::North,
+            (_, true, ..) => Self::South,
+            (.., true, _) => Self::West,
+            (.., true) => Self::East,
+            _ => return None,
+        })
+    }
+
+    fn interaction(self) -> mouse::Interaction {
+        match self {
+            Self::East | Self::West => mouse::Interaction::ResizingHorizontally,
+            _ => mouse::Interaction::ResizingVertically,
+        }
+    }
+}
+
+impl From<ResizeEdge> for ResizeDirection {
+    fn from(edge: ResizeEdge) -> Self {
+        match edge {
+            ResizeEdge::North => Self::North,
+            ResizeEdge::South => Self::South,
+            ResizeEdge::East => Self::East,
+            ResizeEdge::West => Self::West,
+            ResizeEdge::NorthEast => Self::NorthEast,
+            ResizeEdge::NorthWest => Self::NorthWest,
+            ResizeEdge::SouthEast => Self::SouthEast,
+            ResizeEdge::SouthWest => Self::SouthWest,
+        }
+    }
+}
+
+/// The progress shown on the taskbar entry of a window, see
+/// [`WindowShellRequest::SetProgress`].
+///
+/// Progress values go from `0.0` to `1.0`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TaskbarProgress {
+    /// No progress is shown.
+    #[default]
+    None,
+    /// An operation is running, but its progress is unknown.
+    Indeterminate,
+    /// An operation is running.
+    Normal(f32),
+    /// An operation is paused.
+    Paused(f32),
+    /// An operation failed.
+    Error(f32),
+}
+
+/// The pixels of a window icon.
+#[derive(Clone, Debug)]
+pub struct WindowIcon {
+    /// The pixels, in RGBA8 order.
+    pub rgba: Vec<u8>,
+    /// The width of the icon in pixels.
+    pub width: u32,
+    /// The height of the icon in pixels.
+    pub height: u32,
+}
+
+/// Applies [`WindowShellRequest`]s to the primary window.
+pub struct WindowShellPlugin;
+
+impl Plugin for WindowShellPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<WindowShellRequest>()
+            .add_systems(PostUpdate, apply_shell_requests);
+    }
+}
+
+fn apply_shell_requests(
+    mut requests: EventReader<WindowShellRequest>,
+    windows: Query<Entity, With<PrimaryWindow>>,
+    winit_windows: NonSend<WinitWindows>,
+) {
+    let Some(window) = windows
+        .get_single()
+        .ok()
+        .and_then(|entity| winit_windows.get_window(entity))
+    else {
+        requests.clear();
+        return;
+    };
+    for request in requests.read() {
+        match request {
+            WindowShellRequest::SetIcon(None) => window.set_window_icon(None),
+            WindowShellRequest::SetIcon(Some(icon)) => {
+                match Icon::from_rgba(icon.rgba.clone(), icon.width, icon.height) {
+                    Ok(icon) => window.set_window_icon(Some(icon)),
+                    Err(err) => warn!("Invalid window icon: {err}"),
+                }
+            }
+            WindowShellRequest::RequestAttention { critical } => {
+                window.request_user_attention(Some(if *critical {
+                    UserAttentionType::Critical
+                } else {
+                    UserAttentionType::Informational
+                }));
+            }
+            WindowShellRequest::ClearAttention => window.request_user_attention(None),
+            WindowShellRequest::SetProgress(progress) => {
+                if let Err(err) = taskbar::set_progress(window, *progress) {
+                    warn!("Can't set the taskbar progress: {err}");
+                }
+            }
+            WindowShellRequest::StartDrag => {
+                if let Err(err) = window.drag_window() {
+                    warn!("Can't drag the window: {err}");
+                }
+            }
+            WindowShellRequest::StartResize(edge) => {
+                if let Err(err) = window.drag_resize_window((*edge).into()) {
+                    warn!("Can't resize the window: {err}");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod taskbar {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_INPROC_SERVER,
+        COINIT_APARTMENTTHREADED,
+    };
+    use windows::Win32::UI::Shell::{
+        ITaskbarList3, TaskbarList, TBPF_ERROR, TBPF_INDETERMINATE, TBPF_NOPROGRESS, TBPF_NORMAL,
+        TBPF_PAUSED,
+    };
+    use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
+    use winit::window::Window;
+
+    use super::TaskbarProgress;
+
+    // The resolution of progress values given to the taskbar.
+    const TOTAL: u64 = 10_000;
+
+    #[allow(unsafe_code)]
+    pub fn set_progress(
+        window: &Window,
+        progress: TaskbarProgress,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let RawWindowHandle::Win32(handle) = window.window_handle()?.as_raw() else {
+            return Ok(());
+        };
+        let hwnd = HWND(handle.hwnd.get());
+        let (flags, value) = match progress {
+            TaskbarProgress::None => (TBPF_NOPROGRESS, None),
+            TaskbarProgress::Indeterminate => (TBPF_INDETERMINATE, None),
+            TaskbarProgress::Normal(value) => (TBPF_NORMAL, Some(value)),
+            TaskbarProgress::Paused(value) => (TBPF_PAUSED, Some(value)),
+            TaskbarProgress::Error(value) => (TBPF_ERROR, Some(value)),
+        };
+        // SAFETY: this runs on the main thread, which owns the window. The taskbar is released
+        // before COM is uninitialized, balancing the initialization made for it.
+        unsafe {
+            let initialized = CoInitializeEx(None, COINIT_APARTMENTTHREADED).is_ok();
+            let result = (|| {
+                let taskbar: ITaskbarList3 =
+                    CoCreateInstance(&TaskbarList, None, CLSCTX_INPROC_SERVER)?;
+                taskbar.HrInit()?;
+                taskbar.SetProgressState(hwnd, flags)?;
+                if let Some(value) = value {
+                    let completed = (value.clamp(0.0, 1.0) * TOTAL as f32) as u64;
+                    taskbar.SetProgressValue(hwnd, completed, TOTAL)?;
+                }
+                windows::core::Result::Ok(())
+            })();
+            if initialized {
+                CoUninitialize();
+            }
+            result?;
+        }
+        Ok(())
+    }
+}
+
+// Taskbar progress is only supported on Windows.
+#[cfg(not(target_os = "windows"))]
+mod taskbar {
+    use winit::window::Window;
+
+    use super::TaskbarProgress;
+
+    pub fn set_progress(
+        _window: &Window,
+        _progress: TaskbarProgress,
+    ) -> Result<(), std::convert::Infallible> {
+        Ok(())
+    }
+}
+
+/// A widget sending a message when it is pressed where its content ignores the press, to
+/// drag the window with [`WindowShellRequest::StartDrag`].
+///
+/// Wrap the title bar of a window without decorations in it: buttons and other interactive
+/// widgets of the title bar keep their presses, and the rest of it moves the window.
+///
+/// ```ignore
+/// let close = button("x").on_press(UiMessage::Close);
+/// let title_bar = row![text("My tool"), horizontal_space(), close];
+/// drag_region(title_bar, UiMessage::DragWindow).on_double_click(UiMessage::Maximize)
+/// ```
+pub struct DragRegion<'a, Message, Theme, Renderer> {
+    content: Element<'a, Message, Theme, Renderer>,
+    on_drag: Message,
+    on_double_click: Option<Message>,
+}
+
+/// Creates a [`DragRegion`] around `content`, sending `on_drag` when it is pressed.
+pub fn drag_region<'a, Message, Theme, Renderer>(
+    content: impl Into<Element<'a, Message, Theme, Renderer>>,
+    on_drag: Message,
+) -> DragRegion<'a, Message, Theme, Renderer> {
+    DragRegion {
+        content: content.into(),
+        on_drag,
+        on_double_click: None,
+    }
+}
+
+impl<'a, Message, Theme, Renderer> DragRegion<'a, Message, Theme, Renderer> {
+    /// Send `message` instead of the drag message when the region is double-clicked,
+    /// e.g. to maximize the window.
+    pub fn on_double_click(mut self, message: Message) -> Self {
+        self.on_double_click = Some(message);
+        self
+    }
+}
+
+// The last click on the region, to detect double clicks.
+#[derive(Default)]
+struct DragState {
+    last_click: Option<mouse::Click>,
+}
+
+impl<'a, Message: Clone, Theme, Renderer: iced_core::Renderer> Widget<Message, Theme, Renderer>
+    for DragRegion<'a, Message, Theme, Renderer>
+{
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.content.as_widget().size_hint()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<DragState>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(DragState::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: IcedEvent,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        let status = self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event.clone(),
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        );
+        if status == event::Status::Captured {
+            return status;
+        }
+        let pressed = matches!(
+            event,
+            IcedEvent::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+                | IcedEvent::Touch(touch::Event::FingerPressed { .. })
+        );
+        let Some(position) = cursor.position_over(layout.bounds()).filter(|_| pressed) else {
+            return status;
+        };
+        let state = tree.state.downcast_mut::<DragState>();
+        let click = mouse::Click::new(position, state.last_click);
+        state.last_click = Some(click);
+        match (&self.on_double_click, click.kind()) {
+            (Some(message), mouse::click::Kind::Double) => shell.publish(message.clone()),
+            _ => shell.publish(self.on_drag.clone()),
+        }
+        event::Status::Captured
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer, translation)
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<DragRegion<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: Clone + 'a,
+    Theme: 'a,
+    Renderer: iced_core::Renderer + 'a,
+{
+    fn from(region: DragRegion<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(region)
+    }
+}
+
+/// A widget sending a message when it is pressed near its edges, to resize the window with
+/// [`WindowShellRequest::StartResize`].
+///
+/// Wrap the whole interface of a window without decorations in it, so that its edges can
+/// be grabbed like the ones drawn by the OS. The cursor shows a resize icon over them.
+///
+/// ```ignore
+/// resize_edges(content, |edge| UiMessage::ResizeWindow(edge)).thickness(6.0)
+/// ```
+pub struct ResizeEdges<'a, Message, Theme, Renderer> {
+    content: Element<'a, Message, Theme, Renderer>,
+    on_resize: Box<dyn Fn(ResizeEdge) -> Message + 'a>,
+    thickness: f32,
+}
+
+/// Creates [`ResizeEdges`] around `content`, sending the message built by `on_resize` when
+/// one of its edges is pressed.
+pub fn resize_edges<'a, Message, Theme, Renderer>(
+    content: impl Into<Element<'a, Message, Theme, Renderer>>,
+    on_resize: impl Fn(ResizeEdge) -> Message + 'a,
+) -> ResizeEdges<'a, Message, Theme, Renderer> {
+    ResizeEdges {
+        content: content.into(),
+        on_resize: Box::new(on_resize),
+        thickness: 4.0,
+    }
+}
+
+impl<'a, Message, Theme, Renderer> ResizeEdges<'a, Message, Theme, Renderer> {
+    /// Set the thickness of the edges, in logical pixels. It is `4` by default.
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    fn edge(&self, layout: Layout<'_>, cursor: mouse::Cursor) -> Option<ResizeEdge> {
+        ResizeEdge::at(layout.bounds(), cursor.position()?, self.thickness)
+    }
+}
+
+impl<'a, Message, Theme, Renderer: iced_core::Renderer> Widget<Message, Theme, Renderer>
+    for ResizeEdges<'a, Message, Theme, Renderer>
+{
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.content.as_widget().size_hint()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content.as_widget().layout(tree, renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content
+            .as_widget()
+            .draw(tree, renderer, theme, style, layout, cursor, viewport);
+    }
+
+    fn tag(&self) -> tree::Tag {
+        self.content.as_widget().tag()
+    }
+
+    fn state(&self) -> tree::State {
+        self.content.as_widget().state()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.content.as_widget().children()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        self.content.as_widget().diff(tree);
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(tree, layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: IcedEvent,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        // The edges are over the content, which doesn't see the presses on them.
+        if let IcedEvent::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event {
+            if let Some(edge) = self.edge(layout, cursor) {
+                shell.publish((self.on_resize)(edge));
+                return event::Status::Captured;
+            }
+        }
+        self.content.as_widget_mut().on_event(
+            tree, event, layout, cursor, renderer, clipboard, shell, viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        match self.edge(layout, cursor) {
+            Some(edge) => edge.interaction(),
+            None => self
+                .content
+                .as_widget()
+                .mouse_interaction(tree, layout, cursor, viewport, renderer),
+        }
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(tree, layout, renderer, translation)
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<ResizeEdges<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: 'a,
+    Renderer: iced_core::Renderer + 'a,
+{
+    fn from(edges: ResizeEdges<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(edges)
+    }
+}
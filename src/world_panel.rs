@@ -0,0 +1,272 @@
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy_asset::{AssetId, Assets, Handle};
+use bevy_ecs::prelude::{Component, Entity, IntoSystemConfigs, Query};
+use bevy_ecs::system::{Commands, Local, Res, ResMut, Resource};
+use bevy_math::{Ray3d, UVec2, Vec2};
+use bevy_render::render_asset::RenderAssets;
+use bevy_render::render_resource::Extent3d;
+use bevy_render::renderer::{render_system, RenderDevice, RenderQueue};
+use bevy_render::texture::Image;
+use bevy_render::{Extract, ExtractSchedule, Render, RenderApp, RenderSet};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
+use iced_core::mouse::{self, Cursor};
+use iced_core::{Color, Event as IcedEvent, Point, Size};
+use iced_widget::graphics::Viewport;
+use iced_widget::style::Theme;
+
+use crate::render::{self, LayerTarget, PresentedFrame};
+use crate::systems;
+use crate::{DisplayStatus, IcedContext, IcedError, IcedEventQueue, Renderer};
+
+/// A panel in the world that Iced interfaces are displayed on, with
+/// [`IcedContext::display_in_panel`].
+///
+/// The interfaces are rendered once per frame into the [`image`](Self::image) of the panel,
+/// in an sRGB format. Show it on a quad with an unlit material: every camera looking at the
+/// quad, like both eyes of an XR headset, samples the same texture.
+///
+/// ```ignore
+/// let panel = IcedWorldPanel::new(&mut images, UVec2::new(1024, 768), 2.0);
+/// let material = materials.add(StandardMaterial {
+///     base_color_texture: Some(panel.image().clone()),
+///     unlit: true,
+///     ..default()
+/// });
+/// commands.spawn((
+///     PbrBundle {
+///         mesh: meshes.add(Rectangle::new(1.0, 0.75)),
+///         material,
+///         ..default()
+///     },
+///     panel,
+/// ));
+/// ```
+///
+/// Panels don't receive the input of the window: point at them by setting
+/// [`pointer`](Self::pointer) and [`pressed`](Self::pressed), e.g. from the ray of a
+/// controller with [`pointer_from_ray`](Self::pointer_from_ray).
+///
+/// Requires the [`IcedWorldPanelPlugin`].
+#[derive(Component)]
+pub struct IcedWorldPanel {
+    /// The resolution of the texture, in physical pixels.
+    pub size: UVec2,
+    /// The scale factor of the interfaces, e.g. `2.0` to keep text sharp on a panel seen
+    /// up close.
+    pub scale_factor: f64,
+    /// The position of the pointer on the panel, in logical pixels, if it points at it.
+    pub pointer: Option<Vec2>,
+    /// Whether the primary button of the pointer (e.g. the trigger of a controller) is
+    /// pressed.
+    pub pressed: bool,
+    image: Handle<Image>,
+    // The pointer as of the last frame, to send the events of its changes.
+    last_pointer: Option<Vec2>,
+    last_pressed: bool,
+}
+
+impl IcedWorldPanel {
+    /// Create a panel of `size` physical pixels, with its image.
+    pub fn new(images: &mut Assets<Image>, size: UVec2, scale_factor: f64) -> Self {
+        Self {
+            size,
+            scale_factor,
+            pointer: None,
+            pressed: false,
+            image: images.add(render::target_image(extent(size))),
+            last_pointer: None,
+            last_pressed: false,
+        }
+    }
+
+    /// The texture the interfaces of the panel are rendered into.
+    pub fn image(&self) -> &Handle<Image> {
+        &self.image
+    }
+
+    /// The size of the panel, in logical pixels.
+    pub fn logical_size(&self) -> Vec2 {
+        self.size.as_vec2() / self.scale_factor as f32
+    }
+
+    /// The position on the panel hit by `ray`, in logical pixels, for use as the
+    /// [`pointer`](Self::pointer).
+    ///
+    /// The panel is shown on a quad of `quad_size` world units, centered on `transform`
+    /// and facing its local Z axis, like a `Rectangle` mesh. `None` is returned when the
+    /// ray misses the quad.
+    pub fn pointer_from_ray(
+        &self,
+        ray: Ray3d,
+        transform: &GlobalTransform,
+        quad_size: Vec2,
+    ) -> Option<Vec2> {
+        let world_to_quad = transform.compute_matrix().inverse();
+        let origin = world_to_quad.transform_point3(ray.origin);
+        let direction = world_to_quad.transform_vector3(*ray.direction);
+        if direction.z.abs() <= f32::EPSILON {
+            return None;
+        }
+        let distance = -origin.z / direction.z;
+        if distance < 0.0 {
+            return None;
+        }
+        let hit = origin + direction * distance;
+        // The texture goes down the quad, from its top left corner.
+        let uv = Vec2::new(hit.x / quad_size.x + 0.5, 0.5 - hit.y / quad_size.y);
+        let inside = (0.0..=1.0).contains(&uv.x) && (0.0..=1.0).contains(&uv.y);
+        inside.then(|| uv * self.logical_size())
+    }
+}
+
+/// The panels that interfaces can be displayed on, to pass to
+/// [`IcedContext::display_in_panel`].
+pub type IcedWorldPanels<'w, 's> = Query<'w, 's, &'static IcedWorldPanel>;
+
+impl<'w, 's, M: bevy_ecs::event::Event> IcedContext<'w, 's, M> {
+    /// Display an [`Element`](iced_core::Element) on the [`IcedWorldPanel`] of `panel`.
+    ///
+    /// The interface fills the panel, and only processes the pointer of the panel. Several
+    /// interfaces can be displayed on a panel, each over the previous ones, but each
+    /// message type has a single widget state per panel.
+    pub fn display_in_panel<'a>(
+        &'a mut self,
+        panels: &IcedWorldPanels,
+        panel: Entity,
+        element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
+    ) -> DisplayStatus {
+        let Ok(world_panel) = panels.get(panel) else {
+            self.errors.report(IcedError::NoWindow);
+            return DisplayStatus::default();
+        };
+        let cursor = match world_panel.pointer {
+            Some(pointer) => Cursor::Available(Point::new(pointer.x, pointer.y)),
+            None => Cursor::Unavailable,
+        };
+        let size = world_panel.size.max(UVec2::ONE);
+        let target = LayerTarget {
+            entity: panel,
+            viewport: Viewport::with_physical_size(
+                Size::new(size.x, size.y),
+                world_panel.scale_factor,
+            ),
+            background: Color::TRANSPARENT,
+        };
+        self.display_in_target(element.into(), target, cursor, true)
+    }
+}
+
+/// Sends the pointer events of the [`IcedWorldPanel`]s, and renders their interfaces into
+/// their images.
+pub struct IcedWorldPanelPlugin;
+
+impl Plugin for IcedWorldPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            send_pointer_events
+                .after(systems::InputSet::Replay)
+                .before(systems::detect_updates),
+        )
+        .add_systems(PostUpdate, resize_panel_images);
+    }
+
+    fn finish(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<ExtractedPanels>()
+            .add_systems(ExtractSchedule, extract_panels)
+            .add_systems(
+                Render,
+                present_panels
+                    .in_set(RenderSet::Render)
+                    .before(render_system),
+            );
+    }
+}
+
+fn extent(size: UVec2) -> Extent3d {
+    let size = size.max(UVec2::ONE);
+    Extent3d {
+        width: size.x,
+        height: size.y,
+        depth_or_array_layers: 1,
+    }
+}
+
+// Turn the changes of the pointers into the events of their panels.
+fn send_pointer_events(
+    mut panels: Query<(Entity, &mut IcedWorldPanel)>,
+    mut event_queue: ResMut<IcedEventQueue>,
+) {
+    // The redraw tick is always first, and drives animations.
+    let tick = event_queue.first().cloned();
+    for (entity, mut panel) in &mut panels {
+        let queue = event_queue.window(entity);
+        queue.extend(tick.clone());
+        if panel.pointer != panel.last_pointer {
+            queue.push(IcedEvent::Mouse(match panel.pointer {
+                Some(pointer) => mouse::Event::CursorMoved {
+                    position: Point::new(pointer.x, pointer.y),
+                },
+                None => mouse::Event::CursorLeft,
+            }));
+            panel.last_pointer = panel.pointer;
+        }
+        if panel.pressed != panel.last_pressed {
+            queue.push(IcedEvent::Mouse(if panel.pressed {
+                mouse::Event::ButtonPressed(mouse::Button::Left)
+            } else {
+                mouse::Event::ButtonReleased(mouse::Button::Left)
+            }));
+            panel.last_pressed = panel.pressed;
+        }
+    }
+}
+
+fn resize_panel_images(panels: Query<&IcedWorldPanel>, mut images: ResMut<Assets<Image>>) {
+    for panel in &panels {
+        let size = extent(panel.size);
+        // Borrowing the image mutably marks it as changed, and uploads it again.
+        let resized = images
+            .get(&panel.image)
+            .is_some_and(|image| image.texture_descriptor.size != size);
+        if resized {
+            if let Some(image) = images.get_mut(&panel.image) {
+                image.resize(size);
+            }
+        }
+    }
+}
+
+// The textures of the panels, in the render world.
+#[derive(Resource, Default)]
+struct ExtractedPanels(Vec<(Entity, AssetId<Image>)>);
+
+fn extract_panels(mut commands: Commands, panels: Extract<Query<(Entity, &IcedWorldPanel)>>) {
+    commands.insert_resource(ExtractedPanels(
+        panels
+            .iter()
+            .map(|(entity, panel)| (entity, panel.image.id()))
+            .collect(),
+    ));
+}
+
+// Render the interfaces of the panels into their textures, before the cameras sample them.
+fn present_panels(
+    panels: Res<ExtractedPanels>,
+    presented: Res<PresentedFrame>,
+    images: Res<RenderAssets<Image>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut backends: Local<HashMap<Entity, iced_wgpu::Backend>>,
+) {
+    render::present_to_images(
+        &panels.0,
+        &presented,
+        &images,
+        &render_device,
+        &render_queue,
+        &mut backends,
+    );
+}